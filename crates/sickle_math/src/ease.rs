@@ -1,4 +1,6 @@
 use std::f32::consts::PI;
+use std::hash::{Hash, Hasher};
+use std::mem::Discriminant;
 
 use bevy::reflect::Reflect;
 use serde::{Deserialize, Serialize};
@@ -11,7 +13,32 @@ const C5_F32: f32 = (2. * PI) / 4.5;
 const N1_F32: f32 = 7.5625;
 const D1_F32: f32 = 2.75;
 
-#[derive(Default, Copy, Clone, Debug, Hash, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+/// Springs are considered settled once their distance from the target stays below this fraction of the total
+/// distance.
+const SPRING_SETTLE_THRESHOLD: f32 = 0.001;
+/// Damping ratios within this distance of 1 are treated as critically damped.
+const SPRING_CRITICAL_EPSILON: f32 = 1e-4;
+
+/// Controls where the jumps of [`Ease::Steps`] occur, equivalent to CSS `steps()` jump terms.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum StepJump
+{
+    /// The first jump happens at the start of the animation (`jump-start`).
+    Start,
+    /// The last jump happens at the end of the animation (`jump-end`).
+    #[default]
+    End,
+    /// No jumps at the start or end; the first and last steps are held for a full step (`jump-none`).
+    None,
+    /// Jumps at both the start and end of the animation (`jump-both`).
+    Both,
+}
+
+/// Easing curves for animations.
+///
+/// Float parameters are compared and hashed by their bits, so `Ease` can implement `Eq` and `Hash`. This means
+/// `0.0` and `-0.0` are different, and `NaN` parameters equal themselves.
+#[derive(Default, Copy, Clone, Debug, Reflect, Serialize, Deserialize)]
 pub enum Ease
 {
     #[default]
@@ -46,6 +73,227 @@ pub enum Ease
     InBounce,
     OutBounce,
     InOutBounce,
+    /// A cubic bezier curve from `(0, 0)` to `(1, 1)` with control points `(x1, y1)` and `(x2, y2)`, equivalent
+    /// to CSS `cubic-bezier(x1, y1, x2, y2)`.
+    ///
+    /// `x1` and `x2` are clamped to `[0, 1]`. `y1` and `y2` can be outside `[0, 1]` to overshoot.
+    CubicBezier
+    {
+        x1: f32,
+        y1: f32,
+        x2: f32,
+        y2: f32,
+    },
+    /// Jumps between `count` equal steps, equivalent to CSS `steps(count, jump)`.
+    Steps
+    {
+        count: u32,
+        #[reflect(default)]
+        #[serde(default)]
+        jump: StepJump,
+    },
+    /// A damped spring that starts at rest and pulls towards the target.
+    ///
+    /// Under-damped springs (`damping < 2 * sqrt(stiffness * mass)`) overshoot before settling. When used in an
+    /// animation config, the animation's duration is the spring's settle time and the configured duration is
+    /// ignored (see [`Ease::settle_time`]).
+    ///
+    /// Springs with non-positive parameters fall back to linear easing.
+    Spring
+    {
+        stiffness: f32,
+        damping: f32,
+        /// Defaults to `1.0`.
+        #[reflect(default = "Ease::default_spring_mass")]
+        #[serde(default = "Ease::default_spring_mass")]
+        mass: f32,
+    },
+}
+
+impl Ease
+{
+    fn default_spring_mass() -> f32
+    {
+        1.
+    }
+
+    /// Gets the time in seconds it takes for a spring to settle at its target.
+    ///
+    /// Returns `None` for non-spring easings and springs with non-positive parameters.
+    pub fn settle_time(&self) -> Option<f32>
+    {
+        let Self::Spring { stiffness, damping, mass } = *self else { return None };
+        Spring::new(stiffness, damping, mass).map(|s| s.settle_time())
+    }
+
+    /// Gets the duration of an animation that uses this easing.
+    ///
+    /// Springs use their [settle time](Self::settle_time). Other easings use `configured`.
+    pub fn duration(&self, configured: f32) -> f32
+    {
+        self.settle_time().unwrap_or(configured)
+    }
+
+    /// Gets the variant and the bits of its parameters, for comparing and hashing.
+    fn key(&self) -> (Discriminant<Self>, [u32; 4])
+    {
+        let params = match *self {
+            Self::CubicBezier { x1, y1, x2, y2 } => [x1.to_bits(), y1.to_bits(), x2.to_bits(), y2.to_bits()],
+            Self::Steps { count, jump } => [count, jump as u32, 0, 0],
+            Self::Spring { stiffness, damping, mass } => [stiffness.to_bits(), damping.to_bits(), mass.to_bits(), 0],
+            _ => [0; 4],
+        };
+        (std::mem::discriminant(self), params)
+    }
+}
+
+impl PartialEq for Ease
+{
+    fn eq(&self, other: &Self) -> bool
+    {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Ease {}
+
+impl Hash for Ease
+{
+    fn hash<H: Hasher>(&self, state: &mut H)
+    {
+        self.key().hash(state);
+    }
+}
+
+/// Parameters of a damped harmonic oscillator moving from `0` to `1`.
+struct Spring
+{
+    /// Undamped angular frequency.
+    omega: f32,
+    /// Damping ratio.
+    zeta: f32,
+}
+
+impl Spring
+{
+    fn new(stiffness: f32, damping: f32, mass: f32) -> Option<Self>
+    {
+        if [stiffness, damping, mass].iter().any(|v| v.is_nan() || *v <= 0.) {
+            return None;
+        }
+        let omega = (stiffness / mass).sqrt();
+        let zeta = damping / (2. * (stiffness * mass).sqrt());
+        Some(Self { omega, zeta })
+    }
+
+    /// Roots of the characteristic equation of an over-damped spring, ordered slow then fast.
+    fn overdamped_roots(&self) -> (f32, f32)
+    {
+        let d = (self.zeta * self.zeta - 1.).sqrt();
+        (-self.omega * (self.zeta - d), -self.omega * (self.zeta + d))
+    }
+
+    /// Gets the spring's position at `time` seconds.
+    fn position(&self, time: f32) -> f32
+    {
+        let Self { omega, zeta } = *self;
+        if (zeta - 1.).abs() < SPRING_CRITICAL_EPSILON {
+            1. - (-omega * time).exp() * (1. + omega * time)
+        } else if zeta < 1. {
+            let omega_d = omega * (1. - zeta * zeta).sqrt();
+            let decay = (-zeta * omega * time).exp();
+            1. - decay * ((omega_d * time).cos() + (zeta * omega / omega_d) * (omega_d * time).sin())
+        } else {
+            let (r1, r2) = self.overdamped_roots();
+            1. - (r2 * (r1 * time).exp() - r1 * (r2 * time).exp()) / (r2 - r1)
+        }
+    }
+
+    /// Gets the time after which the spring stays within [`SPRING_SETTLE_THRESHOLD`] of its target.
+    ///
+    /// Computed from an exponential envelope `amplitude * e^(-rate * t)` that bounds the distance to the target.
+    fn settle_time(&self) -> f32
+    {
+        let Self { omega, zeta } = *self;
+        let (amplitude, rate) = if (zeta - 1.).abs() < SPRING_CRITICAL_EPSILON {
+            // (1 + wt)e^(-wt) <= (2 / sqrt(e))e^(-wt/2)
+            (2. / 1f32.exp().sqrt(), omega / 2.)
+        } else if zeta < 1. {
+            let omega_d = omega * (1. - zeta * zeta).sqrt();
+            ((1. + (zeta * omega / omega_d).powi(2)).sqrt(), zeta * omega)
+        } else {
+            let (r1, r2) = self.overdamped_roots();
+            ((r1.abs() + r2.abs()) / (r2.abs() - r1.abs()), r1.abs())
+        };
+        ((amplitude / SPRING_SETTLE_THRESHOLD).ln() / rate).max(0.)
+    }
+}
+
+fn cubic_bezier(x: f32, x1: f32, y1: f32, x2: f32, y2: f32) -> f32
+{
+    let x1 = x1.clamp(0., 1.);
+    let x2 = x2.clamp(0., 1.);
+
+    // Polynomial coefficients of the curve: B(s) = ((a * s + b) * s + c) * s
+    let cx = 3. * x1;
+    let bx = 3. * (x2 - x1) - cx;
+    let ax = 1. - cx - bx;
+    let cy = 3. * y1;
+    let by = 3. * (y2 - y1) - cy;
+    let ay = 1. - cy - by;
+
+    let sample_x = |s: f32| ((ax * s + bx) * s + cx) * s;
+    let sample_dx = |s: f32| (3. * ax * s + 2. * bx) * s + cx;
+
+    // Solve x(s) = x with Newton's method, falling back to bisection.
+    let mut s = x;
+    let mut solved = false;
+    for _ in 0..8 {
+        let err = sample_x(s) - x;
+        if err.abs() < 1e-6 {
+            solved = true;
+            break;
+        }
+        let dx = sample_dx(s);
+        if dx.abs() < 1e-6 {
+            break;
+        }
+        s -= err / dx;
+    }
+    if !solved || !(0. ..=1.).contains(&s) {
+        let (mut low, mut high) = (0f32, 1f32);
+        s = x;
+        for _ in 0..32 {
+            let value = sample_x(s);
+            if (value - x).abs() < 1e-6 {
+                break;
+            }
+            if value < x {
+                low = s;
+            } else {
+                high = s;
+            }
+            s = (low + high) / 2.;
+        }
+    }
+
+    ((ay * s + by) * s + cy) * s
+}
+
+fn steps(x: f32, count: u32, jump: StepJump) -> f32
+{
+    let count = count.max(1);
+    let mut step = (x * count as f32).floor();
+    if matches!(jump, StepJump::Start | StepJump::Both) {
+        step += 1.;
+    }
+    let jumps = match jump {
+        StepJump::Start | StepJump::End => count,
+        StepJump::None => count.saturating_sub(1).max(1),
+        StepJump::Both => count + 1,
+    } as f32;
+
+    step.clamp(0., jumps) / jumps
 }
 
 pub trait ValueEasing
@@ -206,6 +454,13 @@ impl ValueEasing for f32
                     (1. + (2. * x - 1.).ease(Ease::OutBounce)) / 2.
                 }
             }
+            Ease::CubicBezier { x1, y1, x2, y2 } => cubic_bezier(x, x1, y1, x2, y2),
+            Ease::Steps { count, jump } => steps(x, count, jump),
+            Ease::Spring { stiffness, damping, mass } => match Spring::new(stiffness, damping, mass) {
+                Some(_) if x >= 1. => 1.,
+                Some(spring) => spring.position(x * spring.settle_time()),
+                None => x,
+            },
         }
     }
 }
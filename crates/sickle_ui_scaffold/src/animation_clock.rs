@@ -0,0 +1,196 @@
+use std::time::Duration;
+
+use bevy::ecs::entity::EntityHashMap;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::DynamicStylePostUpdate;
+
+/// The time source of an [`AnimationClock`].
+#[derive(Reflect, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationTime
+{
+    /// Real time. Animations keep running while virtual time is paused.
+    #[default]
+    Real,
+    /// Virtual time. Animations pause and speed up or slow down with `Time<Virtual>`.
+    Virtual,
+}
+
+/// Component that controls the clock used by animations and interaction timers on an entity and its descendants.
+///
+/// Entities use the clock of the nearest entity with an `AnimationClock` in their ancestry (including
+/// themselves). Entities without one use unscaled real time.
+///
+/// For example, put `AnimationClock { time: AnimationTime::Virtual, scale: 1. }` on an in-world HUD so its
+/// animations freeze when the game is paused, while pause-menu animations keep using real time.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component)]
+pub struct AnimationClock
+{
+    /// The time source. Defaults to [`AnimationTime::Real`].
+    #[reflect(default)]
+    pub time: AnimationTime,
+    /// Multiplier applied to the time source's delta. Negative values are treated as zero.
+    ///
+    /// Defaults to `1.0`.
+    #[reflect(default = "AnimationClock::default_scale")]
+    pub scale: f32,
+}
+
+impl AnimationClock
+{
+    fn default_scale() -> f32
+    {
+        1.
+    }
+
+    /// Gets the time elapsed during the current tick according to this clock.
+    pub fn delta(&self, real: &Time<Real>, virt: &Time<Virtual>) -> Duration
+    {
+        let delta = match self.time {
+            AnimationTime::Real => real.delta(),
+            AnimationTime::Virtual => virt.delta(),
+        };
+        if self.scale == 1. {
+            return delta;
+        }
+        Duration::try_from_secs_f32(delta.as_secs_f32() * self.scale.max(0.)).unwrap_or_default()
+    }
+
+    /// Gets the clock that applies to an entity.
+    pub fn find_in_world(world: &World, entity: Entity) -> Self
+    {
+        let mut current = entity;
+        loop {
+            if let Some(clock) = world.get::<AnimationClock>(current) {
+                return *clock;
+            }
+            let Some(parent) = world.get::<Parent>(current) else { return Self::default() };
+            current = parent.get();
+        }
+    }
+
+    /// Gets the time elapsed during the current tick for animations on an entity.
+    pub fn delta_in_world(world: &World, entity: Entity) -> Duration
+    {
+        Self::find_in_world(world, entity).delta(world.resource::<Time<Real>>(), world.resource::<Time<Virtual>>())
+    }
+}
+
+impl Default for AnimationClock
+{
+    fn default() -> Self
+    {
+        Self { time: AnimationTime::Real, scale: Self::default_scale() }
+    }
+}
+
+/// Resource that caches the [`AnimationClock`] that applies to each entity, so ancestors don't need to be searched
+/// every tick.
+///
+/// The cache is cleared when an `AnimationClock` or the hierarchy changes. Changes are detected at the start of
+/// each frame and again in `PostUpdate` before animations are applied.
+#[derive(Resource, Default, Debug)]
+pub struct AnimationClockCache
+{
+    clocks: EntityHashMap<AnimationClock>,
+}
+
+impl AnimationClockCache
+{
+    /// Returns the number of cached entities.
+    pub fn len(&self) -> usize
+    {
+        self.clocks.len()
+    }
+
+    /// Returns `true` if no entities are cached.
+    pub fn is_empty(&self) -> bool
+    {
+        self.clocks.is_empty()
+    }
+
+    /// Gets the time elapsed during the current tick for animations on an entity, using the cached clock if
+    /// available.
+    ///
+    /// See [`AnimationClock::delta_in_world`].
+    pub fn delta_in_world(&mut self, world: &World, entity: Entity) -> Duration
+    {
+        let clock = *self
+            .clocks
+            .entry(entity)
+            .or_insert_with(|| AnimationClock::find_in_world(world, entity));
+        clock.delta(world.resource::<Time<Real>>(), world.resource::<Time<Virtual>>())
+    }
+}
+
+fn invalidate_animation_clocks(
+    mut cache: ResMut<AnimationClockCache>,
+    changed: Query<(), Or<(Changed<AnimationClock>, Changed<Parent>)>>,
+    mut removed_clocks: RemovedComponents<AnimationClock>,
+    mut removed_parents: RemovedComponents<Parent>,
+    mut removed_nodes: RemovedComponents<Node>,
+)
+{
+    // Read all removals so they aren't seen again next time.
+    // - Node removals catch despawned root nodes so the cache doesn't grow forever.
+    let removed = removed_clocks.read().count() + removed_parents.read().count() + removed_nodes.read().count();
+    if cache.clocks.is_empty() || (removed == 0 && changed.is_empty()) {
+        return;
+    }
+    cache.clocks.clear();
+}
+
+/// System parameter for looking up the [`AnimationClock`] of entities.
+///
+/// Resolved clocks are stored in the [`AnimationClockCache`].
+#[derive(SystemParam)]
+pub struct AnimationClocks<'w, 's>
+{
+    real: Res<'w, Time<Real>>,
+    virt: Res<'w, Time<Virtual>>,
+    cache: ResMut<'w, AnimationClockCache>,
+    clocks: Query<'w, 's, &'static AnimationClock>,
+    parents: Query<'w, 's, &'static Parent>,
+}
+
+impl AnimationClocks<'_, '_>
+{
+    /// Gets the clock that applies to an entity.
+    pub fn clock(&mut self, entity: Entity) -> AnimationClock
+    {
+        if self.clocks.is_empty() {
+            return AnimationClock::default();
+        }
+        if let Some(clock) = self.cache.clocks.get(&entity) {
+            return *clock;
+        }
+        let clock = std::iter::once(entity)
+            .chain(self.parents.iter_ancestors(entity))
+            .find_map(|e| self.clocks.get(e).ok())
+            .copied()
+            .unwrap_or_default();
+        self.cache.clocks.insert(entity, clock);
+        clock
+    }
+
+    /// Gets the time elapsed during the current tick for animations on an entity.
+    pub fn delta(&mut self, entity: Entity) -> Duration
+    {
+        let clock = self.clock(entity);
+        clock.delta(&self.real, &self.virt)
+    }
+}
+
+pub struct AnimationClockPlugin;
+
+impl Plugin for AnimationClockPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.init_resource::<AnimationClockCache>()
+            .add_systems(First, invalidate_animation_clocks)
+            .add_systems(PostUpdate, invalidate_animation_clocks.before(DynamicStylePostUpdate));
+    }
+}
@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+use smol_str::SmolStr;
+
+/// A lifecycle change of an animation, recorded while the animation is ticked.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AnimationLifecycle
+{
+    Started,
+    /// Contains the number of completed loop iterations.
+    LoopIterationFinished(u32),
+    Finished,
+}
+
+impl AnimationLifecycle
+{
+    /// Triggers the observer event that corresponds to this lifecycle change, targeting `entity`.
+    pub fn trigger(self, commands: &mut Commands, entity: Entity, name: Option<SmolStr>)
+    {
+        match self {
+            Self::Started => commands.trigger_targets(AnimationStarted { entity, name }, entity),
+            Self::LoopIterationFinished(iteration) => {
+                commands.trigger_targets(LoopIterationFinished { entity, name, iteration }, entity)
+            }
+            Self::Finished => commands.trigger_targets(AnimationFinished { entity, name }, entity),
+        }
+    }
+}
+
+/// Observer event triggered on an entity when one of its animated attributes starts animating.
+///
+/// For interaction animations this happens when an interaction (or enter) transition begins while the attribute
+/// is at rest, or when a new interaction retargets a running animation.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct AnimationStarted
+{
+    /// The entity whose attribute is animated.
+    pub entity: Entity,
+    /// The name of the animated attribute, if it has one.
+    pub name: Option<SmolStr>,
+}
+
+/// Observer event triggered on an entity when a looping animation completes a loop iteration.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct LoopIterationFinished
+{
+    /// The entity whose attribute is animated.
+    pub entity: Entity,
+    /// The name of the animated attribute, if it has one.
+    pub name: Option<SmolStr>,
+    /// The number of completed iterations. Interaction animations wrap this count at 255.
+    pub iteration: u32,
+}
+
+/// Observer event triggered on an entity when one of its animated attributes comes to rest.
+///
+/// Continuously looping animations never finish.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct AnimationFinished
+{
+    /// The entity whose attribute is animated.
+    pub entity: Entity,
+    /// The name of the animated attribute, if it has one.
+    pub name: Option<SmolStr>,
+}
@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 use bevy::ecs::system::EntityCommand;
 use bevy::prelude::*;
+use smol_str::SmolStr;
 
 use crate::*;
 
@@ -109,6 +110,8 @@ impl PartialEq for ResponsiveStyleAttribute
 pub struct AnimatedStyleAttribute
 {
     type_id: TypeId,
+    name: Option<SmolStr>,
+    exit: bool,
     attr: Arc<dyn AnimatedAttributeObject>,
 }
 
@@ -116,7 +119,31 @@ impl AnimatedStyleAttribute
 {
     pub fn new(type_id: TypeId, attr: Arc<dyn AnimatedAttributeObject>) -> Self
     {
-        Self { type_id, attr }
+        Self { type_id, name: None, exit: false, attr }
+    }
+
+    /// Sets the attribute name reported in animation lifecycle events (e.g. [`AnimationFinished`]).
+    pub fn with_name(mut self, name: Option<SmolStr>) -> Self
+    {
+        self.name = name;
+        self
+    }
+
+    pub fn name(&self) -> Option<&SmolStr>
+    {
+        self.name.as_ref()
+    }
+
+    /// Marks the attribute as an exit animation that should finish before its entity is despawned.
+    pub fn with_exit(mut self, exit: bool) -> Self
+    {
+        self.exit = exit;
+        self
+    }
+
+    pub fn is_exit(&self) -> bool
+    {
+        self.exit
     }
 
     pub fn initialize_enter(&mut self, entity: Entity, world: &World)
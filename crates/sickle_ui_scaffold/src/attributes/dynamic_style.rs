@@ -120,10 +120,13 @@ fn update_dynamic_style_on_flux_change(
     }
 }
 
-fn tick_dynamic_style_stopwatch(time: Res<Time<Real>>, mut q_stopwatches: Query<&mut DynamicStyleStopwatch>)
+fn tick_dynamic_style_stopwatch(
+    mut clocks: AnimationClocks,
+    mut q_stopwatches: Query<(Entity, &mut DynamicStyleStopwatch)>,
+)
 {
-    for mut style_stopwatch in &mut q_stopwatches {
-        style_stopwatch.0.tick(time.delta());
+    for (entity, mut style_stopwatch) in &mut q_stopwatches {
+        style_stopwatch.0.tick(clocks.delta(entity));
     }
 }
 
@@ -161,16 +164,19 @@ fn update_dynamic_style_on_stopwatch_change(
                 continue;
             };
 
+            let target = match context_attribute.target {
+                Some(context) => context,
+                None => entity,
+            };
+
             if let Some(stopwatch) = stopwatch {
                 controller.update(interaction, stopwatch.0.elapsed_secs());
+                for lifecycle in controller.drain_lifecycle() {
+                    lifecycle.trigger(&mut commands, target, attribute.name().cloned());
+                }
             }
 
             if style_changed || controller.dirty() {
-                let target = match context_attribute.target {
-                    Some(context) => context,
-                    None => entity,
-                };
-
                 // Initialize the attribute's enter_ref value immediately before the first time we apply the
                 // attribute.
                 // - We need to do this here so the initialized value gets saved in the DynamicStyle component for
@@ -390,6 +396,19 @@ impl DynamicStyle
             .any(|csa| csa.attribute.is_animated())
     }
 
+    /// Returns `true` if an attribute marked as an exit animation is still animating.
+    ///
+    /// See [`AnimatedStyleAttribute::with_exit`].
+    pub fn is_exit_animating(&self) -> bool
+    {
+        self.attributes.iter().any(|csa| match &csa.attribute {
+            DynamicStyleAttribute::Animated { attribute, controller } => {
+                attribute.is_exit() && controller.is_animating()
+            }
+            _ => false,
+        })
+    }
+
     /// Extracts the inner attribute buffer.
     ///
     /// Allows re-using the buffer via [`Self::copy_from`]. See [`StyleBuilder::convert_to_iter_with_buffers`].
@@ -1,4 +1,5 @@
 use bevy::utils::default;
+use smallvec::SmallVec;
 
 use crate::*;
 
@@ -100,6 +101,10 @@ pub struct DynamicStyleController
     enter_state: EnterState,
     /// True the first tick entering state changed from Unspecified to Entering.
     just_started_entering: bool,
+    /// The target style of the running animation, or `None` if the attribute is at rest.
+    running: Option<InteractionStyle>,
+    /// Lifecycle changes recorded by the last update.
+    lifecycle: SmallVec<[AnimationLifecycle; 2]>,
 }
 
 impl Default for DynamicStyleController
@@ -112,6 +117,8 @@ impl Default for DynamicStyleController
             dirty: Default::default(),
             enter_state: EnterState::Unspecified,
             just_started_entering: false,
+            running: None,
+            lifecycle: SmallVec::new(),
         }
     }
 }
@@ -123,6 +130,58 @@ impl DynamicStyleController
         Self { animation, current_state: starting_state, ..default() }
     }
 
+    /// Returns `true` if an animation toward `target_style` is still in progress at `elapsed`.
+    fn is_running(
+        target_style: InteractionStyle,
+        tween: Option<&AnimationConfig>,
+        loop_tween: Option<&LoopedAnimationConfig>,
+        mut elapsed: f32,
+    ) -> bool
+    {
+        if let Some(tween) = tween {
+            let length = tween.delay + tween.effective_duration();
+            if elapsed < length {
+                return true;
+            }
+            elapsed -= length;
+        }
+
+        let (Some(loop_tween), Some(_)) = (loop_tween, target_style.alt()) else { return false };
+        let duration = loop_tween.effective_duration();
+        if duration <= 0. {
+            return false;
+        }
+
+        match loop_tween.loop_type {
+            AnimationLoop::None => false,
+            AnimationLoop::Continous | AnimationLoop::PingPongContinous => true,
+            AnimationLoop::Times(n, _) | AnimationLoop::PingPong(n) => {
+                elapsed < loop_tween.start_delay + (duration + loop_tween.loop_gap) * n as f32
+            }
+        }
+    }
+
+    fn record_lifecycle(&mut self, running: Option<InteractionStyle>, new_state: &AnimationState)
+    {
+        self.lifecycle.clear();
+        match (self.running, running) {
+            (None, Some(_)) => self.lifecycle.push(AnimationLifecycle::Started),
+            (Some(prev), Some(next)) if prev != next => self.lifecycle.push(AnimationLifecycle::Started),
+            (Some(_), next) => {
+                if new_state.iteration() != self.current_state.iteration() {
+                    self.lifecycle.push(AnimationLifecycle::LoopIterationFinished(
+                        new_state.iteration() as u32,
+                    ));
+                }
+                if next.is_none() {
+                    self.lifecycle.push(AnimationLifecycle::Finished);
+                }
+            }
+            (None, None) => (),
+        }
+        self.running = running;
+    }
+
     pub fn update(&mut self, flux_interaction: &FluxInteraction, mut elapsed: f32)
     {
         // TODO: `enter` animation is currently played when a style animation different from
@@ -156,7 +215,7 @@ impl DynamicStyleController
         // This only activates post-entering.
         if target_style == InteractionStyle::Cancel {
             if let Some(cancel_tween) = tween.clone() {
-                let cancel_tween_length = cancel_tween.duration + cancel_tween.delay;
+                let cancel_tween_length = cancel_tween.effective_duration() + cancel_tween.delay;
 
                 if elapsed >= cancel_tween_length {
                     target_style = InteractionStyle::Idle;
@@ -169,15 +228,18 @@ impl DynamicStyleController
             }
         }
 
+        let running = Self::is_running(target_style, tween.as_ref(), loop_tween.as_ref(), elapsed)
+            .then_some(target_style);
         let new_state = self
             .current_state
             .tick(target_style, tween.clone(), loop_tween, elapsed);
+        self.record_lifecycle(running, &new_state);
 
         // Update entering state post tick, to allow Hold to occur
         self.just_started_entering = false;
         self.enter_state = match self.animation.enter_idle_with.clone() {
             Some(tween) => {
-                if elapsed < (tween.duration + tween.delay) {
+                if elapsed < (tween.effective_duration() + tween.delay) {
                     if matches!(self.enter_state, EnterState::Unspecified) {
                         self.just_started_entering = true;
                     }
@@ -215,16 +277,30 @@ impl DynamicStyleController
         self.enter_state == EnterState::Entered
     }
 
+    /// Returns `true` if an animation is in progress, or if the enter animation hasn't started yet.
+    pub fn is_animating(&self) -> bool
+    {
+        self.running.is_some()
+            || (self.enter_state == EnterState::Unspecified && self.animation.enter_idle_with.is_some())
+    }
+
     /// Returns `true` after the first time the controller is updated if the controller has not yet fully entered.
     pub fn just_started_entering(&self) -> bool
     {
         self.just_started_entering
     }
 
+    /// Drains the lifecycle changes recorded by the last update.
+    pub fn drain_lifecycle(&mut self) -> impl Iterator<Item = AnimationLifecycle> + '_
+    {
+        self.lifecycle.drain(..)
+    }
+
     pub fn copy_state_from(&mut self, other: &DynamicStyleController)
     {
         self.current_state = other.current_state().clone();
         self.enter_state = other.enter_state;
         self.dirty = other.dirty;
+        self.running = other.running;
     }
 }
@@ -1,3 +1,4 @@
+mod animation_events;
 mod custom_attrs;
 mod dynamic_style;
 mod dynamic_style_attribute;
@@ -6,6 +7,7 @@ mod style_animation;
 mod traits;
 mod ui_context;
 
+pub use animation_events::*;
 pub use custom_attrs::*;
 pub use dynamic_style::*;
 pub use dynamic_style_attribute::*;
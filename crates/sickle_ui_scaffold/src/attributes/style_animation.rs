@@ -47,7 +47,7 @@ impl From<&FluxInteraction> for InteractionStyle
 
 impl InteractionStyle
 {
-    fn alt(&self) -> Option<InteractionStyle>
+    pub(crate) fn alt(&self) -> Option<InteractionStyle>
     {
         match self {
             InteractionStyle::Idle => InteractionStyle::IdleAlt.into(),
@@ -128,6 +128,8 @@ pub enum AnimationLoop
 pub struct AnimationConfig
 {
     /// Defaults to zero seconds.
+    ///
+    /// Ignored if `ease` is [`Ease::Spring`], which uses the spring's settle time.
     #[reflect(default)]
     pub duration: f32,
     /// Defaults to linear easing.
@@ -144,12 +146,20 @@ impl AnimationConfig
     {
         AnimationConfig { duration, ease, delay }
     }
+
+    /// Gets the duration of the animation, accounting for easings that derive their own duration.
+    pub fn effective_duration(&self) -> f32
+    {
+        self.ease.duration(self.duration)
+    }
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Reflect, Serialize, Deserialize)]
 pub struct LoopedAnimationConfig
 {
     /// Defaults to zero duration.
+    ///
+    /// Ignored if `ease` is [`Ease::Spring`], which uses the spring's settle time.
     #[reflect(default)]
     pub duration: f32,
     /// Defaults to linear easing.
@@ -179,6 +189,12 @@ impl LoopedAnimationConfig
         LoopedAnimationConfig { duration, ease, start_delay, loop_gap, loop_type }
     }
 
+    /// Gets the duration of one loop iteration, accounting for easings that derive their own duration.
+    pub fn effective_duration(&self) -> f32
+    {
+        self.ease.duration(self.duration)
+    }
+
     fn is_pingpong(&self) -> bool
     {
         match self.loop_type {
@@ -264,7 +280,7 @@ macro_rules! state_animation_setter {
             loop_gap: f32,
             loop_type: AnimationLoop,
         ) -> &mut Self {
-            if duration <= 0. {
+            if ease.duration(duration) <= 0. {
                 warn!("Invalid animation duration used: {}", duration);
             }
 
@@ -302,7 +318,7 @@ macro_rules! state_from_animation_setter {
             config: impl Into<Option<LoopedAnimationConfig>>,
         ) -> &mut Self {
             if let Some(config) = config.into() {
-                if config.duration <= 0. {
+                if config.effective_duration() <= 0. {
                     warn!("Invalid animation duration used: {}", config.duration);
                 }
                 self.$setter = Some(config);
@@ -433,7 +449,7 @@ impl AnimationSettings
             return StopwatchLock::None;
         };
 
-        StopwatchLock::Duration(Duration::from_secs_f32(tween.delay + tween.duration))
+        StopwatchLock::Duration(Duration::from_secs_f32(tween.delay + tween.effective_duration()))
     }
 
     pub fn state_lock_duration(tween: Option<LoopedAnimationConfig>) -> StopwatchLock
@@ -442,17 +458,18 @@ impl AnimationSettings
             return StopwatchLock::None;
         };
 
+        let duration = tween.effective_duration();
         match tween.loop_type {
             AnimationLoop::None => {
-                StopwatchLock::Duration(Duration::from_secs_f32(tween.start_delay + tween.duration))
+                StopwatchLock::Duration(Duration::from_secs_f32(tween.start_delay + duration))
             }
             AnimationLoop::Continous => StopwatchLock::Infinite,
             AnimationLoop::Times(n, _) => StopwatchLock::Duration(Duration::from_secs_f32(
-                tween.start_delay + (tween.duration * n as f32) + (tween.loop_gap * n as f32),
+                tween.start_delay + (duration * n as f32) + (tween.loop_gap * n as f32),
             )),
             AnimationLoop::PingPongContinous => StopwatchLock::Infinite,
             AnimationLoop::PingPong(n) => StopwatchLock::Duration(Duration::from_secs_f32(
-                tween.start_delay + (tween.duration * n as f32) + (tween.loop_gap * n as f32),
+                tween.start_delay + (duration * n as f32) + (tween.loop_gap * n as f32),
             )),
         }
     }
@@ -486,6 +503,12 @@ impl AnimationState
         &self.result
     }
 
+    /// Gets the current loop iteration. Wraps at 255.
+    pub fn iteration(&self) -> u8
+    {
+        self.iteration
+    }
+
     pub fn tick(
         &self,
         target_style: InteractionStyle,
@@ -504,7 +527,7 @@ impl AnimationState
         };
 
         let delay = tween.delay;
-        let tween_time = tween.duration.max(0.);
+        let tween_time = tween.effective_duration().max(0.);
         let ease = tween.ease;
 
         // Includes elapsed == 0.
@@ -555,13 +578,14 @@ impl AnimationState
     ) -> AnimationState
     {
         let start_delay = tween.start_delay;
-        if tween.loop_type == AnimationLoop::None || elapsed < start_delay || tween.duration <= 0. {
+        let duration = tween.effective_duration();
+        if tween.loop_type == AnimationLoop::None || elapsed < start_delay || duration <= 0. {
             return AnimationState { result: AnimationResult::Hold(target_style), iteration: 0 };
         }
         elapsed -= start_delay;
 
         let loop_gap = tween.loop_gap;
-        let iteration = (elapsed / (tween.duration + loop_gap)).floor() as usize;
+        let iteration = (elapsed / (duration + loop_gap)).floor() as usize;
         let even = iteration % 2 == 0;
 
         match tween.loop_type {
@@ -590,8 +614,8 @@ impl AnimationState
             _ => (),
         }
 
-        let offset = elapsed % (tween.duration + loop_gap);
-        if loop_gap > 0. && offset > tween.duration {
+        let offset = elapsed % (duration + loop_gap);
+        if loop_gap > 0. && offset > duration {
             // We are in the pause-gap
             let hold_style = match tween.is_pingpong() {
                 true => match even {
@@ -606,7 +630,7 @@ impl AnimationState
                 iteration: (iteration % 255) as u8,
             }
         } else {
-            let tween_ratio = (offset / tween.duration).clamp(0., 1.).ease(tween.ease);
+            let tween_ratio = (offset / duration).clamp(0., 1.).ease(tween.ease);
             let from = match tween.is_pingpong() {
                 true => match even {
                     true => target_style,
@@ -6,6 +6,8 @@ use bevy::prelude::*;
 use bevy::time::Stopwatch;
 use bevy::utils::HashMap;
 
+use crate::AnimationClocks;
+
 pub struct FluxInteractionPlugin;
 
 impl Plugin for FluxInteractionPlugin
@@ -281,7 +283,7 @@ fn update_prev_interaction(mut q_interaction: Query<(&mut PrevInteraction, &Inte
 
 fn tick_flux_interaction_stopwatch(
     config: Res<FluxInteractionConfig>,
-    time: Res<Time<Real>>,
+    mut clocks: AnimationClocks,
     mut q_stopwatches: Query<(
         Entity,
         &mut FluxInteractionStopwatch,
@@ -305,6 +307,6 @@ fn tick_flux_interaction_stopwatch(
             commands.entity(entity).remove::<FluxInteractionStopwatch>();
         }
 
-        stopwatch.0.tick(time.delta());
+        stopwatch.0.tick(clocks.delta(entity));
     }
 }
@@ -1,3 +1,4 @@
+mod animation_clock;
 mod attributes;
 mod builder_ext;
 mod flux_interaction;
@@ -6,6 +7,7 @@ mod ui_commands;
 mod ui_style;
 mod ui_utils;
 
+pub use animation_clock::*;
 pub use attributes::*;
 pub use builder_ext::*;
 pub use flux_interaction::*;
@@ -3,10 +3,8 @@ use std::borrow::Cow;
 use bevy::prelude::*;
 use bevy::window::SystemCursorIcon;
 use bevy::winit::cursor::{CursorIcon, CustomCursor};
-use smallvec::SmallVec;
 
 use crate::prelude::*;
-use crate::sickle::*;
 
 //-------------------------------------------------------------------------------------------------------------------
 
@@ -254,11 +252,11 @@ impl ResponsiveAttribute for TempCursor {}
 pub struct ResponsiveCursor
 {
     /// Specifies which [`PseudoStates`](PseudoState) the entity must be in for these cursor settings to be
-    /// active.
+    /// active. See [`PseudoStateSelector`].
     ///
     /// Only used if this instruction is applied to an entity with [`ControlRoot`]/[`ControlMember`].
     #[reflect(default)]
-    pub state: Option<SmallVec<[PseudoState; 3]>>,
+    pub state: Option<PseudoStateSelector>,
     /// The cursor to display when the entity is hovered.
     #[reflect(default)]
     pub hover: Option<LoadableCursor>,
@@ -2,7 +2,6 @@ use bevy::prelude::TransformSystem::TransformPropagate;
 use bevy::prelude::*;
 use bevy::ui::UiSystem;
 use bevy_cobweb::prelude::*;
-use smallvec::SmallVec;
 use smol_str::SmolStr;
 
 use crate::prelude::*;
@@ -691,7 +690,7 @@ impl Instruction for Slider
             if let SliderPress::Animate(enter_idle_with) = self.bar_press.clone() {
                 let animation = Animated::<SliderZoom> {
                     name: Some(SmolStr::new_static(SLIDER_ZOOM_ATTR)),
-                    state: Some(SmallVec::from_elem(SLIDER_ZOOM_PSEUDO_STATE.clone(), 1)),
+                    state: Some(SLIDER_ZOOM_PSEUDO_STATE.clone().into()),
                     enter_idle_with: Some(enter_idle_with),
                     idle: SliderValue::default(), // We override the idle value as needed.
                     delete_on_entered: true,
@@ -58,7 +58,7 @@ pub struct Tooltip
 {
     /// State the source must have to display this tooltip.
     #[reflect(Default)]
-    pub state: Option<SmallVec<[PseudoState; 3]>>,
+    pub state: Option<PseudoStateSelector>,
 
     /// The anchor point on the reference node for the tooltip.
     ///
@@ -153,11 +153,8 @@ Events:
 
 - target entity: WithTooltip instruction
     - on_pointer_enter, on_pressed: spawn tooltip, add HasTooltip component to hovered entity
-    - on_pointer_leave, on_released, on_press_canceled: remove HasTooltip component, add "Dying" pseudostate
-    (activates Animated<PropagateOpacity>), also add DespawnOnOpacityOut component which waits for PropagateOpacity to
-    reach zero then despawns
-        - DespawnOnOpacityOut checker system should be ordered in Last to make sure PropagateOpacity has a chance to be
-        inserted by the flux framework
+    - on_pointer_leave, on_released, on_press_canceled: remove HasTooltip component, call despawn_animated() on the
+    tooltip (adds "Dying" pseudostate to activate Animated<PropagateOpacity>, then despawns once it finishes)
 - tooltip
     - has TooltipParent component
     - has WindowClamp component
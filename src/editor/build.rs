@@ -1,6 +1,8 @@
+use std::any::TypeId;
 use std::sync::Arc;
 
 use bevy::prelude::*;
+use bevy::reflect::serde::TypedReflectDeserializer;
 use bevy::reflect::TypeRegistry;
 use bevy::render::camera::RenderTarget;
 use bevy::window::{EnabledButtons, PrimaryWindow, WindowRef, WindowResolution, WindowTheme};
@@ -8,6 +10,7 @@ use bevy_cobweb::prelude::*;
 use serde::de::DeserializeSeed;
 
 use super::*;
+use crate::builtin::widgets::slider::SliderValue;
 use crate::prelude::*;
 use crate::sickle::*;
 
@@ -18,49 +21,83 @@ struct EditorFileSelection(Option<CobFile>);
 
 //-------------------------------------------------------------------------------------------------------------------
 
-fn build_widgets<'a>(
-    l: &mut LoadedScene<'a, UiBuilder<'a, Entity>>,
+fn build_loadable_content(
+    c: &mut Commands,
+    s: &mut SceneLoader,
+    content_entity: Entity,
     widgets: &CobWidgetRegistry,
+    deserializer: TypedReflectDeserializer,
     file_hash: CobFileHash,
     scene_ref: SceneRef,
-    longname: &'static str,
     shortname: &'static str,
-    loadable: Box<dyn PartialReflect + 'static>,
-    death_signal: DeathSignal,
+    loadable: &CobLoadable,
 )
 {
-    // Check for loadable widget
-    if let Some(spawn_fn) = widgets.get(longname) {
-        let content_entity = l.id();
-        let editor_ref = CobEditorRef {
-            file_hash,
-            scene_ref,
-            loadable_name: shortname,
-            structure_path: ReflectStructurePath { path: Arc::from([]) },
-            death_signal,
-        };
-        let (loader, builder) = l.inner();
-        if !(spawn_fn)(
-            builder.commands(),
-            loader,
-            content_entity,
-            &editor_ref,
-            loadable.as_ref(),
-        ) {
-            l.load_scene(("editor.frame", "destructure_unsupported"));
-        }
-
+    // TODO: reflection can fail because of internal constants; we may want those to be editable/inspectable,
+    // but it requires destructuring the CobLoadable representation
+    let Ok(reflected) = deserializer.deserialize(loadable) else {
+        c.ui_builder(content_entity)
+            .load_scene(("editor.frame", "reflect_fail"), s);
         return;
-    }
+    };
+
+    // Replacing the signaler kills widgets from previous builds.
+    let (signaler, death_signal) = DeathSignaler::new();
+    c.entity(content_entity).insert(signaler);
+
+    let editor_ref = CobEditorRef {
+        file_hash,
+        scene_ref,
+        loadable_name: shortname,
+        structure_path: ReflectStructurePath { path: Arc::from([]) },
+        constant: None,
+        death_signal,
+        widgets_entity: content_entity,
+    };
+    let ctx = EditorWidgetContext { widgets };
+    spawn_value_widget(c, s, content_entity, &ctx, &editor_ref, reflected.as_ref(), None);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Adds buttons for moving and removing a scene node or loadable.
+fn build_structure_controls<'a>(
+    l: &mut LoadedScene<'a, UiBuilder<'a, Entity>>,
+    on_move: impl Fn(&mut Commands, isize) + Clone + Send + Sync + 'static,
+    on_remove: impl Fn(&mut Commands) + Send + Sync + 'static,
+)
+{
+    l.load_scene_and_edit(("editor.frame", "structure_controls"), |l| {
+        let on_move_up = on_move.clone();
+        l.get("up")
+            .on_pressed(move |mut c: Commands| (on_move_up)(&mut c, -1));
+        l.get("down")
+            .on_pressed(move |mut c: Commands| (on_move)(&mut c, 1));
+        l.get("remove")
+            .on_pressed(move |mut c: Commands| (on_remove)(&mut c));
+    });
+}
 
-    // Fallback
-    l.load_scene(("editor.frame", "destructure_unsupported"));
+//-------------------------------------------------------------------------------------------------------------------
 
-    // TODO: Destructure and look for widgets for internal values
-    // - TODO: If no widget found for an enum, provide a drop-down.
-    // - TODO: If no widget found for a primitive type, serialize it to COB and display it directly.
-    // - TODO: If no widget found for a set, display "<cannot destructure sets>" (you can only add/remove entries,
-    //   TODO)
+/// Adds a labeled text field for entering the name of a new scene node or loadable.
+fn build_add_entry<'a>(
+    l: &mut LoadedScene<'a, UiBuilder<'a, Entity>>,
+    label: &'static str,
+    submit: impl Fn(&mut Commands, String) + Send + Sync + 'static,
+)
+{
+    l.load_scene_and_edit(("editor.frame", "add_entry"), |l| {
+        l.get("label")
+            .update(move |id: UpdateId, mut e: TextEditor| {
+                write_text!(e, *id, "{}", label);
+            });
+        l.edit("field", |l| {
+            let field_entity = l.id();
+            let (s, builder) = l.inner();
+            spawn_text_field(builder.commands(), s, field_entity, String::default(), submit);
+        });
+    });
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -77,7 +114,7 @@ fn build_loadable<'a>(
 {
     // Look up loadable type
     let name = loadable.id.to_canonical(None);
-    let Some((deserializer, _, longname, shortname)) = get_deserializer(registry, name.as_str(), loadables) else {
+    let Some((deserializer, _, _, shortname)) = get_deserializer(registry, name.as_str(), loadables) else {
         l.load_scene(("editor.frame", "unsupported"));
         return;
     };
@@ -91,22 +128,75 @@ fn build_loadable<'a>(
             });
 
         // Set the content.
-        // TODO: reflection can fail because of internal constants; we may want those to be editable/inspectable,
-        // but it requires destructuring the CobLoadable representation
-        match deserializer.deserialize(loadable) {
-            Ok(reflected) => {
-                l.edit("content", |l| {
-                    let (signaler, signal) = DeathSignaler::new();
-                    l.insert(signaler);
-
-                    build_widgets(l, widgets, file_hash, scene_ref, longname, shortname, reflected, signal);
-                });
-            }
-            Err(_) => {
-                l.get("content")
-                    .load_scene(("editor.frame", "reflect_fail"));
-            }
-        }
+        l.edit("content", |l| {
+            let content_entity = l.id();
+
+            // Rebuild widgets when the loadable's structure is edited.
+            let scene_ref_clone = scene_ref.clone();
+            l.on_event::<RebuildEditorWidgets>().r(
+                move |//
+                    mut c: Commands,
+                    mut s: ResMut<SceneLoader>,
+                    types: Res<AppTypeRegistry>,
+                    loadables: Res<LoadableRegistry>,
+                    widgets: Res<CobWidgetRegistry>,
+                    mut editor: ResMut<CobEditor>//
+                | {
+                    c.entity(content_entity).despawn_descendants();
+
+                    let SceneFile::File(file) = &scene_ref_clone.file else { return };
+                    let Some(file_data) = editor.get_file_mut(file) else { return };
+                    // The file view will be rebuilt if the hash changed.
+                    if file_data.last_save_hash != file_hash {
+                        return;
+                    }
+                    let Some(loadable) = get_targeted_at(&mut file_data.data, &scene_ref_clone, shortname) else {
+                        return;
+                    };
+
+                    let registry = types.read();
+                    let Some((deserializer, ..)) = get_deserializer(&registry, shortname, &loadables) else { return };
+                    build_loadable_content(
+                        &mut c,
+                        &mut s,
+                        content_entity,
+                        &widgets,
+                        deserializer,
+                        file_hash,
+                        scene_ref_clone.clone(),
+                        shortname,
+                        loadable,
+                    );
+                },
+            );
+
+            let (s, builder) = l.inner();
+            build_loadable_content(
+                builder.commands(),
+                s,
+                content_entity,
+                widgets,
+                deserializer,
+                file_hash,
+                scene_ref.clone(),
+                shortname,
+                loadable,
+            );
+        });
+
+        // Add structural controls.
+        l.edit("controls", |l| {
+            let move_ref = scene_ref.clone();
+            build_structure_controls(
+                l,
+                move |c: &mut Commands, offset: isize| {
+                    c.queue(MoveLoadable { scene_ref: move_ref.clone(), loadable_name: shortname.into(), offset });
+                },
+                move |c: &mut Commands| {
+                    c.queue(RemoveLoadable { scene_ref: scene_ref.clone(), loadable_name: shortname.into() });
+                },
+            );
+        });
     });
 }
 
@@ -127,12 +217,92 @@ fn build_scene_layer<'a>(
 
     // Build view
     l.load_scene_and_edit(("editor.frame", "scene_node"), |l| {
+        // Sync with tree state.
+        let header = l.get("header").id();
+        let fold_text = l.get("header::fold::text").id();
+        let content = l.get("content").id();
+        let add = l.get("add").id();
+        let tree_ref = scene_ref.clone();
+        l.update_on(
+            broadcast::<EditorTreeChanged>(),
+            move |//
+                _: UpdateId,
+                mut c: Commands,
+                mut e: TextEditor,
+                ps: PseudoStateParam,
+                tree: Res<EditorTreeState>//
+            | {
+                match tree.is_selected(&tree_ref) {
+                    true => ps.try_select(&mut c, header),
+                    false => ps.try_deselect(&mut c, header),
+                };
+
+                let folded = tree.is_folded(&tree_ref);
+                let display = if folded { DisplayControl::Hide } else { DisplayControl::Show };
+                c.entity(content).insert(display);
+                c.entity(add).insert(display);
+                write_text!(e, fold_text, "{}", if folded { "+" } else { "-" });
+            },
+        );
+
+        // Toggle folding.
+        let fold_ref = scene_ref.clone();
+        l.get("header::fold")
+            .on_pressed(move |mut c: Commands, mut tree: ResMut<EditorTreeState>| {
+                tree.toggle_folded(&fold_ref);
+                c.react().broadcast(EditorTreeChanged);
+            });
+
         // Set node name.
-        let ref_path = scene_ref.path.clone();
-        l.get("name")
-            .update(move |id: UpdateId, mut e: TextEditor| {
-                write_text!(e, *id, "\"{}\"", ref_path.iter().rev().next().unwrap());
+        // - Root scenes can't be renamed or moved since they may be referenced by name in other files.
+        let is_root = scene_ref.path.len() == 1;
+        l.edit("header::name", |l| {
+            let name = layer.name.to_string();
+            if is_root {
+                l.load_scene_and_edit(("editor.frame", "scene_node_name"), |l| {
+                    l.update(move |id: UpdateId, mut e: TextEditor| {
+                        write_text!(e, *id, "\"{}\"", name.as_str());
+                    });
+                });
+                return;
+            }
+
+            let name_entity = l.id();
+            let rename_ref = scene_ref.clone();
+            let (s, builder) = l.inner();
+            spawn_text_field(builder.commands(), s, name_entity, name, move |c: &mut Commands, name: String| {
+                c.queue(RenameSceneNode { scene_ref: rename_ref.clone(), name });
+            });
+        });
+
+        // Add structural controls.
+        if !is_root {
+            l.edit("header::controls", |l| {
+                let move_ref = scene_ref.clone();
+                let remove_ref = scene_ref.clone();
+                build_structure_controls(
+                    l,
+                    move |c: &mut Commands, offset: isize| {
+                        c.queue(MoveSceneNode { scene_ref: move_ref.clone(), offset });
+                    },
+                    move |c: &mut Commands| {
+                        c.queue(RemoveSceneNode { scene_ref: remove_ref.clone() });
+                    },
+                );
+            });
+        }
+
+        // Add inputs for inserting loadables and child nodes.
+        l.edit("add", |l| {
+            let loadable_ref = scene_ref.clone();
+            build_add_entry(l, "+loadable", move |c: &mut Commands, name: String| {
+                c.queue(InsertLoadable { scene_ref: loadable_ref.clone(), loadable_name: name });
+            });
+            let node_ref = scene_ref.clone();
+            build_add_entry(l, "+node", move |c: &mut Commands, name: String| {
+                c.queue(InsertSceneNode { parent: node_ref.clone(), name });
             });
+        });
 
         // Add entries.
         l.edit("content", |l| {
@@ -163,24 +333,262 @@ fn build_scene_layer<'a>(
 
 //-------------------------------------------------------------------------------------------------------------------
 
+fn build_constant_content(
+    c: &mut Commands,
+    s: &mut SceneLoader,
+    content_entity: Entity,
+    registry: &TypeRegistry,
+    widgets: &CobWidgetRegistry,
+    file_hash: CobFileHash,
+    file: &CobFile,
+    constant: &EditorConstantRef,
+    reflected: Box<dyn PartialReflect>,
+)
+{
+    // Replacing the signaler kills widgets from previous builds.
+    let (signaler, death_signal) = DeathSignaler::new();
+    c.entity(content_entity).insert(signaler);
+
+    let editor_ref = CobEditorRef {
+        file_hash,
+        scene_ref: SceneRef { file: file.clone().into(), path: ScenePath::new("#defs") },
+        loadable_name: registry
+            .get_type_info(constant.type_id)
+            .map(|i| i.type_path_table().short_path())
+            .unwrap_or_default(),
+        structure_path: ReflectStructurePath { path: Arc::from([]) },
+        constant: Some(constant.clone()),
+        death_signal,
+        widgets_entity: content_entity,
+    };
+    let ctx = EditorWidgetContext { widgets };
+    spawn_value_widget(c, s, content_entity, &ctx, &editor_ref, reflected.as_ref(), None);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Builds a constant with widgets for editing its value and a button for listing its usages.
+///
+/// If `type_id` is set and the value can be reflected as that type, then the value is edited with the same widgets
+/// as loadables. Otherwise it is edited as raw COB text.
+fn build_constant<'a>(
+    l: &mut LoadedScene<'a, UiBuilder<'a, Entity>>,
+    registry: &TypeRegistry,
+    widgets: &CobWidgetRegistry,
+    file_hash: CobFileHash,
+    file: CobFile,
+    def: &CobConstantDef,
+    type_id: Option<TypeId>,
+)
+{
+    let name = def.name.name.clone();
+    let mut bytes = Vec::default();
+    if let Err(err) = def.value.write_to(&mut DefaultRawSerializer::new(&mut bytes)) {
+        tracing::warn!("failed serializing constant ${} in {:?} for the editor: {err:?}", name, file);
+        return;
+    }
+    let value = String::from_utf8_lossy(&bytes).trim().to_string();
+    let reflected = type_id.and_then(|type_id| {
+        let reflected = reflect_constant(registry, type_id, &def.value).ok()?;
+        Some((type_id, reflected))
+    });
+
+    l.load_scene_and_edit(("editor.frame", "constant"), |l| {
+        // Set the constant's name.
+        let display_name = name.clone();
+        l.get("row::name")
+            .update(move |id: UpdateId, mut e: TextEditor| {
+                write_text!(e, *id, "${}", display_name.as_str());
+            });
+
+        // Edit the value.
+        // - Constants are untyped, so widgets are only used if the constant's type was inferred from its usages.
+        l.edit("row::value", |l| {
+            let value_entity = l.id();
+
+            let Some((type_id, reflected)) = reflected else {
+                let edit_file = file.clone();
+                let edit_name = name.clone();
+                let (s, builder) = l.inner();
+                spawn_text_field(builder.commands(), s, value_entity, value, move |c: &mut Commands, value: String| {
+                    c.queue(EditConstant { file: edit_file.clone(), name: edit_name.to_string(), value });
+                });
+                return;
+            };
+            let constant = EditorConstantRef { name: name.clone(), type_id };
+
+            // Rebuild widgets when the constant's structure is edited.
+            let rebuild_file = file.clone();
+            let rebuild_constant = constant.clone();
+            l.on_event::<RebuildEditorWidgets>().r(
+                move |//
+                    mut c: Commands,
+                    mut s: ResMut<SceneLoader>,
+                    types: Res<AppTypeRegistry>,
+                    widgets: Res<CobWidgetRegistry>,
+                    mut editor: ResMut<CobEditor>//
+                | {
+                    c.entity(value_entity).despawn_descendants();
+
+                    let Some(file_data) = editor.get_file_mut(&rebuild_file) else { return };
+                    // The file view will be rebuilt if the hash changed.
+                    if file_data.last_save_hash != file_hash {
+                        return;
+                    }
+                    let Some(def) = get_constant_def_mut(&mut file_data.data, &rebuild_constant.name) else {
+                        return;
+                    };
+
+                    let registry = types.read();
+                    let Ok(reflected) = reflect_constant(&registry, type_id, &def.value) else { return };
+                    build_constant_content(
+                        &mut c,
+                        &mut s,
+                        value_entity,
+                        &registry,
+                        &widgets,
+                        file_hash,
+                        &rebuild_file,
+                        &rebuild_constant,
+                        reflected,
+                    );
+                },
+            );
+
+            let (s, builder) = l.inner();
+            build_constant_content(
+                builder.commands(),
+                s,
+                value_entity,
+                registry,
+                widgets,
+                file_hash,
+                &file,
+                &constant,
+                reflected,
+            );
+        });
+
+        // Toggle usages.
+        let toggle_file = file.clone();
+        let toggle_name = name.clone();
+        l.get("row::usages")
+            .on_pressed(move |mut c: Commands, mut state: ResMut<EditorDefsState>| {
+                state.toggle_usages(&mut c, toggle_file.clone(), toggle_name.clone());
+            });
+
+        // List usages. They are refreshed when any file changes.
+        let button = l.get("row::usages").id();
+        let usages = l.get("usages").id();
+        l.update_on(
+            (
+                broadcast::<EditorDefsChanged>(),
+                broadcast::<EditorFileExternalChange>(),
+                broadcast::<EditorHistoryChanged>(),
+            ),
+            move |//
+                _: UpdateId,
+                mut c: Commands,
+                mut s: ResMut<SceneLoader>,
+                ps: PseudoStateParam,
+                state: Res<EditorDefsState>,
+                editor: Res<CobEditor>,
+                cob_cache: Res<CobAssetCache>//
+            | {
+                c.entity(usages).despawn_descendants();
+                if !state.is_showing_usages(&file, &name) {
+                    ps.try_deselect(&mut c, button);
+                    return;
+                }
+                ps.try_select(&mut c, button);
+
+                let manifest = cob_cache.manifest_map_clone();
+                let found = find_constant_usages(&editor, &manifest.lock().unwrap(), &file, &name);
+                let mut builder = c.ui_builder(usages);
+                if found.is_empty() {
+                    builder.load_scene(("editor.frame", "no_constant_usages"), &mut s);
+                    return;
+                }
+                for usage in found {
+                    let text = usage.describe();
+                    builder.load_scene_and_edit(("editor.frame", "constant_usage"), &mut s, |l| {
+                        l.get("text")
+                            .update(move |id: UpdateId, mut e: TextEditor| {
+                                write_text!(e, *id, "{}", text.as_str());
+                            });
+
+                        // Scene node usages can be selected.
+                        if let ConstantUsage::SceneNode(scene_ref) = &usage {
+                            let scene_ref = scene_ref.clone();
+                            l.on_pressed(move |mut c: Commands| {
+                                c.queue(SelectSceneNode { scene_ref: scene_ref.clone() });
+                            });
+                        }
+                    });
+                }
+            },
+        );
+    });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn build_conflict<'a>(l: &mut LoadedScene<'a, UiBuilder<'a, Entity>>, file: CobFile, conflict: &CobFileConflict)
+{
+    l.load_scene_and_edit(("editor.frame", "conflict"), |l| {
+        for (button, resolution) in [
+            ("buttons::keep_mine", ConflictResolution::KeepMine),
+            ("buttons::take_theirs", ConflictResolution::TakeTheirs),
+            ("buttons::merge", ConflictResolution::Merge),
+        ] {
+            let file = file.clone();
+            l.get(button).on_pressed(move |mut c: Commands| {
+                c.queue(ResolveEditorConflict { file: file.clone(), resolution });
+            });
+        }
+
+        // List conflicting edits from the last merge attempt.
+        l.edit("failures", |l| {
+            for failure in conflict.merge_failures.iter().cloned() {
+                l.load_scene_and_edit(("editor.frame", "conflict_failure"), |l| {
+                    l.update(move |id: UpdateId, mut e: TextEditor| {
+                        write_text!(e, *id, "merge conflict: {}", failure.as_str());
+                    });
+                });
+            }
+        });
+    });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 fn build_file_view(In((base_entity, file)): In<(Entity, CobFile)>, mut c: Commands)
 {
     // Build file view.
     // - We do this roundabout via a reactor in order to auto-rebuild when the file data changes.
     let mut ec = c.entity(base_entity);
     ec.update_on(
-        (broadcast::<EditorFileExternalChange>(), broadcast::<EditorFileSaved>()),
+        (
+            broadcast::<EditorFileExternalChange>(),
+            broadcast::<EditorFileSaved>(),
+            broadcast::<EditorHistoryChanged>(),
+            broadcast::<EditorFileConflict>(),
+        ),
         move |//
             _: UpdateId,
             mut tracked_hash: Local<Option<CobFileHash>>,
             external_change: BroadcastEvent<EditorFileExternalChange>,
             file_saved: BroadcastEvent<EditorFileSaved>,
+            history_changed: BroadcastEvent<EditorHistoryChanged>,
+            conflict: BroadcastEvent<EditorFileConflict>,
             mut c: Commands,
             mut s: ResMut<SceneLoader>,
             registry: Res<AppTypeRegistry>,
             loadables: Res<LoadableRegistry>,
             widgets: Res<CobWidgetRegistry>,
-            editor: Res<CobEditor>,//
+            editor: Res<CobEditor>,
+            targets: Res<CobEditorSaveTargets>,
+            cob_cache: Res<CobAssetCache>,//
         | {
             // If we are running this system because of an event, exit if the event targets a different file.
             if let Ok(external) = external_change.try_read() {
@@ -195,6 +603,18 @@ fn build_file_view(In((base_entity, file)): In<(Entity, CobFile)>, mut c: Comman
                     return;
                 }
             }
+            if let Ok(history_changed) = history_changed.try_read() {
+                // Structural edits and undo/redo modify file data directly, so widgets need to be rebuilt to show
+                // the new values.
+                if history_changed.file != file {
+                    return;
+                }
+            }
+            if let Ok(conflict) = conflict.try_read() {
+                if conflict.file != file {
+                    return;
+                }
+            }
 
             // Clean up existing children.
             c.entity(base_entity).despawn_descendants();
@@ -204,7 +624,7 @@ fn build_file_view(In((base_entity, file)): In<(Entity, CobFile)>, mut c: Comman
 
             // Handle non-editable files.
             // Note: these are filtered out by the dropdown but we handle it just in case.
-            if !file_data.is_editable() {
+            if !targets.is_editable(&file) {
                 c.ui_builder(base_entity).load_scene(("editor.frame", "file_not_editable"), &mut s);
                 return;
             }
@@ -216,6 +636,44 @@ fn build_file_view(In((base_entity, file)): In<(Entity, CobFile)>, mut c: Comman
             let registry = registry.read();
 
             c.ui_builder(base_entity).load_scene_and_edit(("editor.frame", "file_frame"), &mut s, |l| {
+                // Conflict with external changes
+                if let Some(conflict) = &file_data.conflict {
+                    l.edit("conflict", |l| build_conflict(l, file.clone(), conflict));
+                }
+
+                // Defs section
+                let constants: Vec<&CobConstantDef> = file_data
+                    .data
+                    .sections
+                    .iter()
+                    .filter_map(|s| {
+                        let CobSection::Defs(defs) = s else { return None };
+                        Some(defs)
+                    })
+                    .flat_map(|defs| defs.entries.iter())
+                    .filter_map(|e| {
+                        let CobDefEntry::Constant(def) = e else { return None };
+                        Some(def)
+                    })
+                    .collect();
+                l.edit("defs", |l| {
+                    // Files without constants don't need a defs panel.
+                    if constants.is_empty() {
+                        l.insert(DisplayControl::Hide);
+                        return;
+                    }
+                    let manifest_map = cob_cache.manifest_map_clone();
+                    let manifest = manifest_map.lock().unwrap();
+                    let file_hash = file_data.last_save_hash;
+                    l.edit("content", |l| {
+                        for def in constants.iter() {
+                            let name = def.name.as_str();
+                            let type_id = find_constant_type(&editor, &manifest, &registry, &loadables, &file, name);
+                            build_constant(l, &registry, &widgets, file_hash, file.clone(), def, type_id);
+                        }
+                    });
+                });
+
                 // Commands section
                 l.edit("commands", |l| {
                     let commands_ref = SceneRef{ file: file.clone().into(), path: ScenePath::new("#commands") };
@@ -243,6 +701,10 @@ fn build_file_view(In((base_entity, file)): In<(Entity, CobFile)>, mut c: Comman
                             }
                         }
                     }
+
+                    build_add_entry(l, "+command", move |c: &mut Commands, name: String| {
+                        c.queue(InsertLoadable { scene_ref: commands_ref.clone(), loadable_name: name });
+                    });
                 });
 
                 // Scenes section
@@ -283,7 +745,76 @@ fn build_editor_view(mut c: Commands, mut s: ResMut<SceneLoader>, camera: Query<
         l.insert(TargetCamera(camera_entity));
 
         // Get content entity.
-        let content_entity = l.get("content").id();
+        let content_entity = l.get("content::view::shim").id();
+        let scroll_bar = l.get("content::vertical").id();
+
+        // Record scroll positions so they can be restored when switching files.
+        l.get("content::vertical").update_on(
+            entity_mutation::<SliderValue>(scroll_bar),
+            move |//
+                _: UpdateId,
+                selection: Res<EditorFileSelection>,
+                mut stack: ResMut<EditorStack>,
+                values: Reactive<SliderValue>//
+            | {
+                let Some(file) = selection.0.clone() else { return };
+                let Some(value) = values.get(scroll_bar).ok().and_then(|v| v.single()) else { return };
+                stack.set_scroll(file, value);
+            },
+        );
+
+        // Build search box. Results are refreshed when the query changes, and shortly after files change.
+        l.edit("search::row::field", |l| {
+            let field_entity = l.id();
+            let (s, builder) = l.inner();
+            spawn_text_field(builder.commands(), s, field_entity, String::default(), |c, query| {
+                c.syscall(query, |In(query): In<String>, mut c: Commands, mut state: ResMut<EditorSearchState>| {
+                    state.set_query(&mut c, query);
+                });
+            });
+        });
+        let results = l.get("search::results").id();
+        l.get("search::results").update_on(
+            broadcast::<EditorSearchChanged>(),
+            move |//
+                _: UpdateId,
+                mut c: Commands,
+                mut s: ResMut<SceneLoader>,
+                state: Res<EditorSearchState>,
+                editor: Res<CobEditor>,
+                targets: Res<CobEditorSaveTargets>//
+            | {
+                c.entity(results).despawn_descendants();
+                if state.query().trim().is_empty() {
+                    return;
+                }
+
+                let hits = search_editor(&editor, &targets, state.query());
+                let mut builder = c.ui_builder(results);
+                if hits.is_empty() {
+                    builder.load_scene(("editor.frame", "no_search_hits"), &mut s);
+                    return;
+                }
+                for hit in hits {
+                    let text = hit.describe();
+                    builder.load_scene_and_edit(("editor.frame", "search_hit"), &mut s, |l| {
+                        l.get("text")
+                            .update(move |id: UpdateId, mut e: TextEditor| {
+                                write_text!(e, *id, "{}", text.as_str());
+                            });
+
+                        // Jump to the hit's scene node, or to its file if it's not in a scene.
+                        l.on_pressed(move |mut c: Commands| match hit.scene_ref.clone() {
+                            Some(scene_ref) => c.queue(SelectSceneNode { scene_ref }),
+                            None => {
+                                c.react()
+                                    .broadcast(EditorOpenFileRequest { file: hit.file.clone() });
+                            }
+                        });
+                    });
+                }
+            },
+        );
 
         // Build dropdown
         // TODO: use a proper dropdown widget that tracks selected automatically? (might be harder to get proper
@@ -296,13 +827,16 @@ fn build_editor_view(mut c: Commands, mut s: ResMut<SceneLoader>, camera: Query<
                 move |//
                     event: EntityEvent<Option<CobFile>>,
                     mut c: Commands,
-                    mut selection: ResMut<EditorFileSelection>//
+                    mut selection: ResMut<EditorFileSelection>,
+                    mut stack: ResMut<EditorStack>,
+                    mut slider_values: ReactiveMut<SliderValue>//
                 | {
                     let (_, maybe_file) = event.read();
 
                     // Nothing to do if selection remains the same.
                     if **selection == *maybe_file { return }
                     **selection = maybe_file.clone();
+                    stack.set_file(maybe_file.clone());
 
                     // Clean up old content.
                     c.entity(content_entity).despawn_descendants();
@@ -310,6 +844,9 @@ fn build_editor_view(mut c: Commands, mut s: ResMut<SceneLoader>, camera: Query<
                     // Spawn new content.
                     let Some(file) = maybe_file else { return };
                     c.syscall((content_entity, file.clone()), build_file_view);
+
+                    // Restore the file's scroll position.
+                    slider_values.set_if_neq(&mut c, scroll_bar, SliderValue::Single(stack.scroll(file)));
                 },
             );
 
@@ -319,6 +856,7 @@ fn build_editor_view(mut c: Commands, mut s: ResMut<SceneLoader>, camera: Query<
                     mut c: Commands,
                     mut s: ResMut<SceneLoader>,
                     editor: Res<CobEditor>,
+                    targets: Res<CobEditorSaveTargets>,
                     selection: Res<EditorFileSelection>//
                 | {
                     // Despawn current options.
@@ -333,8 +871,11 @@ fn build_editor_view(mut c: Commands, mut s: ResMut<SceneLoader>, camera: Query<
                     });
 
                     // Get options and sort them lexicographically.
-                    let mut entries: Vec<Option<CobFile>> =
-                        editor.iter_files().filter(|(_, d)| d.is_editable()).map(|(f, _)| Some(f.clone())).collect();
+                    let mut entries: Vec<Option<CobFile>> = editor
+                        .iter_files()
+                        .filter(|(f, _)| targets.is_editable(f))
+                        .map(|(f, _)| Some(f.clone()))
+                        .collect();
                     entries.push(None);
                     entries.sort_unstable_by(|a, b| {
                         let a = a.as_ref().map(|f| f.as_str()).unwrap_or("");
@@ -414,20 +955,54 @@ fn build_editor_view(mut c: Commands, mut s: ResMut<SceneLoader>, camera: Query<
                     }
                 });
 
+            // Switch to the file of a newly-selected scene node.
+            l.react().on(
+                broadcast::<EditorSceneNodeSelected>(),
+                move |//
+                    event: BroadcastEvent<EditorSceneNodeSelected>,
+                    mut c: Commands,
+                    selection: Res<EditorFileSelection>//
+                | {
+                    let Ok(event) = event.try_read() else { return };
+                    if selection.0.as_ref() == Some(&event.file) {
+                        return;
+                    }
+                    c.react().entity_event(dropdown_entity, Some(event.file.clone()));
+                    c.react().entity_event(dropdown_entity, Close);
+                },
+            );
+
+            // Switch to a file requested by a search hit.
+            l.react().on(
+                broadcast::<EditorOpenFileRequest>(),
+                move |//
+                    event: BroadcastEvent<EditorOpenFileRequest>,
+                    mut c: Commands,
+                    selection: Res<EditorFileSelection>//
+                | {
+                    let Ok(event) = event.try_read() else { return };
+                    if selection.0.as_ref() == Some(&event.file) {
+                        return;
+                    }
+                    c.react().entity_event(dropdown_entity, Some(event.file.clone()));
+                    c.react().entity_event(dropdown_entity, Close);
+                },
+            );
+
             // On EditorFileLost (TODO?)
             // - If currently-selected option is not in file list, then send empty file as entity event to self.
             // - if open, close and re-open
 
-            // Initialize. Point to the "main.cob" file if there is one.
-            // TODO: starting point should be obtained from EditorStack
+            // Initialize. Point to the last-selected file, or the "main.cob" file if there is one.
             l.commands()
-                .syscall_once((), move |mut c: Commands, editor: Res<CobEditor>| {
-                    let file = CobFile::try_new("main.cob").unwrap();
-                    let init = if editor.get_file(&file).is_some() {
-                        Some(file)
-                    } else {
-                        None
-                    };
+                .syscall_once((), move |mut c: Commands, editor: Res<CobEditor>, stack: Res<EditorStack>| {
+                    let main = CobFile::try_new("main.cob").unwrap();
+                    let init = stack
+                        .file()
+                        .cloned()
+                        .into_iter()
+                        .chain(std::iter::once(main))
+                        .find(|file| editor.get_file(file).is_some());
                     c.react().entity_event(dropdown_entity, init);
                 });
 
@@ -450,6 +1025,21 @@ fn build_editor_view(mut c: Commands, mut s: ResMut<SceneLoader>, camera: Query<
         );
         l.react().entity_event(unsaved, Disable);
 
+        // Build pick button.
+        l.edit("footer::pick", |l| {
+            let pick_entity = l.id();
+            l.on_pressed(move |mut c: Commands, mut picker: ResMut<EditorPicker>| picker.toggle(&mut c));
+            l.update_on(
+                broadcast::<EditorPickerToggled>(),
+                move |_: UpdateId, mut c: Commands, ps: PseudoStateParam, picker: Res<EditorPicker>| {
+                    match picker.is_active() {
+                        true => ps.try_select(&mut c, pick_entity),
+                        false => ps.try_deselect(&mut c, pick_entity),
+                    };
+                },
+            );
+        });
+
         // Build save button.
         // TODO: use CMD-S instead?
         l.get("footer::save")
@@ -498,6 +1088,38 @@ fn refresh_editor_window(
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Queues undo/redo for the selected file on Ctrl+Z/Ctrl+Shift+Z while the editor window is focused.
+fn handle_editor_shortcuts(
+    mut c: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    selection: Res<EditorFileSelection>,
+    editor_win: Query<&Window, With<EditorWindow>>,
+)
+{
+    if !keys.just_pressed(KeyCode::KeyZ) {
+        return;
+    }
+    if !editor_win.get_single().map(|w| w.focused).unwrap_or(false) {
+        return;
+    }
+    if !keys.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]) {
+        return;
+    }
+    let Some(file) = selection.0.clone() else { return };
+
+    match keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        true => c.queue(RedoEditor { file }),
+        false => c.queue(UndoEditor { file }),
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 // TODO: don't hard-code this, use a resource instead? maybe use resource on startup then allow resizes and
 // any resize change gets saved back the to resource, which can then be saved back to user settings
 const EDITOR_WIDTH: f32 = 300.0;
@@ -562,6 +1184,7 @@ impl Plugin for CobEditorBuildPlugin
         app.add_plugins(CobEditorTemplatePlugin)
             .init_resource::<EditorFileSelection>()
             .add_systems(First, refresh_editor_window)
+            .add_systems(Update, handle_editor_shortcuts)
             .add_systems(OnEnter(LoadState::Done), build_editor_view);
     }
 }
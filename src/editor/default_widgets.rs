@@ -0,0 +1,1021 @@
+use std::any::TypeId;
+use std::ops::RangeInclusive;
+
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use bevy::reflect::attributes::CustomAttributes;
+use bevy::reflect::{
+    DynamicEnum, DynamicStruct, DynamicTuple, DynamicVariant, List, ReflectMut, ReflectRef, TypeInfo,
+    TypeRegistry, VariantInfo,
+};
+use bevy_cobweb::prelude::*;
+use smol_str::SmolStr;
+
+use super::*;
+use crate::prelude::*;
+use crate::sickle::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Number change per dragged pixel for unbounded floats.
+const FLOAT_DRAG_STEP: f64 = 0.01;
+/// Number change per dragged pixel for unbounded integers.
+const INTEGER_DRAG_STEP: f64 = 0.1;
+/// Bounded numbers move across their full range over this many dragged pixels.
+const BOUNDED_DRAG_PIXELS: f64 = 200.;
+/// Number change per dragged pixel for [`Val`] values.
+const VAL_DRAG_STEP: f64 = 0.5;
+/// Channel change per dragged pixel for [`Color`] values.
+const COLOR_DRAG_STEP: f64 = 0.005;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Reactive component that tracks the current value of a default widget.
+#[derive(ReactComponent, PartialEq)]
+struct EditorFieldValue<T: Send + Sync + 'static>(T);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Clone, PartialEq)]
+struct TextFieldState
+{
+    text: String,
+    editing: bool,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Records where text from a text field should be submitted.
+#[derive(Component)]
+struct EditorTextTarget
+{
+    committed: String,
+    submit: Box<dyn Fn(&mut Commands, String) + Send + Sync + 'static>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks the text field that is currently receiving keyboard input.
+#[derive(Resource, Default)]
+struct EditorTextFocus(Option<Entity>);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+struct NumberKind
+{
+    value: f64,
+    integer: bool,
+    range: RangeInclusive<f64>,
+    convert: fn(f64) -> Box<dyn PartialReflect>,
+}
+
+fn get_number_kind(type_id: TypeId, value: &(dyn PartialReflect + 'static)) -> Option<NumberKind>
+{
+    macro_rules! check {
+        ($integer:literal, $($t:ty),*) => {
+            $(
+                if type_id == TypeId::of::<$t>() {
+                    return Some(NumberKind {
+                        value: <$t>::from_reflect(value)? as f64,
+                        integer: $integer,
+                        range: (<$t>::MIN as f64)..=(<$t>::MAX as f64),
+                        convert: |v| -> Box<dyn PartialReflect> { Box::new(v as $t) },
+                    });
+                }
+            )*
+        };
+    }
+
+    check!(false, f32, f64);
+    check!(true, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+    None
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn round_number(value: f64, integer: bool) -> f64
+{
+    match integer {
+        true => value.round(),
+        false => (value * 1000.).round() / 1000.,
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn format_number(value: f64, integer: bool) -> String
+{
+    if integer {
+        return format!("{:.0}", value);
+    }
+
+    let text = format!("{:.3}", value);
+    text.trim_end_matches('0').trim_end_matches('.').into()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn get_field_bounds(attributes: &CustomAttributes) -> Option<RangeInclusive<f64>>
+{
+    if let Some(bounds) = attributes.get::<RangeInclusive<f32>>() {
+        return Some((*bounds.start() as f64)..=(*bounds.end() as f64));
+    }
+    attributes.get::<RangeInclusive<f64>>().cloned()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn get_val_number(val: Val) -> Option<f32>
+{
+    match val {
+        Val::Auto => None,
+        Val::Px(n) | Val::Percent(n) | Val::Vw(n) | Val::Vh(n) | Val::VMin(n) | Val::VMax(n) => Some(n),
+    }
+}
+
+fn set_val_number(val: Val, n: f32) -> Val
+{
+    match val {
+        Val::Auto => Val::Auto,
+        Val::Px(_) => Val::Px(n),
+        Val::Percent(_) => Val::Percent(n),
+        Val::Vw(_) => Val::Vw(n),
+        Val::Vh(_) => Val::Vh(n),
+        Val::VMin(_) => Val::VMin(n),
+        Val::VMax(_) => Val::VMax(n),
+    }
+}
+
+fn next_val_unit(val: Val) -> Val
+{
+    let n = get_val_number(val).unwrap_or(0.);
+    match val {
+        Val::Auto => Val::Px(n),
+        Val::Px(_) => Val::Percent(n),
+        Val::Percent(_) => Val::Vw(n),
+        Val::Vw(_) => Val::Vh(n),
+        Val::Vh(_) => Val::VMin(n),
+        Val::VMin(_) => Val::VMax(n),
+        Val::VMax(_) => Val::Auto,
+    }
+}
+
+fn val_unit_name(val: Val) -> &'static str
+{
+    match val {
+        Val::Auto => "auto",
+        Val::Px(_) => "px",
+        Val::Percent(_) => "%",
+        Val::Vw(_) => "vw",
+        Val::Vh(_) => "vh",
+        Val::VMin(_) => "vmin",
+        Val::VMax(_) => "vmax",
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn color_channel_mut(color: &mut Srgba, channel: usize) -> &mut f32
+{
+    match channel {
+        0 => &mut color.red,
+        1 => &mut color.green,
+        2 => &mut color.blue,
+        _ => &mut color.alpha,
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Converts an edited color back to the color space of the original value.
+fn to_color_space_of(original: Color, color: Srgba) -> Color
+{
+    match original {
+        Color::Srgba(_) => Color::Srgba(color),
+        Color::LinearRgba(_) => Color::LinearRgba(color.into()),
+        Color::Hsla(_) => Color::Hsla(color.into()),
+        Color::Hsva(_) => Color::Hsva(color.into()),
+        Color::Hwba(_) => Color::Hwba(color.into()),
+        Color::Laba(_) => Color::Laba(color.into()),
+        Color::Lcha(_) => Color::Lcha(color.into()),
+        Color::Oklaba(_) => Color::Oklaba(color.into()),
+        Color::Oklcha(_) => Color::Oklcha(color.into()),
+        Color::Xyza(_) => Color::Xyza(color.into()),
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn make_default_value(registry: &TypeRegistry, type_id: TypeId) -> Option<Box<dyn PartialReflect>>
+{
+    let reflect_default = registry.get_type_data::<ReflectDefault>(type_id)?;
+    Some(reflect_default.default().into_partial_reflect())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Makes a variant with default field values.
+///
+/// Returns `None` if any of the variant's fields don't have reflected defaults.
+fn make_default_variant(registry: &TypeRegistry, info: &VariantInfo) -> Option<DynamicVariant>
+{
+    match info {
+        VariantInfo::Unit(_) => Some(DynamicVariant::Unit),
+        VariantInfo::Tuple(tuple) => {
+            let mut dyn_tuple = DynamicTuple::default();
+            for field in tuple.iter() {
+                dyn_tuple.insert_boxed(make_default_value(registry, field.type_id())?);
+            }
+            Some(DynamicVariant::Tuple(dyn_tuple))
+        }
+        VariantInfo::Struct(structure) => {
+            let mut dyn_struct = DynamicStruct::default();
+            for field in structure.iter() {
+                dyn_struct.insert_boxed(field.name(), make_default_value(registry, field.type_id())?);
+            }
+            Some(DynamicVariant::Struct(dyn_struct))
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn focus_text_field(
+    In(entity): In<Entity>,
+    mut c: Commands,
+    mut focus: ResMut<EditorTextFocus>,
+    mut targets: Query<&mut EditorTextTarget>,
+    mut fields: ReactiveMut<EditorFieldValue<TextFieldState>>,
+)
+{
+    if focus.0 == Some(entity) {
+        return;
+    }
+
+    // Commit the previously-focused field.
+    if let Some(prev) = focus.0.take() {
+        if let Ok(field) = fields.get(prev) {
+            let text = field.0.text.clone();
+            finish_text_editing(&mut c, prev, text, true, &mut targets, &mut fields);
+        }
+    }
+
+    let Ok(field) = fields.get(entity) else { return };
+    let text = field.0.text.clone();
+    fields.set_if_neq(&mut c, entity, EditorFieldValue(TextFieldState { text, editing: true }));
+    c.react().entity_event(entity, Select);
+    focus.0 = Some(entity);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn finish_text_editing(
+    c: &mut Commands,
+    entity: Entity,
+    text: String,
+    commit: bool,
+    targets: &mut Query<&mut EditorTextTarget>,
+    fields: &mut ReactiveMut<EditorFieldValue<TextFieldState>>,
+)
+{
+    let Ok(mut target) = targets.get_mut(entity) else { return };
+
+    let text = match commit && text != target.committed {
+        true => {
+            target.committed = text.clone();
+            (target.submit)(c, text.clone());
+            text
+        }
+        false => target.committed.clone(),
+    };
+
+    fields.set_if_neq(c, entity, EditorFieldValue(TextFieldState { text, editing: false }));
+    c.react().entity_event(entity, Deselect);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Forwards keyboard input from the editor window to the focused text field.
+///
+/// `Enter` submits the text and `Escape` discards it.
+fn handle_editor_text_input(
+    mut c: Commands,
+    mut inputs: EventReader<KeyboardInput>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut focus: ResMut<EditorTextFocus>,
+    editor_window: Query<Entity, With<EditorWindow>>,
+    mut targets: Query<&mut EditorTextTarget>,
+    mut fields: ReactiveMut<EditorFieldValue<TextFieldState>>,
+)
+{
+    let Some(entity) = focus.0 else {
+        inputs.clear();
+        return;
+    };
+    let Ok(field) = fields.get(entity) else {
+        // The field was despawned.
+        focus.0 = None;
+        inputs.clear();
+        return;
+    };
+
+    let window = editor_window.get_single().ok();
+    let modified = keys.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]);
+    let mut text = field.0.text.clone();
+    let mut finish = None;
+
+    for input in inputs.read() {
+        if input.state != ButtonState::Pressed || Some(input.window) != window {
+            continue;
+        }
+
+        match &input.logical_key {
+            Key::Character(chars) if !modified => text.push_str(chars.as_str()),
+            Key::Space if !modified => text.push(' '),
+            Key::Backspace => {
+                text.pop();
+            }
+            Key::Enter => {
+                finish = Some(true);
+                break;
+            }
+            Key::Escape => {
+                finish = Some(false);
+                break;
+            }
+            _ => (),
+        }
+    }
+
+    match finish {
+        Some(commit) => {
+            focus.0 = None;
+            finish_text_editing(&mut c, entity, text, commit, &mut targets, &mut fields);
+        }
+        None => {
+            fields.set_if_neq(&mut c, entity, EditorFieldValue(TextFieldState { text, editing: true }));
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn spawn_bool_widget(c: &mut Commands, s: &mut SceneLoader, parent: Entity, editor_ref: &CobEditorRef, value: bool)
+{
+    let editor_ref = editor_ref.clone();
+    c.ui_builder(parent)
+        .load_scene_and_edit(("editor.frame", "value_button"), s, |l| {
+            let widget_id = l.id();
+            l.insert_reactive(EditorFieldValue(value));
+
+            l.get("text").update_on(
+                entity_mutation::<EditorFieldValue<bool>>(widget_id),
+                move |id: UpdateId, mut e: TextEditor, values: Reactive<EditorFieldValue<bool>>| {
+                    let value = values.get(widget_id)?.0;
+                    write_text!(e, *id, "{}", value);
+                    OK
+                },
+            );
+
+            l.on_pressed(move |mut c: Commands, mut values: ReactiveMut<EditorFieldValue<bool>>| {
+                let value = !values.get(widget_id)?.0;
+                values.set_if_neq(&mut c, widget_id, EditorFieldValue(value));
+                c.queue(SubmitPatch { editor_ref: editor_ref.clone(), value: Box::new(value) });
+                OK
+            });
+        });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn spawn_number_widget(
+    c: &mut Commands,
+    s: &mut SceneLoader,
+    parent: Entity,
+    editor_ref: &CobEditorRef,
+    kind: NumberKind,
+    bounds: Option<RangeInclusive<f64>>,
+)
+{
+    let step = match (&bounds, kind.integer) {
+        (Some(bounds), _) => (bounds.end() - bounds.start()) / BOUNDED_DRAG_PIXELS,
+        (None, true) => INTEGER_DRAG_STEP,
+        (None, false) => FLOAT_DRAG_STEP,
+    };
+    let range = match bounds {
+        Some(bounds) => bounds.start().max(*kind.range.start())..=bounds.end().min(*kind.range.end()),
+        None => kind.range.clone(),
+    };
+
+    let editor_ref = editor_ref.clone();
+    c.ui_builder(parent)
+        .load_scene_and_edit(("editor.frame", "number_field"), s, |l| {
+            let widget_id = l.id();
+            l.insert_reactive(EditorFieldValue(kind.value));
+
+            let integer = kind.integer;
+            l.get("text").update_on(
+                entity_mutation::<EditorFieldValue<f64>>(widget_id),
+                move |id: UpdateId, mut e: TextEditor, values: Reactive<EditorFieldValue<f64>>| {
+                    let value = values.get(widget_id)?.0;
+                    write_text!(e, *id, "{}", format_number(value, integer));
+                    OK
+                },
+            );
+
+            // Integers accumulate fractional changes so slow drags can still change the value.
+            let mut raw = kind.value;
+            let convert = kind.convert;
+            l.entity_commands().observe(
+                move |drag: Trigger<Pointer<Drag>>,
+                      mut c: Commands,
+                      mut values: ReactiveMut<EditorFieldValue<f64>>| {
+                    let Ok(current) = values.get(widget_id).map(|v| v.0) else { return };
+                    // Resync if the value changed outside of dragging (e.g. undo/redo).
+                    if round_number(raw, integer) != current {
+                        raw = current;
+                    }
+                    raw = (raw + drag.delta.x as f64 * step).clamp(*range.start(), *range.end());
+                    let value = round_number(raw, integer);
+                    if value == current {
+                        return;
+                    }
+
+                    values.set_if_neq(&mut c, widget_id, EditorFieldValue(value));
+                    c.queue(SubmitPatch { editor_ref: editor_ref.clone(), value: (convert)(value) });
+                },
+            );
+        });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn spawn_text_widget(
+    c: &mut Commands,
+    s: &mut SceneLoader,
+    parent: Entity,
+    editor_ref: &CobEditorRef,
+    text: String,
+    convert: fn(String) -> Box<dyn PartialReflect>,
+)
+{
+    let editor_ref = editor_ref.clone();
+    spawn_text_field(c, s, parent, text, move |c: &mut Commands, text: String| {
+        c.queue(SubmitPatch { editor_ref: editor_ref.clone(), value: (convert)(text) });
+    });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Spawns a text field that calls `submit` when the user commits a new value.
+pub(super) fn spawn_text_field(
+    c: &mut Commands,
+    s: &mut SceneLoader,
+    parent: Entity,
+    text: String,
+    submit: impl Fn(&mut Commands, String) + Send + Sync + 'static,
+)
+{
+    c.ui_builder(parent)
+        .load_scene_and_edit(("editor.frame", "text_field"), s, |l| {
+            let widget_id = l.id();
+            l.insert(EditorTextTarget { committed: text.clone(), submit: Box::new(submit) });
+            l.insert_reactive(EditorFieldValue(TextFieldState { text, editing: false }));
+
+            l.get("text").update_on(
+                entity_mutation::<EditorFieldValue<TextFieldState>>(widget_id),
+                move |id: UpdateId, mut e: TextEditor, values: Reactive<EditorFieldValue<TextFieldState>>| {
+                    let state = &values.get(widget_id)?.0;
+                    match state.editing {
+                        true => write_text!(e, *id, "{}|", state.text.as_str()),
+                        false => write_text!(e, *id, "\"{}\"", state.text.as_str()),
+                    };
+                    OK
+                },
+            );
+
+            l.on_pressed(move |mut c: Commands| {
+                c.syscall(widget_id, focus_text_field);
+            });
+        });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn spawn_val_widget(c: &mut Commands, s: &mut SceneLoader, parent: Entity, editor_ref: &CobEditorRef, value: Val)
+{
+    c.ui_builder(parent)
+        .load_scene_and_edit(("editor.frame", "row"), s, |l| {
+            let widget_id = l.id();
+            l.insert_reactive(EditorFieldValue(value));
+
+            // Number
+            let number_ref = editor_ref.clone();
+            l.load_scene_and_edit(("editor.frame", "number_field"), |l| {
+                l.get("text").update_on(
+                    entity_mutation::<EditorFieldValue<Val>>(widget_id),
+                    move |id: UpdateId, mut e: TextEditor, values: Reactive<EditorFieldValue<Val>>| {
+                        let val = values.get(widget_id)?.0;
+                        match get_val_number(val) {
+                            Some(n) => write_text!(e, *id, "{}", format_number(n as f64, false)),
+                            None => write_text!(e, *id, "-"),
+                        };
+                        OK
+                    },
+                );
+
+                l.entity_commands().observe(
+                    move |drag: Trigger<Pointer<Drag>>,
+                          mut c: Commands,
+                          mut values: ReactiveMut<EditorFieldValue<Val>>| {
+                        let Ok(val) = values.get(widget_id).map(|v| v.0) else { return };
+                        let Some(n) = get_val_number(val) else { return };
+                        let n = round_number(n as f64 + drag.delta.x as f64 * VAL_DRAG_STEP, false);
+                        let new_val = set_val_number(val, n as f32);
+                        if new_val == val {
+                            return;
+                        }
+
+                        values.set_if_neq(&mut c, widget_id, EditorFieldValue(new_val));
+                        c.queue(SubmitPatch { editor_ref: number_ref.clone(), value: Box::new(new_val) });
+                    },
+                );
+            });
+
+            // Unit
+            let unit_ref = editor_ref.clone();
+            l.load_scene_and_edit(("editor.frame", "value_button"), |l| {
+                l.get("text").update_on(
+                    entity_mutation::<EditorFieldValue<Val>>(widget_id),
+                    move |id: UpdateId, mut e: TextEditor, values: Reactive<EditorFieldValue<Val>>| {
+                        let val = values.get(widget_id)?.0;
+                        write_text!(e, *id, "{}", val_unit_name(val));
+                        OK
+                    },
+                );
+
+                l.on_pressed(move |mut c: Commands, mut values: ReactiveMut<EditorFieldValue<Val>>| {
+                    let new_val = next_val_unit(values.get(widget_id)?.0);
+                    values.set_if_neq(&mut c, widget_id, EditorFieldValue(new_val));
+                    c.queue(SubmitPatch { editor_ref: unit_ref.clone(), value: Box::new(new_val) });
+                    OK
+                });
+            });
+        });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Edits colors as sRGBA channels. Edits are submitted in the original color space.
+fn spawn_color_widget(c: &mut Commands, s: &mut SceneLoader, parent: Entity, editor_ref: &CobEditorRef, value: Color)
+{
+    c.ui_builder(parent)
+        .load_scene_and_edit(("editor.frame", "row"), s, |l| {
+            let widget_id = l.id();
+            l.insert_reactive(EditorFieldValue(value.to_srgba()));
+
+            l.load_scene_and_edit(("editor.frame", "color_swatch"), |l| {
+                l.update_on(
+                    entity_mutation::<EditorFieldValue<Srgba>>(widget_id),
+                    move |id: UpdateId, mut c: Commands, values: Reactive<EditorFieldValue<Srgba>>| {
+                        let color = values.get(widget_id)?.0;
+                        c.entity(*id).insert(BackgroundColor(color.into()));
+                        OK
+                    },
+                );
+            });
+
+            for (channel, name) in ["r", "g", "b", "a"].into_iter().enumerate() {
+                let editor_ref = editor_ref.clone();
+                l.load_scene_and_edit(("editor.frame", "color_channel"), |l| {
+                    l.get("name")
+                        .update(move |id: UpdateId, mut e: TextEditor| {
+                            write_text!(e, *id, "{}", name);
+                        });
+
+                    l.edit("value", |l| {
+                        l.get("text").update_on(
+                            entity_mutation::<EditorFieldValue<Srgba>>(widget_id),
+                            move |id: UpdateId, mut e: TextEditor, values: Reactive<EditorFieldValue<Srgba>>| {
+                                let mut color = values.get(widget_id)?.0;
+                                let n = *color_channel_mut(&mut color, channel);
+                                write_text!(e, *id, "{}", format_number(n as f64, false));
+                                OK
+                            },
+                        );
+
+                        l.entity_commands().observe(
+                            move |drag: Trigger<Pointer<Drag>>,
+                                  mut c: Commands,
+                                  mut values: ReactiveMut<EditorFieldValue<Srgba>>| {
+                                let Ok(color) = values.get(widget_id).map(|v| v.0) else { return };
+                                let mut new_color = color;
+                                let n = color_channel_mut(&mut new_color, channel);
+                                let new_n = round_number(*n as f64 + drag.delta.x as f64 * COLOR_DRAG_STEP, false);
+                                *n = new_n.clamp(0., 1.) as f32;
+                                if new_color == color {
+                                    return;
+                                }
+
+                                values.set_if_neq(&mut c, widget_id, EditorFieldValue(new_color));
+                                c.queue(SubmitPatch {
+                                    editor_ref: editor_ref.clone(),
+                                    value: Box::new(to_color_space_of(value, new_color)),
+                                });
+                            },
+                        );
+                    });
+                });
+            }
+        });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tries to spawn a widget for primitives and common bevy types.
+fn try_spawn_builtin_widget(
+    c: &mut Commands,
+    s: &mut SceneLoader,
+    parent: Entity,
+    editor_ref: &CobEditorRef,
+    value: &(dyn PartialReflect + 'static),
+    bounds: Option<RangeInclusive<f64>>,
+) -> bool
+{
+    let Some(type_id) = value.get_represented_type_info().map(|i| i.type_id()) else { return false };
+
+    if let Some(kind) = get_number_kind(type_id, value) {
+        spawn_number_widget(c, s, parent, editor_ref, kind, bounds);
+        return true;
+    }
+
+    if type_id == TypeId::of::<bool>() {
+        let Some(value) = bool::from_reflect(value) else { return false };
+        spawn_bool_widget(c, s, parent, editor_ref, value);
+    } else if type_id == TypeId::of::<String>() {
+        let Some(value) = String::from_reflect(value) else { return false };
+        spawn_text_widget(c, s, parent, editor_ref, value, |t| Box::new(t));
+    } else if type_id == TypeId::of::<SmolStr>() {
+        let Some(value) = SmolStr::from_reflect(value) else { return false };
+        spawn_text_widget(c, s, parent, editor_ref, value.into(), |t| Box::new(SmolStr::from(t)));
+    } else if type_id == TypeId::of::<Val>() {
+        let Some(value) = Val::from_reflect(value) else { return false };
+        spawn_val_widget(c, s, parent, editor_ref, value);
+    } else if type_id == TypeId::of::<Color>() {
+        let Some(value) = Color::from_reflect(value) else { return false };
+        spawn_color_widget(c, s, parent, editor_ref, value);
+    } else {
+        return false;
+    }
+
+    true
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Spawns a labeled widget for a value inside a container.
+fn spawn_field_widget(
+    c: &mut Commands,
+    s: &mut SceneLoader,
+    parent: Entity,
+    ctx: &EditorWidgetContext,
+    editor_ref: &CobEditorRef,
+    name: String,
+    value: &(dyn PartialReflect + 'static),
+    bounds: Option<RangeInclusive<f64>>,
+)
+{
+    c.ui_builder(parent)
+        .load_scene_and_edit(("editor.frame", "field"), s, |l| {
+            l.get("name")
+                .update(move |id: UpdateId, mut e: TextEditor| {
+                    write_text!(e, *id, "{}:", name);
+                });
+
+            l.edit("content", |l| {
+                let content_entity = l.id();
+                let (s, builder) = l.inner();
+                spawn_value_widget(builder.commands(), s, content_entity, ctx, editor_ref, value, bounds);
+            });
+        });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Spawns a container for a list of fields and returns its entity.
+fn spawn_fields_container(c: &mut Commands, s: &mut SceneLoader, parent: Entity) -> Entity
+{
+    let mut entity = parent;
+    c.ui_builder(parent)
+        .load_scene_and_edit(("editor.frame", "fields"), s, |l| {
+            entity = l.id();
+        });
+    entity
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn spawn_enum_widget(
+    c: &mut Commands,
+    s: &mut SceneLoader,
+    parent: Entity,
+    ctx: &EditorWidgetContext,
+    editor_ref: &CobEditorRef,
+    value: &(dyn PartialReflect + 'static),
+)
+{
+    let ReflectRef::Enum(dyn_enum) = value.reflect_ref() else { return };
+    let Some(type_info) = value.get_represented_type_info() else { return };
+    let TypeInfo::Enum(enum_info) = type_info else { return };
+    let Some(variant_info) = enum_info.variant(dyn_enum.variant_name()) else { return };
+    let variant_name = variant_info.name();
+
+    c.ui_builder(parent)
+        .load_scene_and_edit(("editor.frame", "enum"), s, |l| {
+            // Variant picker. Pressing cycles to the next variant that can be default-constructed.
+            let editor_ref_clone = editor_ref.clone();
+            l.edit("variant", |l| {
+                l.get("text")
+                    .update(move |id: UpdateId, mut e: TextEditor| {
+                        write_text!(e, *id, "{}", variant_name);
+                    });
+
+                l.on_pressed(move |mut c: Commands, types: Res<AppTypeRegistry>| {
+                    let registry = types.read();
+                    let names = enum_info.variant_names();
+                    let start = names.iter().position(|n| *n == variant_name).unwrap_or(0);
+
+                    for offset in 1..names.len() {
+                        let Some(info) = enum_info.variant_at((start + offset) % names.len()) else { continue };
+                        let Some(variant) = make_default_variant(&registry, info) else { continue };
+                        let mut new_value = DynamicEnum::new(info.name(), variant);
+                        new_value.set_represented_type(Some(type_info));
+
+                        c.queue(SubmitPatch { editor_ref: editor_ref_clone.clone(), value: Box::new(new_value) });
+                        editor_ref_clone.rebuild_widgets(&mut c);
+                        return;
+                    }
+                });
+            });
+
+            // Variant fields.
+            l.edit("fields", |l| {
+                let fields_entity = l.id();
+                let (s, builder) = l.inner();
+                let c = builder.commands();
+
+                for (index, field) in dyn_enum.iter_fields().enumerate() {
+                    let field_ref = editor_ref.extend(ReflectStructurePoint::Enum(variant_name, index));
+                    let (name, bounds) = match variant_info {
+                        VariantInfo::Struct(info) => info
+                            .field_at(index)
+                            .map(|f| (f.name().into(), get_field_bounds(f.custom_attributes())))
+                            .unwrap_or_else(|| (format!("{index}"), None)),
+                        VariantInfo::Tuple(info) => (
+                            format!("{index}"),
+                            info.field_at(index)
+                                .and_then(|f| get_field_bounds(f.custom_attributes())),
+                        ),
+                        VariantInfo::Unit(_) => break,
+                    };
+
+                    // Newtype variants don't need labels.
+                    if dyn_enum.field_len() == 1 && matches!(variant_info, VariantInfo::Tuple(_)) {
+                        spawn_value_widget(c, s, fields_entity, ctx, &field_ref, field.value(), bounds);
+                    } else {
+                        spawn_field_widget(c, s, fields_entity, ctx, &field_ref, name, field.value(), bounds);
+                    }
+                }
+            });
+        });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn spawn_list_controls(
+    c: &mut Commands,
+    s: &mut SceneLoader,
+    parent: Entity,
+    editor_ref: &CobEditorRef,
+    value: &(dyn PartialReflect + 'static),
+)
+{
+    let Some(TypeInfo::List(list_info)) = value.get_represented_type_info() else { return };
+    let item_type_id = list_info.item_ty().id();
+
+    c.ui_builder(parent)
+        .load_scene_and_edit(("editor.frame", "list_controls"), s, |l| {
+            // The list is read when the edit is applied, so edits to list entries made after the widgets were
+            // built aren't lost.
+
+            // Add entry.
+            let editor_ref_clone = editor_ref.clone();
+            l.get("add").on_pressed(move |mut c: Commands| {
+                let editor_ref = editor_ref_clone.clone();
+                c.queue(move |world: &mut World| {
+                    let item = make_default_value(&world.resource::<AppTypeRegistry>().read(), item_type_id);
+                    let Some(item) = item else {
+                        tracing::warn!("failed adding list entry in editor for {:?}; list item type does not \
+                            reflect Default", editor_ref.scene_ref);
+                        return;
+                    };
+                    edit_list(world, editor_ref, move |list| {
+                        list.push(item);
+                        true
+                    });
+                });
+            });
+
+            // Remove last entry.
+            let editor_ref = editor_ref.clone();
+            l.get("remove").on_pressed(move |mut c: Commands| {
+                let editor_ref = editor_ref.clone();
+                c.queue(move |world: &mut World| {
+                    edit_list(world, editor_ref, |list| list.pop().is_some());
+                });
+            });
+        });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Edits the current value of the list that `editor_ref` points to, then submits it as a patch and rebuilds the
+/// list's widgets.
+///
+/// Does nothing if `edit` returns `false`.
+fn edit_list(world: &mut World, editor_ref: CobEditorRef, edit: impl FnOnce(&mut dyn List) -> bool)
+{
+    let Some(mut list) = get_editor_value(world, &editor_ref) else {
+        tracing::warn!("failed editing list in editor for {:?}; list value not found", editor_ref.scene_ref);
+        return;
+    };
+    let ReflectMut::List(dyn_list) = list.reflect_mut() else { return };
+    if !(edit)(dyn_list) {
+        return;
+    }
+
+    let rebuild_ref = editor_ref.clone();
+    SubmitPatch { editor_ref, value: list }.apply(world);
+    rebuild_ref.rebuild_widgets(&mut world.commands());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Spawns widgets for the fields of a reflected container.
+///
+/// Returns `false` if the value is not a container.
+fn try_spawn_container_widget(
+    c: &mut Commands,
+    s: &mut SceneLoader,
+    parent: Entity,
+    ctx: &EditorWidgetContext,
+    editor_ref: &CobEditorRef,
+    value: &(dyn PartialReflect + 'static),
+) -> bool
+{
+    let type_info = value.get_represented_type_info();
+
+    match value.reflect_ref() {
+        ReflectRef::Struct(dyn_struct) => {
+            let Some(TypeInfo::Struct(info)) = type_info else { return false };
+            let container = spawn_fields_container(c, s, parent);
+            // Fields that aren't present in the value are reflect-defaulted and can't be added by patching.
+            for field_info in info.iter() {
+                let Some(field) = dyn_struct.field(field_info.name()) else { continue };
+                let field_ref = editor_ref.extend(ReflectStructurePoint::Struct(field_info.name()));
+                let bounds = get_field_bounds(field_info.custom_attributes());
+                let name = field_info.name().into();
+                spawn_field_widget(c, s, container, ctx, &field_ref, name, field, bounds);
+            }
+        }
+        ReflectRef::TupleStruct(dyn_tuplestruct) => {
+            let Some(TypeInfo::TupleStruct(info)) = type_info else { return false };
+            // Newtypes don't need labels.
+            if dyn_tuplestruct.field_len() == 1 {
+                let Some(field) = dyn_tuplestruct.field(0) else { return false };
+                let field_ref = editor_ref.extend(ReflectStructurePoint::TupleStruct(0));
+                let bounds = info
+                    .field_at(0)
+                    .and_then(|f| get_field_bounds(f.custom_attributes()));
+                spawn_value_widget(c, s, parent, ctx, &field_ref, field, bounds);
+                return true;
+            }
+
+            let container = spawn_fields_container(c, s, parent);
+            for (index, field) in dyn_tuplestruct.iter_fields().enumerate() {
+                let field_ref = editor_ref.extend(ReflectStructurePoint::TupleStruct(index));
+                let bounds = info
+                    .field_at(index)
+                    .and_then(|f| get_field_bounds(f.custom_attributes()));
+                spawn_field_widget(c, s, container, ctx, &field_ref, format!("{index}"), field, bounds);
+            }
+        }
+        ReflectRef::Tuple(dyn_tuple) => {
+            let container = spawn_fields_container(c, s, parent);
+            for (index, field) in dyn_tuple.iter_fields().enumerate() {
+                let field_ref = editor_ref.extend(ReflectStructurePoint::Tuple(index));
+                spawn_field_widget(c, s, container, ctx, &field_ref, format!("{index}"), field, None);
+            }
+        }
+        ReflectRef::List(dyn_list) => {
+            let container = spawn_fields_container(c, s, parent);
+            for (index, item) in dyn_list.iter().enumerate() {
+                let item_ref = editor_ref.extend(ReflectStructurePoint::List(index));
+                spawn_field_widget(c, s, container, ctx, &item_ref, format!("[{index}]"), item, None);
+            }
+            spawn_list_controls(c, s, container, editor_ref, value);
+        }
+        ReflectRef::Array(dyn_array) => {
+            let container = spawn_fields_container(c, s, parent);
+            for (index, item) in dyn_array.iter().enumerate() {
+                let item_ref = editor_ref.extend(ReflectStructurePoint::Array(index));
+                spawn_field_widget(c, s, container, ctx, &item_ref, format!("[{index}]"), item, None);
+            }
+        }
+        ReflectRef::Map(dyn_map) => {
+            let container = spawn_fields_container(c, s, parent);
+            for (index, (key, entry)) in dyn_map.iter().enumerate() {
+                let entry_ref = editor_ref.extend(ReflectStructurePoint::MapValue(index));
+                spawn_field_widget(c, s, container, ctx, &entry_ref, format!("{key:?}"), entry, None);
+            }
+        }
+        ReflectRef::Set(_) => {
+            c.ui_builder(parent)
+                .load_scene(("editor.frame", "set_unsupported"), s);
+        }
+        ReflectRef::Enum(_) => {
+            spawn_enum_widget(c, s, parent, ctx, editor_ref, value);
+        }
+        _ => return false,
+    }
+
+    true
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Data needed to spawn editor widgets.
+pub(super) struct EditorWidgetContext<'a>
+{
+    pub(super) widgets: &'a CobWidgetRegistry,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Spawns a widget for `value` as a child of `parent`.
+///
+/// Widgets registered with [`CobWidgetAppExt::register_editor_widget`] take precedence. If no registered widget
+/// accepts the value, then a default widget is spawned based on the value's reflected structure. Containers
+/// are destructured so their contents can be edited individually.
+pub(super) fn spawn_value_widget(
+    c: &mut Commands,
+    s: &mut SceneLoader,
+    parent: Entity,
+    ctx: &EditorWidgetContext,
+    editor_ref: &CobEditorRef,
+    value: &(dyn PartialReflect + 'static),
+    bounds: Option<RangeInclusive<f64>>,
+)
+{
+    // Check for a registered widget.
+    if let Some(spawn_fn) = value
+        .get_represented_type_info()
+        .and_then(|i| ctx.widgets.get(i.type_path()))
+    {
+        if (spawn_fn)(c, s, parent, editor_ref, value) {
+            return;
+        }
+    }
+
+    if try_spawn_builtin_widget(c, s, parent, editor_ref, value, bounds) {
+        return;
+    }
+
+    if try_spawn_container_widget(c, s, parent, ctx, editor_ref, value) {
+        return;
+    }
+
+    c.ui_builder(parent)
+        .load_scene(("editor.frame", "destructure_unsupported"), s);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(super) struct CobDefaultWidgetsPlugin;
+
+impl Plugin for CobDefaultWidgetsPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.init_resource::<EditorTextFocus>()
+            .add_systems(Update, handle_editor_text_input);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
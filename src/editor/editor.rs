@@ -1,7 +1,5 @@
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
 
-use bevy::asset::io::file::FileAssetReader;
 use bevy::prelude::*;
 use bevy_cobweb::prelude::*;
 
@@ -15,22 +13,58 @@ pub(super) struct CobFileData
     pub(super) last_save_hash: CobFileHash,
     /// Data for the file. Defs in this data are *not* resolved.
     pub(super) data: Cob,
+    /// Undo/redo history for edits to the file.
+    pub(super) history: EditorHistory,
+    /// Data for the file as of `last_save_hash`. Used as the base when merging conflicting changes.
+    pub(super) base: Cob,
+    /// New file data that arrived while the editor had unsaved changes.
+    pub(super) conflict: Option<CobFileConflict>,
 }
 
 impl CobFileData
 {
-    pub(super) fn is_editable(&self) -> bool
+    /// Replaces the file data with data from outside the editor.
+    fn take_external(&mut self, hash: CobFileHash, data: Cob)
     {
-        // Temp hack to qualify files with non-default asset source as uneditable, since we don't have a good
-        // way to save them yet.
-        self.data.file.as_str().find("://").is_none()
+        // History entries refer to the old data so they must be discarded.
+        self.last_save_hash = hash;
+        self.base = data.clone();
+        self.data = data;
+        self.conflict = None;
+        self.history.clear();
     }
+
+    /// Rebases unsaved changes onto conflicting file data, replacing the current data with `data`.
+    ///
+    /// Returns the conflict's hash, which the asset cache associates with the file. Returns `None` if there is no
+    /// conflict.
+    pub(super) fn rebase(&mut self, data: Cob) -> Option<CobFileHash>
+    {
+        let conflict = self.conflict.take()?;
+        self.last_save_hash = conflict.hash;
+        self.base = conflict.data;
+        self.data = data;
+        // The last-saved state can no longer be reached by undoing.
+        self.history.forget_save();
+        Some(conflict.hash)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// File data that conflicts with unsaved changes in the editor.
+pub(super) struct CobFileConflict
+{
+    pub(super) hash: CobFileHash,
+    pub(super) data: Cob,
+    /// Descriptions of conflicting scene nodes and sections if a merge was attempted and failed.
+    pub(super) merge_failures: Vec<String>,
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
-/// Files cannot be saved on `wasm32` or `android` targets.
-#[derive(Resource)]
+/// Files are saved to the locations resolved by [`CobEditorSaveTargets`].
+#[derive(Resource, Default)]
 pub(crate) struct CobEditor
 {
     /// All files tracked by the editor.
@@ -38,37 +72,18 @@ pub(crate) struct CobEditor
 
     /// Files waiting to be saved.
     unsaved: HashSet<CobFile>,
-
-    /// Asset directory location.
-    ///
-    /// If there is no path then files cannot be saved.
-    asset_dir: Option<PathBuf>,
 }
 
 impl CobEditor
 {
-    fn new(asset_path: String) -> Self
+    pub(super) fn any_unsaved(&self) -> bool
     {
-        let asset_dir = {
-            #[cfg(not(any(target_arch = "wasm32", target_os = "android")))]
-            {
-                Some(FileAssetReader::new(asset_path).root_path().clone())
-            }
-
-            #[cfg(any(target_arch = "wasm32", target_os = "android"))]
-            None
-        };
-
-        Self {
-            files: HashMap::default(),
-            unsaved: HashSet::default(),
-            asset_dir,
-        }
+        !self.unsaved.is_empty()
     }
 
-    pub(super) fn any_unsaved(&self) -> bool
+    pub(super) fn is_unsaved(&self, file: &CobFile) -> bool
     {
-        !self.unsaved.is_empty()
+        self.unsaved.contains(file)
     }
 
     pub(super) fn iter_files(&self) -> impl Iterator<Item = (&CobFile, &CobFileData)> + '_
@@ -92,17 +107,41 @@ impl CobEditor
         let Some(existing) = self.files.get_mut(&data.file) else {
             self.files.insert(
                 data.file.clone(),
-                CobFileData { last_save_hash: hash, data: data.clone() },
+                CobFileData {
+                    last_save_hash: hash,
+                    data: data.clone(),
+                    history: EditorHistory::default(),
+                    base: data.clone(),
+                    conflict: None,
+                },
             );
             c.react()
                 .broadcast(EditorNewFile { file: data.file.clone() });
             return;
         };
 
+        // Exit if this is the editor's own data coming back from the backend (e.g. after a structural edit was
+        // re-processed). Unsaved changes and history must be preserved.
+        // - The editor view already shows this data, and rebuilding it would interrupt widgets that are being
+        //   used (e.g. while dragging a constant's number).
+        if existing.last_save_hash == hash && existing.data == *data {
+            return;
+        }
+
         // Always broadcast this in case the editor view needs to be respawned.
         c.react()
             .broadcast(EditorFileExternalChange { file: data.file.clone() });
 
+        // If the file changed while we have unsaved changes, record a conflict that must be resolved by the user.
+        if existing.last_save_hash != hash && self.unsaved.contains(&data.file) {
+            tracing::warn!("file {:?} was changed externally while the editor has unsaved changes; resolve the \
+                conflict in the editor before saving", data.file);
+            existing.conflict = Some(CobFileConflict { hash, data: data.clone(), merge_failures: Vec::default() });
+            c.react()
+                .broadcast(EditorFileConflict { file: data.file.clone() });
+            return;
+        }
+
         // Remove from unsaved.
         let removed = self.unsaved.remove(&data.file);
 
@@ -119,8 +158,22 @@ impl CobEditor
         }
 
         // Save new data.
-        existing.last_save_hash = hash;
-        existing.data = data.clone();
+        existing.take_external(hash, data.clone());
+    }
+
+    /// Resolves a conflict by discarding unsaved changes in favor of the conflicting file data.
+    ///
+    /// Returns `false` if there is no conflict.
+    pub(super) fn take_theirs(&mut self, c: &mut Commands, file: &CobFile) -> bool
+    {
+        let Some(file_data) = self.files.get_mut(file) else { return false };
+        let Some(conflict) = file_data.conflict.take() else { return false };
+        file_data.take_external(conflict.hash, conflict.data);
+        if self.unsaved.remove(file) {
+            c.react()
+                .broadcast(EditorFileSaved { file: file.clone(), hash: conflict.hash });
+        }
+        true
     }
 
     pub(super) fn mark_unsaved(&mut self, c: &mut Commands, file: CobFile)
@@ -130,26 +183,57 @@ impl CobEditor
         self.unsaved.insert(file);
     }
 
+    /// Removes 'unsaved' status from a file whose data was returned to its last-saved state (e.g. by undoing
+    /// edits).
+    pub(super) fn mark_saved(&mut self, c: &mut Commands, file: CobFile)
+    {
+        let Some(file_data) = self.files.get(&file) else { return };
+        if !self.unsaved.remove(&file) {
+            return;
+        }
+        c.react()
+            .broadcast(EditorFileSaved { file, hash: file_data.last_save_hash });
+    }
+
     /// Saves currently-unsaved files.
     // TODO: currently blocks the main loop, maybe pass this off to the CPU thread pool? problem is how to
     // correctly synchronize with the editor; also need to be careful about not contesting the scratch file name
-    pub(super) fn save(&mut self, c: &mut Commands, cob_cache: &mut CobAssetCache, registry: &CobHashRegistry)
+    pub(super) fn save(
+        &mut self,
+        c: &mut Commands,
+        cob_cache: &mut CobAssetCache,
+        registry: &CobHashRegistry,
+        targets: &CobEditorSaveTargets,
+    )
     {
-        let Some(asset_dir) = &self.asset_dir else {
-            if !self.unsaved.is_empty() {
-                tracing::error!("failed saving files {:?}, no asset directory is available (this is a bug)",
-                    self.unsaved);
-                self.unsaved.clear();
-            }
-            return;
-        };
-
+        let mut blocked = Vec::default();
         for unsaved in self.unsaved.drain() {
             let Some(file_data) = self.files.get_mut(&unsaved) else {
                 tracing::error!("file {:?} is missing on save (this is a bug)", unsaved);
                 continue;
             };
 
+            // Don't overwrite external changes.
+            // - If the hash registry doesn't match, then a new version of the file is on its way to the editor. It
+            //   will be recorded as a conflict when it arrives.
+            if file_data.conflict.is_some()
+                || registry
+                    .get_file_hash(unsaved.as_str())
+                    .map(|hash| hash != file_data.last_save_hash)
+                    .unwrap_or(false)
+            {
+                tracing::warn!("not saving file {:?}; the file was changed externally and the conflict with \
+                    unsaved changes must be resolved first", unsaved);
+                blocked.push(unsaved);
+                continue;
+            }
+
+            // Find where to save the file.
+            let Some(target) = targets.resolve(&unsaved) else {
+                tracing::error!("failed saving file {:?}; the file has no save target (this is a bug)", unsaved);
+                continue;
+            };
+
             // Collect bytes.
             let mut buff = Vec::<u8>::default();
             let mut serializer = DefaultRawSerializer::new(&mut buff);
@@ -157,6 +241,7 @@ impl CobEditor
 
             // Compute hash.
             let hash = CobFileHash::new(&buff);
+            file_data.history.mark_saved();
 
             // Notify listeners.
             c.react()
@@ -183,13 +268,15 @@ impl CobEditor
 
             // Save the file.
             file_data.last_save_hash = hash;
+            file_data.base = file_data.data.clone();
 
             #[cfg(not(any(target_arch = "wasm32", target_os = "android")))]
             {
                 use std::io::Write;
 
                 // Write to scratch file.
-                let scratch = asset_dir.join(".__cob_editor_scratch");
+                // - The scratch file is next to the target so it can be renamed atomically.
+                let scratch = target.with_file_name(".__cob_editor_scratch");
                 {
                     let mut file = match std::fs::File::create(&scratch) {
                         Ok(file) => file,
@@ -216,11 +303,14 @@ impl CobEditor
                 }
 
                 // Safely replace the target file with the scratch.
-                if let Err(err) = std::fs::rename(&scratch, asset_dir.join(unsaved.as_str())) {
+                if let Err(err) = std::fs::rename(&scratch, &target) {
                     tracing::warn!("saving {unsaved:?} failed unexpectedly while renaming scratch: {err:?}");
                 }
             }
         }
+
+        // Files with conflicts remain unsaved.
+        self.unsaved.extend(blocked);
     }
 }
 
@@ -232,11 +322,7 @@ impl Plugin for CobEditorImplPlugin
 {
     fn build(&self, app: &mut App)
     {
-        let added = app.get_added_plugins::<AssetPlugin>();
-        let asset_plugin = added
-            .get(0)
-            .expect("AssetPlugin should be added before CobwebUiPlugin");
-        app.insert_resource(CobEditor::new(asset_plugin.file_path.clone()));
+        app.init_resource::<CobEditor>();
     }
 }
 
@@ -1,6 +1,8 @@
+use std::any::TypeId;
 use std::sync::Arc;
 
 use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
 use serde::de::DeserializeSeed;
 
 use super::*;
@@ -19,8 +21,12 @@ impl Command for SaveEditor
             world.resource_scope::<CobAssetCache, ()>(|world: &mut World, mut cob_cache: Mut<CobAssetCache>| {
                 world.resource_scope::<CobHashRegistry, ()>(
                     |world: &mut World, registry: Mut<CobHashRegistry>| {
-                        let mut c = world.commands();
-                        editor.save(&mut c, &mut cob_cache, &registry);
+                        world.resource_scope::<CobEditorSaveTargets, ()>(
+                            |world: &mut World, targets: Mut<CobEditorSaveTargets>| {
+                                let mut c = world.commands();
+                                editor.save(&mut c, &mut cob_cache, &registry, &targets);
+                            },
+                        );
                     },
                 );
             });
@@ -30,7 +36,7 @@ impl Command for SaveEditor
 
 //-------------------------------------------------------------------------------------------------------------------
 
-/// Command for patching a value in the editor (a command or scene node loadable).
+/// Command for patching a value in the editor (a command or scene node loadable, or a constant).
 #[derive(Debug)]
 pub struct SubmitPatch
 {
@@ -51,6 +57,11 @@ impl Command for SubmitPatch
             return;
         }
 
+        if editor_ref.constant.is_some() {
+            patch_constant(world, editor_ref, self.value);
+            return;
+        }
+
         world.resource_scope::<CobEditor, ()>(|world: &mut World, mut editor: Mut<CobEditor>| {
             // Get the file id.
             let SceneFile::File(file) = editor_ref.scene_ref.file.clone() else {
@@ -134,104 +145,1033 @@ impl Command for SubmitPatch
             new_loadable.recover_fill(targeted);
 
             // Replace the old value.
-            *targeted = new_loadable.clone();
+            let old_loadable = std::mem::replace(targeted, new_loadable.clone());
+
+            // Record the change so it can be undone.
+            let time = world.resource::<Time<Real>>().elapsed();
+            file_data.history.push(EditorHistoryEntry {
+                change: EditorHistoryChange::Patch {
+                    scene_ref: editor_ref.scene_ref.clone(),
+                    loadable_name: editor_ref.loadable_name,
+                    structure_path: editor_ref.structure_path.clone(),
+                    old: old_loadable,
+                    new: new_loadable.clone(),
+                },
+                time,
+            });
 
             // Mark the file as unsaved in the editor.
             let mut commands = world.commands();
             editor.mark_unsaved(&mut commands, file.clone());
 
-            // Try to repair cob asset cache's preprocessed or processed file.
-            let mut cob_cache = world.resource_mut::<CobAssetCache>();
-            if let Some((cache_hash, cache_data, is_processed)) = cob_cache.get_file_info_mut(&file) {
-                // Check file hash.
-                if *cache_hash != editor_ref.file_hash {
-                    tracing::warn!("failed propagating loadable patch for {} in {:?} to backend; target file \
-                        is currently being re-processed, likely due to a hot-reloaded change; the current \
-                        editor view of the file will likely be overwritten soon",
-                        editor_ref.loadable_name, editor_ref.scene_ref);
+            // Propagate the new value.
+            let erased = ErasedLoadable {
+                type_id,
+                loadable: ReflectedLoadable::Value(Arc::new(reflected_target)),
+            };
+            propagate_loadable(
+                world,
+                editor_ref.file_hash,
+                &editor_ref.scene_ref,
+                editor_ref.loadable_name,
+                new_loadable,
+                erased,
+                longname,
+            );
+        });
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Command for undoing the most recent edit to a file in the editor.
+#[derive(Debug)]
+pub struct UndoEditor
+{
+    pub file: CobFile,
+}
+
+impl Command for UndoEditor
+{
+    fn apply(self, world: &mut World)
+    {
+        apply_history_step(world, self.file, true);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Command for re-applying the most recently undone edit to a file in the editor.
+#[derive(Debug)]
+pub struct RedoEditor
+{
+    pub file: CobFile,
+}
+
+impl Command for RedoEditor
+{
+    fn apply(self, world: &mut World)
+    {
+        apply_history_step(world, self.file, false);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// How to resolve a conflict between unsaved changes in the editor and external changes to a file.
+///
+/// See [`EditorFileConflict`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ConflictResolution
+{
+    /// Keep the editor's changes. Saving will overwrite the external changes.
+    KeepMine,
+    /// Discard the editor's unsaved changes.
+    TakeTheirs,
+    /// Merge both sets of changes at the granularity of scene nodes.
+    ///
+    /// Fails if the same scene node (or the same non-scene section) was changed by both. The conflict will remain
+    /// and the failures will be displayed in the editor.
+    Merge,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Command for resolving a conflict between unsaved changes in the editor and external changes to a file.
+#[derive(Debug)]
+pub struct ResolveEditorConflict
+{
+    pub file: CobFile,
+    pub resolution: ConflictResolution,
+}
+
+impl Command for ResolveEditorConflict
+{
+    fn apply(self, world: &mut World)
+    {
+        let file = self.file;
+        world.resource_scope::<CobEditor, ()>(|world: &mut World, mut editor: Mut<CobEditor>| {
+            let Some(file_data) = editor.get_file_mut(&file) else { return };
+            let Some(conflict) = &mut file_data.conflict else {
+                tracing::warn!("ignoring conflict resolution for {:?}; the file has no conflict", file);
+                return;
+            };
+
+            let data = match self.resolution {
+                ConflictResolution::KeepMine => file_data.data.clone(),
+                ConflictResolution::TakeTheirs => {
+                    let mut c = world.commands();
+                    editor.take_theirs(&mut c, &file);
+                    c.react().broadcast(EditorFileExternalChange { file });
                     return;
                 }
+                ConflictResolution::Merge => match merge_cob(&file_data.base, &file_data.data, &conflict.data) {
+                    Ok(merged) => {
+                        // History entries may refer to data that was changed by the merge.
+                        file_data.history.clear();
+                        merged
+                    }
+                    Err(failures) => {
+                        tracing::warn!("failed merging changes to {:?}; conflicting edits: {:?}", file, failures);
+                        conflict.merge_failures = failures;
+                        world
+                            .commands()
+                            .react()
+                            .broadcast(EditorFileConflict { file });
+                        return;
+                    }
+                },
+            };
 
-                // Get targeted value.
-                let Some(targeted) = get_targeted(cache_data, &editor_ref) else {
-                    tracing::error!("failed propagating loadable patch for {} in {:?} to backend; targeted loadable \
-                        not found in target file (processed={is_processed}) (this is a bug)",
-                        editor_ref.loadable_name, editor_ref.scene_ref);
-                    return;
+            // Rebase the editor's data onto the new file data, then send it to the backend.
+            let Some(file_hash) = file_data.rebase(data.clone()) else { return };
+            let is_at_save = file_data.data == file_data.base;
+            let mut c = world.commands();
+            if is_at_save {
+                editor.mark_saved(&mut c, file.clone());
+            }
+            c.react().broadcast(EditorFileExternalChange { file });
+            propagate_file(world, file_hash, data);
+        });
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn apply_history_step(world: &mut World, file: CobFile, undo: bool)
+{
+    world.resource_scope::<CobEditor, ()>(|world: &mut World, mut editor: Mut<CobEditor>| {
+        let Some(file_data) = editor.get_file_mut(&file) else { return };
+        let file_hash = file_data.last_save_hash;
+
+        // Step the history.
+        let step = match undo {
+            true => file_data.history.undo(),
+            false => file_data.history.redo(),
+        };
+        let Some(entry) = step else { return };
+
+        match &entry.change {
+            EditorHistoryChange::Patch { scene_ref, loadable_name, old, new, .. } => {
+                let scene_ref = scene_ref.clone();
+                let loadable_name = *loadable_name;
+                let value = match undo {
+                    true => old.clone(),
+                    false => new.clone(),
                 };
 
-                // Note: targeted value does not need to be resolved, we currently only support fully resolved values.
-
-                // Set targeted value.
-                *targeted = new_loadable;
-
-                // Pass value to the app for use.
-                // - We only do this for processed files since preprocessed files will automatically propagate
-                // values when they are processed.
-                if is_processed {
-                    let erased = ErasedLoadable{ type_id, loadable: ReflectedLoadable::Value(Arc::new(reflected_target)) };
-
-                    match editor_ref.is_command() {
-                        true => {
-                            let mut commands_buffer = world.resource_mut::<CommandsBuffer>();
-                            commands_buffer.patch_command(file, longname, erased);
-                        }
-                        false => {
-                            let mut scenes_buffer = world.resource_mut::<SceneBuffer>();
-                            // TODO: if constants become editable, it may not be safe to naively stick new values
-                            // at the end of the update queue here
-                            // - The main thing is editor changes should be 'transactional' and all effects move
-                            // through as a single block of changes that synchronize with other 'transactional'
-                            // changes such as hot-reloading a file.
-                            scenes_buffer.insert_loadable(
-                                &editor_ref.scene_ref,
-                                None, // Insert in-place.
-                                erased.loadable,
-                                erased.type_id,
-                                longname,
-                            );
-                        }
+                // Prep the reflected value for the app.
+                let (erased, longname) = match reflect_loadable(world, loadable_name, &value) {
+                    Ok(r) => r,
+                    Err(err) => {
+                        tracing::warn!("discarding editor history for {:?}; {err}", file);
+                        file_data.history.clear();
+                        return;
                     }
+                };
+
+                // Set the value in the editor.
+                let Some(targeted) = get_targeted_at(&mut file_data.data, &scene_ref, loadable_name) else {
+                    tracing::warn!("discarding editor history for {:?}; targeted loadable {} in {:?} not found",
+                        file, loadable_name, scene_ref);
+                    file_data.history.clear();
+                    return;
+                };
+                *targeted = value.clone();
+                let is_at_save = file_data.history.is_at_save();
+
+                finish_history_step(&mut world.commands(), &mut editor, file, is_at_save);
+                propagate_loadable(world, file_hash, &scene_ref, loadable_name, value, erased, longname);
+            }
+            EditorHistoryChange::Constant { name, old, new, .. } => {
+                let name = name.clone();
+                let value = match undo {
+                    true => old.clone(),
+                    false => new.clone(),
+                };
+                let Some(def) = get_constant_def_mut(&mut file_data.data, &name) else {
+                    tracing::warn!("discarding editor history for {:?}; constant ${} not found", file, name);
+                    file_data.history.clear();
+                    return;
+                };
+                def.value = value;
+                let data = file_data.data.clone();
+                let is_at_save = file_data.history.is_at_save();
+
+                finish_history_step(&mut world.commands(), &mut editor, file, is_at_save);
+                propagate_file(world, file_hash, data);
+            }
+            EditorHistoryChange::Structure(change) => {
+                if let Err(err) = change.apply(&mut file_data.data, undo) {
+                    tracing::warn!("discarding editor history for {:?}; {err}", file);
+                    file_data.history.clear();
+                    return;
                 }
-            } else {
-                tracing::error!("patch for loadable {} in {:?} could not be propagated to the app because the \
-                    file is missing in the backend (this is a bug)",
-                    editor_ref.loadable_name, editor_ref.scene_ref);
+                let data = file_data.data.clone();
+                let is_at_save = file_data.history.is_at_save();
+
+                finish_history_step(&mut world.commands(), &mut editor, file, is_at_save);
+                propagate_file(world, file_hash, data);
             }
-        });
+        }
+    });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Updates a file's saved status after undo/redo.
+fn finish_history_step(c: &mut Commands, editor: &mut CobEditor, file: CobFile, is_at_save: bool)
+{
+    match is_at_save {
+        true => editor.mark_saved(c, file.clone()),
+        false => editor.mark_unsaved(c, file.clone()),
+    }
+    c.react().broadcast(EditorHistoryChanged { file });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Deserializes a loadable so it can be sent to the app.
+///
+/// Returns the loadable's long name.
+fn reflect_loadable(
+    world: &World,
+    loadable_name: &str,
+    value: &CobLoadable,
+) -> Result<(ErasedLoadable, &'static str), String>
+{
+    let loadables = world.resource::<LoadableRegistry>();
+    let type_registry = world.resource::<AppTypeRegistry>().read();
+    let Some((deserializer, type_id, longname, _)) = get_deserializer(&type_registry, loadable_name, &loadables)
+    else {
+        return Err(format!("failed looking up loadable {loadable_name} in type registry"));
+    };
+    let reflected = deserializer
+        .deserialize(value)
+        .map_err(|err| format!("value for {loadable_name} failed to deserialize: {err:?}"))?;
+
+    Ok((ErasedLoadable { type_id, loadable: ReflectedLoadable::Value(Arc::new(reflected)) }, longname))
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Sends an edited loadable to the cob asset cache and the app.
+fn propagate_loadable(
+    world: &mut World,
+    file_hash: CobFileHash,
+    scene_ref: &SceneRef,
+    loadable_name: &'static str,
+    new_loadable: CobLoadable,
+    erased: ErasedLoadable,
+    longname: &'static str,
+)
+{
+    let SceneFile::File(file) = scene_ref.file.clone() else { return };
+
+    // Try to repair cob asset cache's preprocessed or processed file.
+    let mut cob_cache = world.resource_mut::<CobAssetCache>();
+    let Some((cache_hash, cache_data, is_processed)) = cob_cache.get_file_info_mut(&file) else {
+        tracing::error!("patch for loadable {} in {:?} could not be propagated to the app because the \
+            file is missing in the backend (this is a bug)",
+            loadable_name, scene_ref);
+        return;
+    };
+
+    // Check file hash.
+    if *cache_hash != file_hash {
+        tracing::warn!("failed propagating loadable patch for {} in {:?} to backend; target file \
+            is currently being re-processed, likely due to a hot-reloaded change; the current \
+            editor view of the file will likely be overwritten soon",
+            loadable_name, scene_ref);
+        return;
+    }
+
+    // Get targeted value.
+    let Some(targeted) = get_targeted_at(cache_data, scene_ref, loadable_name) else {
+        tracing::error!("failed propagating loadable patch for {} in {:?} to backend; targeted loadable \
+            not found in target file (processed={is_processed}) (this is a bug)",
+            loadable_name, scene_ref);
+        return;
+    };
+
+    // Note: targeted value does not need to be resolved, we currently only support fully resolved values.
+
+    // Set targeted value.
+    *targeted = new_loadable;
+
+    // Pass value to the app for use.
+    // - We only do this for processed files since preprocessed files will automatically propagate
+    // values when they are processed.
+    if !is_processed {
+        return;
+    }
+
+    match scene_ref.path.iter().next() == Some("#commands") {
+        true => {
+            let mut commands_buffer = world.resource_mut::<CommandsBuffer>();
+            commands_buffer.patch_command(file, longname, erased);
+        }
+        false => {
+            let mut scenes_buffer = world.resource_mut::<SceneBuffer>();
+            // Loadable patches only touch this scene node, so the new value can be inserted in-place.
+            // - Constant edits (see `EditConstant`) are structural edits that re-process the file and its
+            // dependents as a single block, the same as hot-reloading a file.
+            scenes_buffer.insert_loadable(
+                scene_ref,
+                None, // Insert in-place.
+                erased.loadable,
+                erased.type_id,
+                longname,
+            );
+        }
     }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Sends edited file data to the cob asset cache, where it will be re-processed like a hot-reloaded file.
+fn propagate_file(world: &mut World, file_hash: CobFileHash, data: Cob)
+{
+    let file = data.file.clone();
+    world.resource_scope::<CobAssetCache, ()>(|world: &mut World, mut cob_cache: Mut<CobAssetCache>| {
+        let mut commands_buffer = world.resource_mut::<CommandsBuffer>();
+        if !cob_cache.reprocess_file_for_editor(file_hash, data, &mut commands_buffer) {
+            tracing::warn!("failed propagating structural edit for {:?} to backend; target file is unknown or \
+                currently being re-processed, likely due to a hot-reloaded change; the current editor view of the \
+                file will likely be overwritten soon", file);
+        }
+    });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 // TODO: add/remove struct/enum-struct field
 // - requires re-spawning widgets
 // - requires patching the CobLoadable directly, since fields of reflected values cannot be inserted/removed easily
 
 //-------------------------------------------------------------------------------------------------------------------
 
-// TODO: set enum variant
+// TODO: add/remove map entry
 // - requires re-spawning widgets
-// - might require special handling for Option<T>
 
 //-------------------------------------------------------------------------------------------------------------------
 
-// TODO: add/remove list entry
-// - requires re-spawning widgets
+/// Applies a structural edit to a file in the editor and records it in the file's history.
+///
+/// `edit` returns the part of the file it changed, which is recorded instead of the whole file. The new file data
+/// is re-processed by the backend so changes are applied to the app.
+fn edit_file_structure(
+    world: &mut World,
+    scene_ref: &SceneRef,
+    edit: impl FnOnce(&World, &mut Cob) -> Result<EditorStructureChange, String>,
+)
+{
+    let SceneFile::File(file) = &scene_ref.file else {
+        tracing::error!("ignoring structural edit for {:?}; scene ref unexpectedly has a manifest key instead of \
+            file", scene_ref);
+        return;
+    };
+
+    world.resource_scope::<CobEditor, ()>(|world: &mut World, mut editor: Mut<CobEditor>| {
+        let Some(file_data) = editor.get_file_mut(file) else {
+            tracing::warn!("ignoring structural edit for {:?}; file is unknown", scene_ref);
+            return;
+        };
+        if !world.resource::<CobEditorSaveTargets>().is_editable(file) {
+            tracing::warn!("ignoring structural edit for {:?}; file is not editable", scene_ref);
+            return;
+        }
+
+        // Edit a copy of the data so failed edits don't leave partial changes.
+        let mut data = file_data.data.clone();
+        let change = match (edit)(world, &mut data) {
+            Ok(change) => change,
+            Err(err) => {
+                tracing::warn!("ignoring structural edit for {:?}; {err}", scene_ref);
+                return;
+            }
+        };
+        file_data.data = data.clone();
+
+        // Record the change so it can be undone.
+        let time = world.resource::<Time<Real>>().elapsed();
+        file_data.history.push(EditorHistoryEntry { change: EditorHistoryChange::Structure(change), time });
+        let file_hash = file_data.last_save_hash;
+
+        // Rebuild the editor view.
+        let mut commands = world.commands();
+        editor.mark_unsaved(&mut commands, file.clone());
+        commands
+            .react()
+            .broadcast(EditorHistoryChanged { file: file.clone() });
+
+        propagate_file(world, file_hash, data);
+    });
+}
 
 //-------------------------------------------------------------------------------------------------------------------
 
-// TODO: add/remove map entry
-// - requires re-spawning widgets
+/// Moves `entries[idx]` by `offset` positions among the entries that satisfy `is_sibling`.
+///
+/// Returns the entry's new index.
+fn move_entry<T>(entries: &mut Vec<T>, idx: usize, offset: isize, is_sibling: impl Fn(&T) -> bool) -> usize
+{
+    let siblings: Vec<usize> = entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, e)| is_sibling(e).then_some(i))
+        .collect();
+    let Some(pos) = siblings.iter().position(|i| *i == idx) else { return idx };
+    let target = (pos as isize + offset).clamp(0, siblings.len() as isize - 1) as usize;
+    if target == pos {
+        return idx;
+    }
+
+    let entry = entries.remove(idx);
+    entries.insert(siblings[target], entry);
+    siblings[target]
+}
 
 //-------------------------------------------------------------------------------------------------------------------
 
-// TODO: add/remove/move scene node
-// - requires re-spawning widgets
+/// Applies `edit` to a scene node and returns the change to the node's entries, excluding child nodes.
+fn edit_node_entries(
+    data: &mut Cob,
+    path: &ScenePath,
+    edit: impl FnOnce(&mut CobSceneLayer) -> Result<(), String>,
+) -> Result<EditorStructureChange, String>
+{
+    let layer = data
+        .get_scene_layer_mut(path)
+        .ok_or_else(|| "scene node not found".to_string())?;
+    let old = own_entries(layer).into_iter().cloned().collect();
+    (edit)(layer)?;
+    let new = own_entries(layer).into_iter().cloned().collect();
+    Ok(EditorStructureChange::NodeEntries { path: path.clone(), old, new })
+}
 
 //-------------------------------------------------------------------------------------------------------------------
 
-// TODO: add/remove/move loadable
-// - requires re-spawning widgets
+/// Applies `edit` to a file's `#commands` sections and returns the change to the sections.
+fn edit_commands(
+    data: &mut Cob,
+    edit: impl FnOnce(&mut Cob) -> Result<(), String>,
+) -> Result<EditorStructureChange, String>
+{
+    let old = sections_of_kind(data, "#commands").into_iter().cloned().collect();
+    (edit)(data)?;
+    let new = sections_of_kind(data, "#commands").into_iter().cloned().collect();
+    Ok(EditorStructureChange::Commands { old, new })
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Finds the command loadable with the given canonical name.
+fn find_command<'a>(data: &'a mut Cob, name: &str) -> Option<(&'a mut Vec<CobCommandEntry>, usize)>
+{
+    let mut id_scratch = String::default();
+    data.sections.iter_mut().find_map(|s| {
+        let CobSection::Commands(commands) = s else { return None };
+        let idx = commands.entries.iter().position(|e| match e {
+            CobCommandEntry::Loadable(loadable) => {
+                id_scratch = loadable.id.to_canonical(Some(std::mem::take(&mut id_scratch)));
+                id_scratch == name
+            }
+            _ => false,
+        })?;
+        Some((&mut commands.entries, idx))
+    })
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn is_command_ref(scene_ref: &SceneRef) -> bool
+{
+    scene_ref.path.iter().next() == Some("#commands")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Scene node names must be snake-case-like identifiers (e.g. `a_b_c`).
+fn is_valid_node_name(name: &str) -> bool
+{
+    name.chars().next().map(|c| c.is_ascii_alphanumeric()).unwrap_or(false)
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the path and layer of a child scene node's parent, and the node's index in the parent.
+fn find_child_layer<'a>(
+    data: &'a mut Cob,
+    path: &ScenePath,
+) -> Result<(ScenePath, &'a mut CobSceneLayer, usize), String>
+{
+    let Some(parent_path) = path.parent().filter(|p| p.len() > 0) else {
+        return Err("root scenes cannot be edited".into());
+    };
+    let name = path.iter().last().unwrap();
+    let parent = data
+        .get_scene_layer_mut(&parent_path)
+        .ok_or_else(|| format!("parent node {:?} not found", parent_path))?;
+    let idx = parent
+        .find_layer(name)
+        .ok_or_else(|| format!("node \"{name}\" not found"))?;
+    Ok((parent_path, parent, idx))
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the type id of a loadable and checks that it can be used as a command or in a scene node.
+fn get_loadable_type_id(world: &World, loadable_name: &str, is_command: bool) -> Result<TypeId, String>
+{
+    let loadables = world.resource::<LoadableRegistry>();
+    let type_registry = world.resource::<AppTypeRegistry>().read();
+    let Some((_, type_id, _, _)) = get_registration(&type_registry, loadable_name, &loadables) else {
+        return Err(format!("loadable {loadable_name} is not registered"));
+    };
+    match is_command {
+        true if !loadables.is_command(type_id) => Err(format!("{loadable_name} is not a command")),
+        false if loadables.get_for_node(type_id).is_none() => {
+            Err(format!("{loadable_name} can't be inserted to a scene node"))
+        }
+        _ => Ok(type_id),
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Makes a new loadable with its default value.
+fn make_default_loadable(world: &World, loadable_name: &str, is_command: bool) -> Result<CobLoadable, String>
+{
+    let type_id = get_loadable_type_id(world, loadable_name, is_command)?;
+    let type_registry = world.resource::<AppTypeRegistry>().read();
+    let Some(reflect_default) = type_registry.get_type_data::<ReflectDefault>(type_id) else {
+        return Err(format!("{loadable_name} does not reflect Default"));
+    };
+
+    let value = reflect_default.default();
+    CobLoadable::extract_partial_reflect(value.as_partial_reflect(), &type_registry)
+        .map_err(|err| format!("failed extracting default value of {loadable_name}: {err:?}"))
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Inserts a loadable into a scene node or the `#commands` section.
+///
+/// If `replace` is `true` then an existing loadable with the same name will be replaced, otherwise it is an error
+/// for the loadable to exist already.
+fn insert_loadable(
+    data: &mut Cob,
+    path: &ScenePath,
+    is_command: bool,
+    mut loadable: CobLoadable,
+    replace: bool,
+) -> Result<EditorStructureChange, String>
+{
+    let name = loadable.id.to_canonical(None);
+
+    if is_command {
+        return edit_commands(data, |data| {
+            if let Some((entries, idx)) = find_command(data, &name) {
+                let CobCommandEntry::Loadable(existing) = &mut entries[idx] else { unreachable!() };
+                if !replace {
+                    return Err(format!("command {name} already exists"));
+                }
+                loadable.recover_fill(existing);
+                *existing = loadable;
+                return Ok(());
+            }
+            loadable.fill = CobFill::new("\n");
+            data.unconditional_commands_mut()
+                .entries
+                .push(CobCommandEntry::Loadable(loadable));
+            Ok(())
+        });
+    }
+
+    edit_node_entries(data, path, |layer| {
+        if let Some(idx) = layer.find_loadable(&name) {
+            let CobSceneLayerEntry::Loadable(existing) = &mut layer.entries[idx] else { unreachable!() };
+            if !replace {
+                return Err(format!("{name} already exists in the scene node"));
+            }
+            loadable.recover_fill(existing);
+            *existing = loadable;
+            return Ok(());
+        }
+        loadable.fill = CobFill::new(format!("\n{}", " ".repeat(layer.entry_indent())));
+        let idx = layer
+            .entries
+            .iter()
+            .position(|e| !matches!(e, CobSceneLayerEntry::Loadable(_) | CobSceneLayerEntry::LoadableMacroCall(_)))
+            .unwrap_or(layer.entries.len());
+        layer
+            .entries
+            .insert(idx, CobSceneLayerEntry::Loadable(loadable));
+        Ok(())
+    })
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Command for inserting a loadable with its default value into a scene node or the `#commands` section.
+///
+/// The loadable must be registered and reflect `Default`. Scene node loadables are inserted after the node's
+/// existing loadables, and commands are inserted at the end of the file's first unconditional `#commands`
+/// section.
+#[derive(Debug)]
+pub struct InsertLoadable
+{
+    /// Scene node that receives the loadable. Use the path `#commands` to insert a command.
+    pub scene_ref: SceneRef,
+    /// Short name of the loadable (e.g. `BackgroundColor`).
+    pub loadable_name: String,
+}
+
+impl Command for InsertLoadable
+{
+    fn apply(self, world: &mut World)
+    {
+        let is_command = is_command_ref(&self.scene_ref);
+        edit_file_structure(world, &self.scene_ref, |world, data| {
+            let loadable = make_default_loadable(world, &self.loadable_name, is_command)?;
+            insert_loadable(data, &self.scene_ref.path, is_command, loadable, false)
+        });
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Command for setting the value of a loadable in a scene node or the `#commands` section.
+///
+/// Replaces the existing loadable with the same name, or inserts the loadable if it doesn't exist. Unlike
+/// [`SubmitPatch`], the new value doesn't need to match the structure of the existing value (e.g. an enum
+/// loadable can switch variants).
+#[derive(Debug)]
+pub struct SetLoadable
+{
+    /// Scene node that receives the loadable. Use the path `#commands` to set a command.
+    pub scene_ref: SceneRef,
+    pub loadable: CobLoadable,
+}
+
+impl Command for SetLoadable
+{
+    fn apply(self, world: &mut World)
+    {
+        let Self { scene_ref, loadable } = self;
+        let is_command = is_command_ref(&scene_ref);
+        let path = scene_ref.path.clone();
+        edit_file_structure(world, &scene_ref, move |world, data| {
+            let name = loadable.id.to_canonical(None);
+            get_loadable_type_id(world, &name, is_command)?;
+            insert_loadable(data, &path, is_command, loadable, true)
+        });
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Command for removing a loadable from a scene node or the `#commands` section.
+#[derive(Debug)]
+pub struct RemoveLoadable
+{
+    /// Scene node that contains the loadable. Use the path `#commands` to remove a command.
+    pub scene_ref: SceneRef,
+    /// Short name of the loadable (e.g. `BackgroundColor`).
+    pub loadable_name: String,
+}
+
+impl Command for RemoveLoadable
+{
+    fn apply(self, world: &mut World)
+    {
+        let is_command = is_command_ref(&self.scene_ref);
+        edit_file_structure(world, &self.scene_ref, |_, data| {
+            if is_command {
+                return edit_commands(data, |data| {
+                    let (entries, idx) = find_command(data, &self.loadable_name)
+                        .ok_or_else(|| format!("command {} not found", self.loadable_name))?;
+                    entries.remove(idx);
+                    Ok(())
+                });
+            }
+
+            edit_node_entries(data, &self.scene_ref.path, |layer| {
+                let idx = layer
+                    .find_loadable(&self.loadable_name)
+                    .ok_or_else(|| format!("loadable {} not found", self.loadable_name))?;
+                layer.entries.remove(idx);
+                Ok(())
+            })
+        });
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Command for moving a loadable up (negative offset) or down (positive offset) among the other loadables in a
+/// scene node or `#commands` section.
+#[derive(Debug)]
+pub struct MoveLoadable
+{
+    /// Scene node that contains the loadable. Use the path `#commands` to move a command.
+    pub scene_ref: SceneRef,
+    /// Short name of the loadable (e.g. `BackgroundColor`).
+    pub loadable_name: String,
+    pub offset: isize,
+}
+
+impl Command for MoveLoadable
+{
+    fn apply(self, world: &mut World)
+    {
+        let is_command = is_command_ref(&self.scene_ref);
+        edit_file_structure(world, &self.scene_ref, |_, data| {
+            if is_command {
+                return edit_commands(data, |data| {
+                    let (entries, idx) = find_command(data, &self.loadable_name)
+                        .ok_or_else(|| format!("command {} not found", self.loadable_name))?;
+                    move_entry(entries, idx, self.offset, |e| matches!(e, CobCommandEntry::Loadable(_)));
+                    Ok(())
+                });
+            }
+
+            edit_node_entries(data, &self.scene_ref.path, |layer| {
+                let idx = layer
+                    .find_loadable(&self.loadable_name)
+                    .ok_or_else(|| format!("loadable {} not found", self.loadable_name))?;
+                move_entry(&mut layer.entries, idx, self.offset, |e| matches!(e, CobSceneLayerEntry::Loadable(_)));
+                Ok(())
+            })
+        });
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Command for adding an empty child node to the end of a scene node.
+#[derive(Debug)]
+pub struct InsertSceneNode
+{
+    pub parent: SceneRef,
+    /// Name of the new node. Must be unique among its siblings.
+    pub name: String,
+}
+
+impl Command for InsertSceneNode
+{
+    fn apply(self, world: &mut World)
+    {
+        edit_file_structure(world, &self.parent, |_, data| {
+            if !is_valid_node_name(&self.name) {
+                return Err(format!("invalid node name \"{}\"", self.name));
+            }
+            let layer = data
+                .get_scene_layer_mut(&self.parent.path)
+                .ok_or_else(|| "parent node not found".to_string())?;
+            if layer.find_layer(&self.name).is_some() {
+                return Err(format!("node \"{}\" already exists", self.name));
+            }
+            let node = CobSceneLayer::new_empty(self.name.as_str(), layer.entry_indent());
+            layer.entries.push(CobSceneLayerEntry::Layer(node.clone()));
+            Ok(EditorStructureChange::InsertNode {
+                parent: self.parent.path.clone(),
+                index: layer.entries.len() - 1,
+                node,
+            })
+        });
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Command for renaming a child scene node. Root scenes cannot be renamed.
+#[derive(Debug)]
+pub struct RenameSceneNode
+{
+    pub scene_ref: SceneRef,
+    /// New name of the node. Must be unique among its siblings.
+    pub name: String,
+}
+
+impl Command for RenameSceneNode
+{
+    fn apply(self, world: &mut World)
+    {
+        edit_file_structure(world, &self.scene_ref, |_, data| {
+            if !is_valid_node_name(&self.name) {
+                return Err(format!("invalid node name \"{}\"", self.name));
+            }
+            let (parent_path, parent, idx) = find_child_layer(data, &self.scene_ref.path)?;
+            if parent.find_layer(&self.name).is_some() {
+                return Err(format!("node \"{}\" already exists", self.name));
+            }
+            let CobSceneLayerEntry::Layer(layer) = &mut parent.entries[idx] else { unreachable!() };
+            let new = CobSceneNodeName(self.name.as_str().into());
+            let old = std::mem::replace(&mut layer.name, new.clone());
+            Ok(EditorStructureChange::RenameNode { parent: parent_path, index: idx, old, new })
+        });
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Command for removing a child scene node and its descendants. Root scenes cannot be removed.
+#[derive(Debug)]
+pub struct RemoveSceneNode
+{
+    pub scene_ref: SceneRef,
+}
+
+impl Command for RemoveSceneNode
+{
+    fn apply(self, world: &mut World)
+    {
+        edit_file_structure(world, &self.scene_ref, |_, data| {
+            let (parent_path, parent, idx) = find_child_layer(data, &self.scene_ref.path)?;
+            let CobSceneLayerEntry::Layer(node) = parent.entries.remove(idx) else { unreachable!() };
+            Ok(EditorStructureChange::RemoveNode { parent: parent_path, index: idx, node })
+        });
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Command for moving a child scene node up (negative offset) or down (positive offset) among its sibling nodes.
+#[derive(Debug)]
+pub struct MoveSceneNode
+{
+    pub scene_ref: SceneRef,
+    pub offset: isize,
+}
+
+impl Command for MoveSceneNode
+{
+    fn apply(self, world: &mut World)
+    {
+        edit_file_structure(world, &self.scene_ref, |_, data| {
+            let (parent_path, parent, idx) = find_child_layer(data, &self.scene_ref.path)?;
+            let is_node = |e: &CobSceneLayerEntry| matches!(e, CobSceneLayerEntry::Layer(_));
+            let to = move_entry(&mut parent.entries, idx, self.offset, is_node);
+            Ok(EditorStructureChange::MoveNode { parent: parent_path, from: idx, to })
+        });
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Parses the raw COB text of a constant value (e.g. `10px` or `\ a b \`).
+fn parse_constant_value(file: &CobFile, text: &str) -> Result<CobConstantValue, String>
+{
+    let span = Span::new_extra(text.trim(), CobLocationMetadata { file: file.as_str() });
+    let Ok((Some(value), _, remaining)) = CobConstantValue::try_parse(CobFill::new(" "), span) else {
+        return Err(format!("failed parsing \"{text}\" as a constant value"));
+    };
+    if !remaining.fragment().trim().is_empty() {
+        return Err(format!("failed parsing \"{text}\" as a constant value; unexpected trailing content"));
+    }
+    Ok(value)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Parses raw COB text as a loadable (e.g. `BackgroundColor(#FF0000)`).
+pub(super) fn parse_loadable(file: &CobFile, text: &str) -> Result<CobLoadable, String>
+{
+    let span = Span::new_extra(text.trim(), CobLocationMetadata { file: file.as_str() });
+    let Ok((Some(loadable), _, remaining)) = CobLoadable::try_parse(CobFill::default(), span) else {
+        return Err(format!("failed parsing \"{text}\" as a loadable"));
+    };
+    if !remaining.fragment().trim().is_empty() {
+        return Err(format!("failed parsing \"{text}\" as a loadable; unexpected trailing content"));
+    }
+    Ok(loadable)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Command for replacing the value of a constant in a file's `#defs` sections.
+///
+/// Files that import the constant's file are re-processed along with it, so the new value is applied to all
+/// dependents.
+#[derive(Debug)]
+pub struct EditConstant
+{
+    pub file: CobFile,
+    /// Name of the constant without the leading `$`.
+    pub name: String,
+    /// Raw COB text of the new value (e.g. `10px` or `\ a b \`).
+    pub value: String,
+}
+
+impl Command for EditConstant
+{
+    fn apply(self, world: &mut World)
+    {
+        let edited = edit_constant(world, &self.file, &self.name, None, |_, _| {
+            parse_constant_value(&self.file, &self.value)
+        });
+
+        // Rebuild the editor view in case the constant's type changed.
+        if edited {
+            world
+                .commands()
+                .react()
+                .broadcast(EditorHistoryChanged { file: self.file });
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Replaces the value of a constant with the value returned by `edit`, and records the change in the file's
+/// history.
+///
+/// The file is re-processed by the backend so the new value reaches every file that imports the constant. The
+/// editor view is not rebuilt.
+///
+/// Returns `false` if the edit failed.
+fn edit_constant(
+    world: &mut World,
+    file: &CobFile,
+    name: &str,
+    structure_path: Option<ReflectStructurePath>,
+    edit: impl FnOnce(&World, &CobConstantValue) -> Result<CobConstantValue, String>,
+) -> bool
+{
+    world.resource_scope::<CobEditor, bool>(|world: &mut World, mut editor: Mut<CobEditor>| {
+        let Some(file_data) = editor.get_file_mut(file) else {
+            tracing::warn!("ignoring edit to constant ${} in {:?}; file is unknown", name, file);
+            return false;
+        };
+        if !world.resource::<CobEditorSaveTargets>().is_editable(file) {
+            tracing::warn!("ignoring edit to constant ${} in {:?}; file is not editable", name, file);
+            return false;
+        }
+        let Some(def) = get_constant_def_mut(&mut file_data.data, name) else {
+            tracing::warn!("ignoring edit to constant ${} in {:?}; constant not found", name, file);
+            return false;
+        };
+
+        let mut value = match (edit)(world, &def.value) {
+            Ok(value) => value,
+            Err(err) => {
+                tracing::warn!("ignoring edit to constant ${} in {:?}; {err}", name, file);
+                return false;
+            }
+        };
+        value.recover_fill(&def.value);
+        let old = std::mem::replace(&mut def.value, value.clone());
+
+        // Record the change so it can be undone.
+        let time = world.resource::<Time<Real>>().elapsed();
+        file_data.history.push(EditorHistoryEntry {
+            change: EditorHistoryChange::Constant { name: name.into(), structure_path, old, new: value },
+            time,
+        });
+        let file_hash = file_data.last_save_hash;
+        let data = file_data.data.clone();
+
+        let mut commands = world.commands();
+        editor.mark_unsaved(&mut commands, file.clone());
+
+        propagate_file(world, file_hash, data);
+        true
+    })
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Applies a [`SubmitPatch`] to a constant.
+fn patch_constant(world: &mut World, editor_ref: CobEditorRef, value: Box<dyn PartialReflect + 'static>)
+{
+    let Some(constant) = &editor_ref.constant else { return };
+    let SceneFile::File(file) = &editor_ref.scene_ref.file else { return };
+
+    // Exit if file hash doesn't match.
+    let current_hash = world
+        .resource::<CobEditor>()
+        .get_file(file)
+        .map(|f| f.last_save_hash);
+    if current_hash != Some(editor_ref.file_hash) {
+        tracing::warn!("ignoring editor patch for constant ${} in {:?}; widget has a stale editor reference",
+            constant.name, file);
+        return;
+    }
+
+    let structure_path = editor_ref.structure_path.clone();
+    edit_constant(world, file, &constant.name, Some(structure_path.clone()), |world, old| {
+        let type_registry = world.resource::<AppTypeRegistry>().read();
+        let mut reflected = reflect_constant(&type_registry, constant.type_id, old)?;
+        structure_path
+            .try_patch_value(&mut reflected, value)
+            .map_err(|err| format!("failed applying patch with path {structure_path:?}; error: {err:?}"))?;
+        let value = CobValue::extract_partial_reflect(reflected.as_ref(), &type_registry)
+            .map_err(|err| format!("failed extracting patched value: {err:?}"))?;
+        Ok(CobConstantValue::Value(value))
+    });
+}
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,586 @@
+use std::any::TypeId;
+
+use bevy::prelude::*;
+use bevy::reflect::{TypeInfo, TypeRegistry, VariantInfo};
+use bevy_cobweb::prelude::*;
+use smol_str::SmolStr;
+
+use super::*;
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+const CONSTANT_SEPARATOR: &str = "::";
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Reactive event broadcasted when [`EditorDefsState`] changes.
+#[derive(Debug, Clone)]
+pub(super) struct EditorDefsChanged;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A reference to a constant found by [`find_constant_usages`].
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum ConstantUsage
+{
+    /// A loadable in a scene node references the constant.
+    SceneNode(SceneRef),
+    /// A command references the constant.
+    Command
+    {
+        file: CobFile,
+        name: SmolStr,
+    },
+    /// Another constant's value references the constant.
+    Constant
+    {
+        file: CobFile,
+        name: SmolStr,
+    },
+}
+
+impl ConstantUsage
+{
+    /// Gets a short description of the usage for display.
+    pub(super) fn describe(&self) -> String
+    {
+        match self {
+            Self::SceneNode(scene_ref) => {
+                let path = scene_ref.path.iter().collect::<Vec<_>>().join(SCENE_PATH_SEPARATOR);
+                format!("{}: \"{}\"", scene_ref.file.as_str(), path)
+            }
+            Self::Command { file, name } => format!("{}: #commands {}", file.as_str(), name),
+            Self::Constant { file, name } => format!("{}: ${}", file.as_str(), name),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn visit_map_entry(entry: &CobMapEntry, f: &mut impl FnMut(&CobConstantPath))
+{
+    match entry {
+        CobMapEntry::KeyValue(key_value) => {
+            if let CobMapKey::Value(key) = &key_value.key {
+                visit_value(key, f);
+            }
+            visit_value(&key_value.value, f);
+        }
+        CobMapEntry::Constant(constant) => f(&constant.path),
+        CobMapEntry::MacroParam(_) => (),
+    }
+}
+
+fn visit_map(map: &CobMap, f: &mut impl FnMut(&CobConstantPath))
+{
+    map.entries.iter().for_each(|e| visit_map_entry(e, f));
+}
+
+fn visit_enum(value: &CobEnum, f: &mut impl FnMut(&CobConstantPath))
+{
+    match &value.variant {
+        CobEnumVariant::Unit => (),
+        CobEnumVariant::Tuple(tuple) => tuple.entries.iter().for_each(|v| visit_value(v, f)),
+        CobEnumVariant::Array(array) => array.entries.iter().for_each(|v| visit_value(v, f)),
+        CobEnumVariant::Map(map) => visit_map(map, f),
+    }
+}
+
+/// Calls `f` on every constant referenced in a value.
+///
+/// Constants passed to data macro calls are not visited.
+fn visit_value(value: &CobValue, f: &mut impl FnMut(&CobConstantPath))
+{
+    match value {
+        CobValue::Enum(value) => visit_enum(value, f),
+        CobValue::Array(array) => array.entries.iter().for_each(|v| visit_value(v, f)),
+        CobValue::Tuple(tuple) => tuple.entries.iter().for_each(|v| visit_value(v, f)),
+        CobValue::Map(map) => visit_map(map, f),
+        CobValue::Constant(constant) => f(&constant.path),
+        _ => (),
+    }
+}
+
+fn visit_loadable(loadable: &CobLoadable, f: &mut impl FnMut(&CobConstantPath))
+{
+    match &loadable.variant {
+        CobLoadableVariant::Unit => (),
+        CobLoadableVariant::Tuple(tuple) => tuple.entries.iter().for_each(|v| visit_value(v, f)),
+        CobLoadableVariant::Array(array) => array.entries.iter().for_each(|v| visit_value(v, f)),
+        CobLoadableVariant::Map(map) => visit_map(map, f),
+        CobLoadableVariant::Enum(value) => visit_enum(value, f),
+    }
+}
+
+fn visit_constant_value(value: &CobConstantValue, f: &mut impl FnMut(&CobConstantPath))
+{
+    match value {
+        CobConstantValue::Value(value) => visit_value(value, f),
+        CobConstantValue::ValueGroup(group) => {
+            for entry in group.entries.iter() {
+                match entry {
+                    CobValueGroupEntry::KeyValue(key_value) => {
+                        if let CobMapKey::Value(key) = &key_value.key {
+                            visit_value(key, f);
+                        }
+                        visit_value(&key_value.value, f);
+                    }
+                    CobValueGroupEntry::Value(value) => visit_value(value, f),
+                }
+            }
+        }
+    }
+}
+
+/// Returns `true` if any loadable in `loadables` references a constant in `paths`.
+fn any_references<'a>(loadables: impl IntoIterator<Item = &'a CobLoadable>, paths: &[String]) -> bool
+{
+    let mut found = false;
+    for loadable in loadables {
+        visit_loadable(loadable, &mut |path| found |= paths.iter().any(|p| p == path.as_str()));
+    }
+    found
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Collects the path prefixes `importer` uses to access constants defined in `target`.
+///
+/// There can be multiple prefixes if `target` is imported more than once (e.g. transitively via different
+/// files). The prefix is empty for constants defined in `importer`.
+fn import_prefixes(
+    editor: &CobEditor,
+    manifest: &ManifestMap,
+    importer: &CobFile,
+    target: &CobFile,
+    visiting: &mut Vec<CobFile>,
+) -> Vec<String>
+{
+    if importer == target {
+        return vec![String::default()];
+    }
+    // Import cycles are invalid, but we guard against them since the editor sees files before they are
+    // validated.
+    if visiting.contains(importer) {
+        return Vec::default();
+    }
+    let Some(file_data) = editor.get_file(importer) else { return Vec::default() };
+
+    visiting.push(importer.clone());
+    let mut prefixes = Vec::default();
+    for section in file_data.data.sections.iter() {
+        let CobSection::Import(import) = section else { continue };
+        for entry in import.entries.iter() {
+            let Some(imported) = manifest.get(&entry.key) else { continue };
+            for prefix in import_prefixes(editor, manifest, &imported, target, visiting) {
+                let alias = entry.alias.as_str();
+                let prefix = match (alias.is_empty(), prefix.is_empty()) {
+                    (true, _) => prefix,
+                    (false, true) => alias.to_string(),
+                    (false, false) => format!("{alias}{CONSTANT_SEPARATOR}{prefix}"),
+                };
+                if !prefixes.contains(&prefix) {
+                    prefixes.push(prefix);
+                }
+            }
+        }
+    }
+    visiting.pop();
+
+    prefixes
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Collects a scene node and its descendants if their loadables reference a constant in `paths`.
+fn collect_layer_usages(
+    usages: &mut Vec<ConstantUsage>,
+    paths: &[String],
+    scene_ref: SceneRef,
+    layer: &CobSceneLayer,
+)
+{
+    let loadables = layer.entries.iter().filter_map(|e| match e {
+        CobSceneLayerEntry::Loadable(loadable) => Some(loadable),
+        _ => None,
+    });
+    if any_references(loadables, paths) {
+        usages.push(ConstantUsage::SceneNode(scene_ref.clone()));
+    }
+    for entry in layer.entries.iter() {
+        let CobSceneLayerEntry::Layer(child) = entry else { continue };
+        collect_layer_usages(usages, paths, scene_ref.clone() + child.name.as_str(), child);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the paths `user` can use to reference the constant `$name` defined in `file`.
+fn constant_paths(
+    editor: &CobEditor,
+    manifest: &ManifestMap,
+    user: &CobFile,
+    file: &CobFile,
+    name: &str,
+) -> Vec<String>
+{
+    import_prefixes(editor, manifest, user, file, &mut Vec::default())
+        .into_iter()
+        .map(|prefix| match prefix.is_empty() {
+            true => name.to_string(),
+            false => format!("{prefix}{CONSTANT_SEPARATOR}{name}"),
+        })
+        .collect()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks the reflected types of loadable values that reference a constant.
+struct ConstantTypeSearch<'a>
+{
+    registry: &'a TypeRegistry,
+    /// Paths that reference the constant in the current file.
+    paths: Vec<String>,
+    /// The type of the references found so far.
+    found: Option<TypeId>,
+    /// Set if a reference's type can't be determined or differs from other references.
+    ambiguous: bool,
+}
+
+impl<'a> ConstantTypeSearch<'a>
+{
+    fn is_reference(&self, path: &CobConstantPath) -> bool
+    {
+        self.paths.iter().any(|p| p == path.as_str())
+    }
+
+    fn record(&mut self, type_id: TypeId)
+    {
+        match self.found {
+            None => self.found = Some(type_id),
+            Some(found) => self.ambiguous |= found != type_id,
+        }
+    }
+
+    /// Marks the search as ambiguous if `visit` finds a reference.
+    ///
+    /// Used for values whose type is unknown.
+    fn visit_untyped(&mut self, visit: impl FnOnce(&mut dyn FnMut(&CobConstantPath)))
+    {
+        let mut found = false;
+        visit(&mut |path| found |= self.paths.iter().any(|p| p == path.as_str()));
+        self.ambiguous |= found;
+    }
+
+    /// Gets the type info of `type_id`, skipping newtypes and options since they are transparent in COB.
+    fn get_info(&self, mut type_id: TypeId) -> Option<&'static TypeInfo>
+    {
+        loop {
+            let info = self.registry.get_type_info(type_id)?;
+            type_id = match info {
+                TypeInfo::TupleStruct(info) if info.field_len() == 1 => info.field_at(0)?.type_id(),
+                TypeInfo::Enum(info) if info.type_path().starts_with("core::option::Option<") => {
+                    let Some(VariantInfo::Tuple(some)) = info.variant("Some") else { return None };
+                    some.field_at(0)?.type_id()
+                }
+                _ => return Some(info),
+            };
+        }
+    }
+
+    fn visit_loadable(&mut self, loadable: &CobLoadable, type_id: TypeId)
+    {
+        match &loadable.variant {
+            CobLoadableVariant::Unit => (),
+            CobLoadableVariant::Tuple(tuple) => self.visit_tuple(tuple, type_id),
+            CobLoadableVariant::Array(array) => self.visit_array(array, type_id),
+            CobLoadableVariant::Map(map) => self.visit_map(map, type_id),
+            CobLoadableVariant::Enum(value) => self.visit_enum(value, type_id),
+        }
+    }
+
+    fn visit_value(&mut self, value: &CobValue, type_id: TypeId)
+    {
+        match value {
+            CobValue::Constant(constant) => {
+                if self.is_reference(&constant.path) {
+                    self.record(type_id);
+                }
+            }
+            CobValue::Enum(value) => self.visit_enum(value, type_id),
+            CobValue::Array(array) => self.visit_array(array, type_id),
+            CobValue::Tuple(tuple) => self.visit_tuple(tuple, type_id),
+            CobValue::Map(map) => self.visit_map(map, type_id),
+            _ => (),
+        }
+    }
+
+    fn visit_entries(&mut self, entries: &[CobValue], type_id: impl Fn(usize) -> Option<TypeId>)
+    {
+        for (idx, entry) in entries.iter().enumerate() {
+            match (type_id)(idx) {
+                Some(type_id) => self.visit_value(entry, type_id),
+                None => self.visit_untyped(|mut f| visit_value(entry, &mut f)),
+            }
+        }
+    }
+
+    fn visit_array(&mut self, array: &CobArray, type_id: TypeId)
+    {
+        let item = match self.get_info(type_id) {
+            Some(TypeInfo::List(info)) => Some(info.item_ty().id()),
+            Some(TypeInfo::Array(info)) => Some(info.item_ty().id()),
+            Some(TypeInfo::Set(info)) => Some(info.value_ty().id()),
+            _ => None,
+        };
+        self.visit_entries(&array.entries, |_| item);
+    }
+
+    fn visit_tuple(&mut self, tuple: &CobTuple, type_id: TypeId)
+    {
+        match self.get_info(type_id) {
+            Some(TypeInfo::TupleStruct(info)) => {
+                self.visit_entries(&tuple.entries, |idx| info.field_at(idx).map(|f| f.type_id()))
+            }
+            Some(TypeInfo::Tuple(info)) => {
+                self.visit_entries(&tuple.entries, |idx| info.field_at(idx).map(|f| f.type_id()))
+            }
+            Some(TypeInfo::List(info)) => {
+                let item = info.item_ty().id();
+                self.visit_entries(&tuple.entries, |_| Some(item))
+            }
+            _ => self.visit_entries(&tuple.entries, |_| None),
+        }
+    }
+
+    fn visit_fields(&mut self, map: &CobMap, type_id: impl Fn(&str) -> Option<TypeId>)
+    {
+        for entry in map.entries.iter() {
+            match entry {
+                CobMapEntry::KeyValue(CobMapKeyValue { key: CobMapKey::FieldName { name, .. }, value, .. }) => {
+                    match (type_id)(name.as_str()) {
+                        Some(type_id) => self.visit_value(value, type_id),
+                        None => self.visit_untyped(|mut f| visit_value(value, &mut f)),
+                    }
+                }
+                _ => self.visit_untyped(|mut f| visit_map_entry(entry, &mut f)),
+            }
+        }
+    }
+
+    fn visit_map(&mut self, map: &CobMap, type_id: TypeId)
+    {
+        match self.get_info(type_id) {
+            Some(TypeInfo::Struct(info)) => self.visit_fields(map, |name| info.field(name).map(|f| f.type_id())),
+            _ => self.visit_untyped(|mut f| visit_map(map, &mut f)),
+        }
+    }
+
+    fn visit_enum(&mut self, value: &CobEnum, type_id: TypeId)
+    {
+        let variant = match self.get_info(type_id) {
+            Some(TypeInfo::Enum(info)) => info.variant(value.id.0.as_str()),
+            _ => None,
+        };
+        match (variant, &value.variant) {
+            (_, CobEnumVariant::Unit) => (),
+            (Some(VariantInfo::Tuple(info)), CobEnumVariant::Tuple(tuple)) => {
+                self.visit_entries(&tuple.entries, |idx| info.field_at(idx).map(|f| f.type_id()))
+            }
+            (Some(VariantInfo::Tuple(info)), CobEnumVariant::Array(array)) if info.field_len() == 1 => {
+                self.visit_array(array, info.field_at(0).unwrap().type_id())
+            }
+            (Some(VariantInfo::Tuple(info)), CobEnumVariant::Map(map)) if info.field_len() == 1 => {
+                self.visit_map(map, info.field_at(0).unwrap().type_id())
+            }
+            (Some(VariantInfo::Struct(info)), CobEnumVariant::Map(map)) => {
+                self.visit_fields(map, |name| info.field(name).map(|f| f.type_id()))
+            }
+            _ => self.visit_untyped(|mut f| visit_enum(value, &mut f)),
+        }
+    }
+
+    fn visit_layer(&mut self, loadables: &LoadableRegistry, layer: &CobSceneLayer)
+    {
+        for entry in layer.entries.iter() {
+            match entry {
+                CobSceneLayerEntry::Loadable(loadable) => self.visit_loadable_entry(loadables, loadable),
+                CobSceneLayerEntry::Layer(child) => self.visit_layer(loadables, child),
+                _ => (),
+            }
+        }
+    }
+
+    fn visit_loadable_entry(&mut self, loadables: &LoadableRegistry, loadable: &CobLoadable)
+    {
+        let name = loadable.id.to_canonical(None);
+        match get_registration(self.registry, name.as_str(), loadables) {
+            Some((_, type_id, ..)) => self.visit_loadable(loadable, type_id),
+            None => self.visit_untyped(|mut f| visit_loadable(loadable, &mut f)),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Finds the reflected type of the constant `$name` defined in `file` from the loadables that reference it.
+///
+/// Returns `None` if no loadables reference the constant, if references have different types, or if a
+/// reference's type can't be determined (e.g. when it is referenced by another constant or used as a map key).
+pub(super) fn find_constant_type(
+    editor: &CobEditor,
+    manifest: &ManifestMap,
+    registry: &TypeRegistry,
+    loadables: &LoadableRegistry,
+    file: &CobFile,
+    name: &str,
+) -> Option<TypeId>
+{
+    let mut search = ConstantTypeSearch { registry, paths: Vec::default(), found: None, ambiguous: false };
+
+    for (user, file_data) in editor.iter_files() {
+        search.paths = constant_paths(editor, manifest, user, file, name);
+        if search.paths.is_empty() {
+            continue;
+        }
+
+        for section in file_data.data.sections.iter() {
+            match section {
+                CobSection::Defs(defs) => {
+                    for entry in defs.entries.iter() {
+                        let CobDefEntry::Constant(def) = entry else { continue };
+                        search.visit_untyped(|mut f| visit_constant_value(&def.value, &mut f));
+                    }
+                }
+                CobSection::Commands(commands) => {
+                    for entry in commands.entries.iter() {
+                        let CobCommandEntry::Loadable(loadable) = entry else { continue };
+                        search.visit_loadable_entry(loadables, loadable);
+                    }
+                }
+                CobSection::Scenes(scenes) => {
+                    for scene in scenes.scenes.iter() {
+                        search.visit_layer(loadables, scene);
+                    }
+                }
+                _ => (),
+            }
+        }
+        if search.ambiguous {
+            return None;
+        }
+    }
+
+    search.found
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Finds scene nodes, commands, and constants in all editor files that reference the constant `$name` defined in
+/// `file`.
+///
+/// References are found in unresolved file data by following `#import` aliases, so a usage may be reported even
+/// if another constant with the same name shadows it. References inside macro definitions and macro calls are not
+/// found.
+pub(super) fn find_constant_usages(
+    editor: &CobEditor,
+    manifest: &ManifestMap,
+    file: &CobFile,
+    name: &str,
+) -> Vec<ConstantUsage>
+{
+    let mut usages = Vec::default();
+    let mut files: Vec<&CobFile> = editor.iter_files().map(|(f, _)| f).collect();
+    files.sort_unstable_by(|a, b| a.as_str().cmp(b.as_str()));
+
+    for user in files {
+        let paths = constant_paths(editor, manifest, user, file, name);
+        if paths.is_empty() {
+            continue;
+        }
+        let Some(file_data) = editor.get_file(user) else { continue };
+
+        // Constants
+        for section in file_data.data.sections.iter() {
+            let CobSection::Defs(defs) = section else { continue };
+            for entry in defs.entries.iter() {
+                let CobDefEntry::Constant(def) = entry else { continue };
+                let mut found = false;
+                visit_constant_value(&def.value, &mut |path| found |= paths.iter().any(|p| p == path.as_str()));
+                if found {
+                    usages.push(ConstantUsage::Constant { file: user.clone(), name: def.name.name.clone() });
+                }
+            }
+        }
+
+        // Commands
+        let mut id_scratch = String::default();
+        for section in file_data.data.sections.iter() {
+            let CobSection::Commands(commands) = section else { continue };
+            for entry in commands.entries.iter() {
+                let CobCommandEntry::Loadable(loadable) = entry else { continue };
+                if any_references([loadable], &paths) {
+                    id_scratch = loadable.id.to_canonical(Some(std::mem::take(&mut id_scratch)));
+                    usages.push(ConstantUsage::Command { file: user.clone(), name: id_scratch.as_str().into() });
+                }
+            }
+        }
+
+        // Scene nodes
+        for section in file_data.data.sections.iter() {
+            let CobSection::Scenes(scenes) = section else { continue };
+            for scene in scenes.scenes.iter() {
+                let scene_ref = SceneRef { file: user.clone().into(), path: ScenePath::new(scene.name.as_str()) };
+                collect_layer_usages(&mut usages, &paths, scene_ref, scene);
+            }
+        }
+    }
+
+    usages
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks the state of `#defs` panels in the editor.
+#[derive(Resource, Debug, Default)]
+pub(super) struct EditorDefsState
+{
+    /// The constant whose usages are displayed.
+    usages_of: Option<(CobFile, SmolStr)>,
+}
+
+impl EditorDefsState
+{
+    pub(super) fn is_showing_usages(&self, file: &CobFile, name: &str) -> bool
+    {
+        self.usages_of
+            .as_ref()
+            .map(|(f, n)| f == file && n == name)
+            .unwrap_or(false)
+    }
+
+    /// Shows usages of the constant, or hides them if they are already shown.
+    pub(super) fn toggle_usages(&mut self, c: &mut Commands, file: CobFile, name: SmolStr)
+    {
+        match self.is_showing_usages(&file, &name) {
+            true => self.usages_of = None,
+            false => self.usages_of = Some((file, name)),
+        }
+        c.react().broadcast(EditorDefsChanged);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(super) struct CobEditorDefsPlugin;
+
+impl Plugin for CobEditorDefsPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.init_resource::<EditorDefsState>();
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -21,6 +21,18 @@ pub struct EditorFileExternalChange
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Reactive event broadcasted when a file's data is changed by external factors while the editor has unsaved
+/// changes to the file.
+///
+/// The conflict can be resolved with [`ResolveEditorConflict`]. The file can't be saved until then.
+#[derive(Debug, Clone)]
+pub struct EditorFileConflict
+{
+    pub file: CobFile,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 /// Reactive event broadcasted when a file acquires 'unsaved' status in the editor.
 #[derive(Debug, Clone)]
 pub struct EditorFileUnsaved
@@ -41,3 +53,23 @@ pub struct EditorFileSaved
 }
 
 //-------------------------------------------------------------------------------------------------------------------
+
+/// Reactive event broadcasted when a structural edit is made to a file in the editor, or when an edit is undone or
+/// redone.
+#[derive(Debug, Clone)]
+pub struct EditorHistoryChanged
+{
+    pub file: CobFile,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Reactive event broadcasted when a scene node is selected in the editor (e.g. with [`SelectSceneNode`]).
+#[derive(Debug, Clone)]
+pub struct EditorSceneNodeSelected
+{
+    pub file: CobFile,
+    pub scene_ref: SceneRef,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,344 @@
+use std::time::Duration;
+
+use smol_str::SmolStr;
+
+use super::*;
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Patches to the same value within this window are merged into one history entry (e.g. while dragging a
+/// number).
+const MERGE_WINDOW: Duration = Duration::from_millis(500);
+
+/// Maximum number of entries in a file's history. The oldest entries are dropped when the history is full.
+const MAX_HISTORY_ENTRIES: usize = 256;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A change recorded in [`EditorHistory`].
+#[derive(Debug, Clone)]
+pub(super) enum EditorHistoryChange
+{
+    /// A loadable's value before and after it was patched.
+    Patch
+    {
+        scene_ref: SceneRef,
+        loadable_name: &'static str,
+        /// Location of the edited value inside the loadable. Used to merge edits.
+        structure_path: ReflectStructurePath,
+        old: CobLoadable,
+        new: CobLoadable,
+    },
+    /// A constant's value before and after it was edited.
+    Constant
+    {
+        /// Name of the constant without the leading `$`.
+        name: SmolStr,
+        /// Location of the edited value inside the constant if it was edited with a widget. Used to merge edits.
+        structure_path: Option<ReflectStructurePath>,
+        old: CobConstantValue,
+        new: CobConstantValue,
+    },
+    /// A structural edit (e.g. inserting a scene node).
+    Structure(EditorStructureChange),
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// The part of a file changed by a structural edit.
+///
+/// Only the edited part is recorded so history entries stay small.
+#[derive(Debug, Clone)]
+pub(super) enum EditorStructureChange
+{
+    /// A scene node's entries before and after they were edited, excluding child nodes.
+    NodeEntries
+    {
+        path: ScenePath,
+        old: Vec<CobSceneLayerEntry>,
+        new: Vec<CobSceneLayerEntry>,
+    },
+    /// The file's `#commands` sections before and after they were edited.
+    Commands
+    {
+        old: Vec<CobSection>,
+        new: Vec<CobSection>,
+    },
+    /// A child node was inserted at `index` in its parent's entries.
+    InsertNode
+    {
+        parent: ScenePath,
+        index: usize,
+        node: CobSceneLayer,
+    },
+    /// A child node was removed from `index` in its parent's entries.
+    RemoveNode
+    {
+        parent: ScenePath,
+        index: usize,
+        node: CobSceneLayer,
+    },
+    /// The child node at `index` in its parent's entries was renamed.
+    RenameNode
+    {
+        parent: ScenePath,
+        index: usize,
+        old: CobSceneNodeName,
+        new: CobSceneNodeName,
+    },
+    /// A child node was moved from index `from` to index `to` in its parent's entries.
+    MoveNode
+    {
+        parent: ScenePath,
+        from: usize,
+        to: usize,
+    },
+}
+
+impl EditorStructureChange
+{
+    /// Re-applies the change to `data`, or reverts it if `undo` is `true`.
+    pub(super) fn apply(&self, data: &mut Cob, undo: bool) -> Result<(), String>
+    {
+        match self {
+            Self::NodeEntries { path, old, new } => {
+                let layer = data
+                    .get_scene_layer_mut(path)
+                    .ok_or_else(|| format!("scene node {path:?} not found"))?;
+                let entries = if undo { old } else { new };
+                replace_own_entries(layer, entries.iter().cloned());
+            }
+            Self::Commands { old, new } => {
+                let sections = if undo { old } else { new };
+                replace_sections(data, "#commands", sections.clone());
+            }
+            Self::InsertNode { parent, index, node } => match undo {
+                true => remove_child_node(data, parent, *index, node.name.as_str())?,
+                false => insert_child_node(data, parent, *index, node)?,
+            },
+            Self::RemoveNode { parent, index, node } => match undo {
+                true => insert_child_node(data, parent, *index, node)?,
+                false => remove_child_node(data, parent, *index, node.name.as_str())?,
+            },
+            Self::RenameNode { parent, index, old, new } => {
+                let (from, to) = if undo { (new, old) } else { (old, new) };
+                let node = get_child_node_mut(data, parent, *index, from.as_str())?;
+                node.name = to.clone();
+            }
+            Self::MoveNode { parent, from, to } => {
+                let (from, to) = if undo { (*to, *from) } else { (*from, *to) };
+                let layer = data
+                    .get_scene_layer_mut(parent)
+                    .ok_or_else(|| format!("scene node {parent:?} not found"))?;
+                if from >= layer.entries.len() || to >= layer.entries.len() {
+                    return Err(format!("child node index out of bounds in {parent:?}"));
+                }
+                let entry = layer.entries.remove(from);
+                layer.entries.insert(to, entry);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the child node at `index` in its parent's entries, and checks that it has the expected name.
+fn get_child_node_mut<'a>(
+    data: &'a mut Cob,
+    parent: &ScenePath,
+    index: usize,
+    name: &str,
+) -> Result<&'a mut CobSceneLayer, String>
+{
+    let layer = data
+        .get_scene_layer_mut(parent)
+        .ok_or_else(|| format!("scene node {parent:?} not found"))?;
+    match layer.entries.get_mut(index) {
+        Some(CobSceneLayerEntry::Layer(node)) if node.name.as_str() == name => Ok(node),
+        _ => Err(format!("child node \"{name}\" not found in {parent:?}")),
+    }
+}
+
+fn insert_child_node(data: &mut Cob, parent: &ScenePath, index: usize, node: &CobSceneLayer) -> Result<(), String>
+{
+    let layer = data
+        .get_scene_layer_mut(parent)
+        .ok_or_else(|| format!("scene node {parent:?} not found"))?;
+    if index > layer.entries.len() {
+        return Err(format!("child node index out of bounds in {parent:?}"));
+    }
+    layer
+        .entries
+        .insert(index, CobSceneLayerEntry::Layer(node.clone()));
+    Ok(())
+}
+
+fn remove_child_node(data: &mut Cob, parent: &ScenePath, index: usize, name: &str) -> Result<(), String>
+{
+    get_child_node_mut(data, parent, index, name)?;
+    let layer = data.get_scene_layer_mut(parent).unwrap();
+    layer.entries.remove(index);
+    Ok(())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Records an edit.
+#[derive(Debug, Clone)]
+pub(super) struct EditorHistoryEntry
+{
+    pub(super) change: EditorHistoryChange,
+    /// App time when the entry was last updated.
+    pub(super) time: Duration,
+}
+
+impl EditorHistoryEntry
+{
+    /// Merges `other` into this entry if they are patches to the same value made in quick succession.
+    ///
+    /// Returns `other` if it can't be merged.
+    fn try_merge(&mut self, other: Self) -> Option<Self>
+    {
+        if other.time.saturating_sub(self.time) >= MERGE_WINDOW {
+            return Some(other);
+        }
+        match (&mut self.change, &other.change) {
+            (
+                EditorHistoryChange::Patch { scene_ref, loadable_name, structure_path, new, .. },
+                EditorHistoryChange::Patch {
+                    scene_ref: other_scene_ref,
+                    loadable_name: other_loadable_name,
+                    structure_path: other_structure_path,
+                    new: other_new,
+                    ..
+                },
+            ) => {
+                if scene_ref != other_scene_ref
+                    || loadable_name != other_loadable_name
+                    || structure_path != other_structure_path
+                {
+                    return Some(other);
+                }
+                *new = other_new.clone();
+            }
+            (
+                EditorHistoryChange::Constant { name, structure_path: Some(structure_path), new, .. },
+                EditorHistoryChange::Constant {
+                    name: other_name,
+                    structure_path: Some(other_structure_path),
+                    new: other_new,
+                    ..
+                },
+            ) => {
+                if name != other_name || structure_path != other_structure_path {
+                    return Some(other);
+                }
+                *new = other_new.clone();
+            }
+            _ => return Some(other),
+        }
+
+        self.time = other.time;
+        None
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Undo/redo history for a file in the editor.
+#[derive(Debug)]
+pub(super) struct EditorHistory
+{
+    entries: Vec<EditorHistoryEntry>,
+    /// Number of entries that are currently applied.
+    cursor: usize,
+    /// Cursor position that matches the saved file. `None` if the saved state is not reachable.
+    saved_cursor: Option<usize>,
+}
+
+impl EditorHistory
+{
+    /// Adds an entry. Entries that were undone are discarded.
+    ///
+    /// The oldest entries are dropped if there are more than [`MAX_HISTORY_ENTRIES`].
+    pub(super) fn push(&mut self, entry: EditorHistoryEntry)
+    {
+        self.entries.truncate(self.cursor);
+        if self.saved_cursor.map(|c| c > self.cursor).unwrap_or(false) {
+            self.saved_cursor = None;
+        }
+
+        // Merge with the previous entry unless that would modify the saved state.
+        let entry = match (self.saved_cursor != Some(self.cursor), self.entries.last_mut()) {
+            (true, Some(prev)) => prev.try_merge(entry),
+            _ => Some(entry),
+        };
+        let Some(entry) = entry else { return };
+
+        self.entries.push(entry);
+
+        // Drop the oldest entries.
+        // - The saved state is no longer reachable if its entries were dropped.
+        let excess = self.entries.len().saturating_sub(MAX_HISTORY_ENTRIES);
+        if excess > 0 {
+            self.entries.drain(..excess);
+            self.saved_cursor = self.saved_cursor.and_then(|c| c.checked_sub(excess));
+        }
+        self.cursor = self.entries.len();
+    }
+
+    /// Steps back one entry. Returns the entry that should be reverted.
+    pub(super) fn undo(&mut self) -> Option<&EditorHistoryEntry>
+    {
+        if self.cursor == 0 {
+            return None;
+        }
+        self.cursor -= 1;
+        self.entries.get(self.cursor)
+    }
+
+    /// Steps forward one entry. Returns the entry that should be re-applied.
+    pub(super) fn redo(&mut self) -> Option<&EditorHistoryEntry>
+    {
+        let entry = self.entries.get(self.cursor)?;
+        self.cursor += 1;
+        Some(entry)
+    }
+
+    /// Returns `true` if the current state matches the saved file.
+    pub(super) fn is_at_save(&self) -> bool
+    {
+        self.saved_cursor == Some(self.cursor)
+    }
+
+    /// Marks the current state as saved.
+    pub(super) fn mark_saved(&mut self)
+    {
+        self.saved_cursor = Some(self.cursor);
+    }
+
+    /// Marks the saved state as unreachable (e.g. because the saved file was replaced).
+    pub(super) fn forget_save(&mut self)
+    {
+        self.saved_cursor = None;
+    }
+
+    /// Discards all entries. Used when file data is replaced.
+    pub(super) fn clear(&mut self)
+    {
+        *self = Self::default();
+    }
+}
+
+impl Default for EditorHistory
+{
+    fn default() -> Self
+    {
+        Self { entries: Vec::default(), cursor: 0, saved_cursor: Some(0) }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,273 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_cobweb::prelude::*;
+
+use super::*;
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Width of the outline drawn around picked entities, in logical pixels.
+const OUTLINE_WIDTH: f32 = 2.0;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Marker component for UI nodes spawned into the app's window by the picker.
+#[derive(Component, Debug)]
+struct EditorPickerNode;
+
+/// Marker component for the picker's outline overlay.
+#[derive(Component, Debug)]
+struct EditorPickerOutline;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Reactive event broadcasted when pick mode is toggled.
+#[derive(Debug, Clone)]
+pub(super) struct EditorPickerToggled;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks 'pick mode', where clicking a UI entity in the app's window selects the scene node that spawned it.
+#[derive(Resource, Debug, Default)]
+pub(super) struct EditorPicker
+{
+    active: bool,
+    /// Entity under the cursor while picking. Only entities loaded from scene nodes are recorded.
+    hovered: Option<Entity>,
+    /// The last entity that was picked.
+    picked: Option<Entity>,
+    /// Full-window node that blocks app interactions while picking.
+    blocker: Option<Entity>,
+    outline: Option<Entity>,
+}
+
+impl EditorPicker
+{
+    pub(super) fn is_active(&self) -> bool
+    {
+        self.active
+    }
+
+    pub(super) fn toggle(&mut self, c: &mut Commands)
+    {
+        self.active = !self.active;
+        self.hovered = None;
+        c.react().broadcast(EditorPickerToggled);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(SystemParam)]
+struct PickerHitTest<'w, 's>
+{
+    nodes: Query<
+        'w,
+        's,
+        (Entity, &'static ComputedNode, &'static GlobalTransform, &'static ViewVisibility),
+        Without<EditorPickerNode>,
+    >,
+    parents: Query<'w, 's, &'static Parent>,
+    targets: Query<'w, 's, &'static TargetCamera>,
+    editor_camera: Query<'w, 's, Entity, With<EditorCamera>>,
+    scene_buffer: Res<'w, SceneBuffer>,
+}
+
+impl PickerHitTest<'_, '_>
+{
+    /// Returns `true` if the entity is part of the editor's UI.
+    fn is_editor_entity(&self, entity: Entity) -> bool
+    {
+        let Ok(editor_camera) = self.editor_camera.get_single() else { return false };
+        let root = self.parents.root_ancestor(entity);
+        self.targets
+            .get(root)
+            .map(|t| t.entity() == editor_camera)
+            .unwrap_or(false)
+    }
+
+    /// Finds the top-most app UI node at the cursor position, then returns the nearest entity in its hierarchy
+    /// that was loaded from a scene node.
+    fn find_tracked(&self, cursor: Vec2) -> Option<(Entity, &SceneRef)>
+    {
+        let hit = self
+            .nodes
+            .iter()
+            .filter(|(_, node, transform, visibility)| {
+                visibility.get()
+                    && Rect::from_center_size(transform.translation().truncate(), node.size()).contains(cursor)
+            })
+            .filter(|(entity, ..)| !self.is_editor_entity(*entity))
+            .max_by_key(|(_, node, ..)| node.stack_index())
+            .map(|(entity, ..)| entity)?;
+
+        std::iter::once(hit)
+            .chain(self.parents.iter_ancestors(hit))
+            .find_map(|entity| Some((entity, self.scene_buffer.get_tracked(entity)?)))
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn update_picker_mode(
+    mut c: Commands,
+    mut picker: ResMut<EditorPicker>,
+    keys: Res<ButtonInput<KeyCode>>,
+    editor_win: Query<&Window, With<EditorWindow>>,
+    primary_win: Query<&Window, With<PrimaryWindow>>,
+)
+{
+    // Escape cancels picking.
+    let focused = editor_win
+        .iter()
+        .chain(primary_win.iter())
+        .any(|w| w.focused);
+    if picker.active && focused && keys.just_pressed(KeyCode::Escape) {
+        picker.toggle(&mut c);
+    }
+
+    // Spawn or despawn the blocker.
+    match (picker.active, picker.blocker) {
+        (true, None) => {
+            let blocker = c
+                .spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        width: Val::Percent(100.),
+                        height: Val::Percent(100.),
+                        ..default()
+                    },
+                    GlobalZIndex(i32::MAX - 1),
+                    EditorPickerNode,
+                ))
+                .id();
+            picker.blocker = Some(blocker);
+        }
+        (false, Some(blocker)) => {
+            c.entity(blocker).despawn_recursive();
+            picker.blocker = None;
+        }
+        _ => (),
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn update_picker_target(
+    mut c: Commands,
+    mut picker: ResMut<EditorPicker>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    primary_win: Query<&Window, With<PrimaryWindow>>,
+    hit_test: PickerHitTest,
+)
+{
+    if !picker.active {
+        return;
+    }
+    let Ok(window) = primary_win.get_single() else { return };
+    let Some(cursor) = window.physical_cursor_position() else {
+        picker.hovered = None;
+        return;
+    };
+
+    let tracked = hit_test.find_tracked(cursor);
+    picker.hovered = tracked.map(|(entity, _)| entity);
+
+    if !window.focused || !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let Some((entity, scene_ref)) = tracked else { return };
+    picker.picked = Some(entity);
+    picker.toggle(&mut c);
+    c.queue(SelectSceneNode { scene_ref: scene_ref.clone() });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn update_picker_outline(
+    mut c: Commands,
+    mut picker: ResMut<EditorPicker>,
+    tree: Res<EditorTreeState>,
+    scene_buffer: Res<SceneBuffer>,
+    targets: Query<(&ComputedNode, &GlobalTransform), Without<EditorPickerNode>>,
+    mut outlines: Query<&mut Node, With<EditorPickerOutline>>,
+)
+{
+    // Forget the picked entity if it was despawned or a different node was selected.
+    if let Some(picked) = picker.picked {
+        if tree.selected().is_none() || tree.selected() != scene_buffer.get_tracked(picked) {
+            picker.picked = None;
+        }
+    }
+
+    // Get the outline node.
+    let outline = *picker.outline.get_or_insert_with(|| {
+        c.spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                display: Display::None,
+                border: UiRect::all(Val::Px(OUTLINE_WIDTH)),
+                ..default()
+            },
+            BorderColor(Color::srgb(0.99, 0.83, 0.3)),
+            GlobalZIndex(i32::MAX),
+            PickingBehavior::IGNORE,
+            EditorPickerNode,
+            EditorPickerOutline,
+        ))
+        .id()
+    });
+    let Ok(mut node) = outlines.get_mut(outline) else { return };
+
+    // Outline the hovered entity while picking, otherwise the picked entity.
+    let target = match picker.active {
+        true => picker.hovered,
+        false => picker.picked,
+    };
+    let Some((computed, transform)) = target.and_then(|t| targets.get(t).ok()) else {
+        if node.display != Display::None {
+            node.display = Display::None;
+        }
+        return;
+    };
+
+    // Convert physical coordinates to logical.
+    let scale = computed.inverse_scale_factor();
+    let size = computed.size() * scale;
+    let top_left = transform.translation().truncate() * scale - size / 2.;
+    let left = Val::Px(top_left.x - OUTLINE_WIDTH);
+    let top = Val::Px(top_left.y - OUTLINE_WIDTH);
+    let width = Val::Px(size.x + OUTLINE_WIDTH * 2.);
+    let height = Val::Px(size.y + OUTLINE_WIDTH * 2.);
+    if node.display != Display::Flex
+        || node.left != left
+        || node.top != top
+        || node.width != width
+        || node.height != height
+    {
+        node.display = Display::Flex;
+        node.left = left;
+        node.top = top;
+        node.width = width;
+        node.height = height;
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(super) struct CobEditorPickerPlugin;
+
+impl Plugin for CobEditorPickerPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.init_resource::<EditorPicker>().add_systems(
+            Update,
+            (update_picker_mode, update_picker_target, update_picker_outline).chain(),
+        );
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
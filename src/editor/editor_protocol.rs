@@ -0,0 +1,241 @@
+//! Messages exchanged between the app and out-of-process editor clients.
+//!
+//! See [`CobEditorRemotePlugin`](super::CobEditorRemotePlugin).
+//!
+//! Messages are serialized with RON, one message per line.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+
+use serde::{Deserialize, Serialize};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Information about a scene node in an [`EditorServerMessage::SceneTree`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoteSceneNode
+{
+    /// Scene path of the node, e.g. `menu::header::title`.
+    pub path: String,
+    /// The node's loadables as COB text, e.g. `BackgroundColor(#FF0000)`.
+    pub loadables: Vec<String>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Messages sent from an editor client to the app.
+///
+/// Files are identified by their [`CobFile`](crate::prelude::CobFile) names, e.g. `main.cob`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EditorClientMessage
+{
+    /// Requests the list of editable files. Answered with [`EditorServerMessage::Files`].
+    ListFiles,
+    /// Requests the current COB text of a file, including unsaved changes. Answered with
+    /// [`EditorServerMessage::File`].
+    GetFile
+    {
+        file: String,
+    },
+    /// Requests the commands and scene nodes of a file. Answered with [`EditorServerMessage::SceneTree`].
+    GetSceneTree
+    {
+        file: String,
+    },
+    /// Sets a loadable in a scene node with the [`SetLoadable`] command. Use the path `#commands` to set a command.
+    ///
+    /// The loadable is COB text, e.g. `BackgroundColor(#FF0000)`. Answered with
+    /// [`EditorServerMessage::Accepted`] if the loadable parses.
+    SetLoadable
+    {
+        file: String,
+        path: String,
+        loadable: String,
+    },
+    /// Removes a loadable from a scene node with the [`RemoveLoadable`] command.
+    RemoveLoadable
+    {
+        file: String,
+        path: String,
+        /// Short name of the loadable (e.g. `BackgroundColor`).
+        name: String,
+    },
+    /// Sets the value of a constant with the [`EditConstant`] command.
+    EditConstant
+    {
+        file: String,
+        /// Name of the constant without the leading `$`.
+        name: String,
+        /// COB text of the value.
+        value: String,
+    },
+    /// Selects a scene node in the editor with the [`SelectSceneNode`] command.
+    SelectSceneNode
+    {
+        file: String,
+        path: String,
+    },
+    /// Undoes the most recent edit to a file with the [`UndoEditor`] command. Does nothing if there is nothing
+    /// to undo.
+    Undo
+    {
+        file: String,
+    },
+    /// Re-applies the most recently undone edit to a file with the [`RedoEditor`] command. Does nothing if there
+    /// is nothing to redo.
+    Redo
+    {
+        file: String,
+    },
+    /// Saves all unsaved files.
+    Save,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Messages sent from the app to editor clients.
+///
+/// Requests are handled in order, and edits are applied before the next request is handled. A request to edit a
+/// file is answered with [`EditorServerMessage::Accepted`], and the edit's result is also reported with change
+/// notifications. If an edit fails, a warning is logged in the app.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EditorServerMessage
+{
+    Files
+    {
+        files: Vec<String>,
+    },
+    File
+    {
+        file: String,
+        /// COB text of the file.
+        content: String,
+        /// `true` if the file has changes that aren't saved.
+        unsaved: bool,
+    },
+    SceneTree
+    {
+        file: String,
+        /// Commands as COB text.
+        commands: Vec<String>,
+        /// Scene nodes, with parents before their children.
+        nodes: Vec<RemoteSceneNode>,
+    },
+    /// The request was queued.
+    Accepted,
+    /// The request failed.
+    Error
+    {
+        message: String,
+    },
+
+    /// Notification that a file was added to the editor.
+    FileAdded
+    {
+        file: String,
+    },
+    /// Notification that a file's data changed (e.g. after a hot reload, structural edit, or undo/redo).
+    FileChanged
+    {
+        file: String,
+    },
+    /// Notification that a file has unsaved changes.
+    FileUnsaved
+    {
+        file: String,
+    },
+    /// Notification that a file was saved or its unsaved changes were discarded.
+    FileSaved
+    {
+        file: String,
+    },
+    /// Notification that a file changed externally while it had unsaved changes.
+    ///
+    /// See [`EditorFileConflict`](super::EditorFileConflict).
+    FileConflict
+    {
+        file: String,
+    },
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Serializes a message as one line of RON.
+pub(super) fn write_message(writer: &mut impl Write, message: &impl Serialize) -> std::io::Result<()>
+{
+    let line = ron::ser::to_string(message).map_err(|err| std::io::Error::other(err.to_string()))?;
+    writer.write_all(line.as_bytes())?;
+    writer.write_all(b"\n")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Blocking client for an app running [`CobEditorRemotePlugin`](super::CobEditorRemotePlugin).
+///
+/// Useful for scripts and tests. Notifications are received in the same stream as responses, so callers should
+/// skip notifications while waiting for a response.
+pub struct EditorRemoteClient
+{
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl EditorRemoteClient
+{
+    /// Connects to the app.
+    pub fn connect(addr: impl ToSocketAddrs) -> std::io::Result<Self>
+    {
+        let writer = TcpStream::connect(addr)?;
+        writer.set_nodelay(true)?;
+        let reader = BufReader::new(writer.try_clone()?);
+        Ok(Self { reader, writer })
+    }
+
+    /// Sends a message to the app.
+    pub fn send(&mut self, message: &EditorClientMessage) -> std::io::Result<()>
+    {
+        write_message(&mut self.writer, message)
+    }
+
+    /// Waits for the next message from the app.
+    pub fn recv(&mut self) -> std::io::Result<EditorServerMessage>
+    {
+        let mut line = String::default();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        ron::de::from_str(&line).map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+    }
+
+    /// Sends a request and waits for the first message from the app that isn't a notification.
+    pub fn request(&mut self, message: &EditorClientMessage) -> std::io::Result<EditorServerMessage>
+    {
+        self.send(message)?;
+        loop {
+            let response = self.recv()?;
+            if !response.is_notification() {
+                return Ok(response);
+            }
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+impl EditorServerMessage
+{
+    /// Returns `true` if the message is a change notification rather than a response to a request.
+    pub fn is_notification(&self) -> bool
+    {
+        matches!(
+            self,
+            Self::FileAdded { .. }
+                | Self::FileChanged { .. }
+                | Self::FileUnsaved { .. }
+                | Self::FileSaved { .. }
+                | Self::FileConflict { .. }
+        )
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
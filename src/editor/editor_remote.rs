@@ -0,0 +1,488 @@
+use std::io::{ErrorKind, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+
+use super::*;
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Default port used by [`CobEditorRemotePlugin`].
+pub const DEFAULT_EDITOR_REMOTE_PORT: u16 = 7433;
+
+/// Maximum length of a message sent by an editor remote client, in bytes.
+///
+/// Clients that send longer messages are sent an error and disconnected.
+pub const MAX_EDITOR_REMOTE_MESSAGE_BYTES: usize = 1 << 20;
+
+/// Maximum number of bytes read from each client per tick, so a client that keeps writing can't stall the app.
+const MAX_EDITOR_REMOTE_READ_BYTES_PER_TICK: usize = 1 << 16;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource with the address the [`CobEditorRemotePlugin`] server is listening on.
+///
+/// Useful if the plugin was configured with port `0` so the OS chooses a free port.
+#[derive(Resource, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CobEditorRemoteAddr(pub SocketAddr);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+struct RemoteClient
+{
+    stream: TcpStream,
+    read_buf: Vec<u8>,
+    write_buf: Vec<u8>,
+    /// The client will be closed once the write buffer is flushed.
+    closing: bool,
+    closed: bool,
+}
+
+impl RemoteClient
+{
+    fn new(stream: TcpStream) -> Self
+    {
+        Self {
+            stream,
+            read_buf: Vec::default(),
+            write_buf: Vec::default(),
+            closing: false,
+            closed: false,
+        }
+    }
+
+    /// Reads available bytes from the stream, up to [`MAX_EDITOR_REMOTE_READ_BYTES_PER_TICK`].
+    fn read(&mut self)
+    {
+        let mut bytes = [0u8; 4096];
+        let mut total = 0;
+        while !self.closed && !self.closing && total < MAX_EDITOR_REMOTE_READ_BYTES_PER_TICK {
+            match self.stream.read(&mut bytes) {
+                Ok(0) => self.closed = true,
+                Ok(len) => {
+                    total += len;
+                    self.read_buf.extend_from_slice(&bytes[..len]);
+                }
+                Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(_) => self.closed = true,
+            }
+        }
+    }
+
+    /// Takes the next complete line from the read buffer.
+    ///
+    /// Returns an error if the line is longer than [`MAX_EDITOR_REMOTE_MESSAGE_BYTES`].
+    fn next_line(&mut self) -> Option<Result<String, String>>
+    {
+        match self.read_buf.iter().position(|b| *b == b'\n') {
+            Some(end) if end <= MAX_EDITOR_REMOTE_MESSAGE_BYTES => {
+                let line: Vec<u8> = self.read_buf.drain(..=end).collect();
+                Some(Ok(String::from_utf8_lossy(&line).trim().to_string()))
+            }
+            None if self.read_buf.len() <= MAX_EDITOR_REMOTE_MESSAGE_BYTES => None,
+            _ => Some(Err(format!(
+                "message exceeds the maximum length of {MAX_EDITOR_REMOTE_MESSAGE_BYTES} bytes"
+            ))),
+        }
+    }
+
+    /// Closes the client after sending the messages that are already queued.
+    fn close_after_flush(&mut self)
+    {
+        self.closing = true;
+        self.read_buf.clear();
+    }
+
+    fn queue(&mut self, message: &EditorServerMessage)
+    {
+        if let Err(err) = write_message(&mut self.write_buf, message) {
+            tracing::warn!("failed serializing editor remote message {:?}: {err:?}", message);
+        }
+    }
+
+    /// Writes as much of the write buffer as the stream will accept.
+    fn flush(&mut self)
+    {
+        while !self.closed && !self.write_buf.is_empty() {
+            match self.stream.write(&self.write_buf) {
+                Ok(0) => self.closed = true,
+                Ok(len) => {
+                    self.write_buf.drain(..len);
+                }
+                Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(_) => self.closed = true,
+            }
+        }
+
+        if self.closing && self.write_buf.is_empty() {
+            self.closed = true;
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Resource)]
+struct EditorRemoteServer
+{
+    listener: TcpListener,
+    clients: Vec<RemoteClient>,
+}
+
+impl EditorRemoteServer
+{
+    fn accept(&mut self)
+    {
+        loop {
+            match self.listener.accept() {
+                Ok((stream, addr)) => {
+                    if let Err(err) = stream.set_nonblocking(true) {
+                        tracing::warn!("failed accepting editor remote client {addr}: {err:?}");
+                        continue;
+                    }
+                    let _ = stream.set_nodelay(true);
+                    tracing::info!("editor remote client connected: {addr}");
+                    self.clients.push(RemoteClient::new(stream));
+                }
+                Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+                Err(err) => {
+                    tracing::warn!("failed accepting editor remote client: {err:?}");
+                    break;
+                }
+            }
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Notifications waiting to be sent to all clients.
+#[derive(Resource, Default)]
+struct EditorRemoteOutbox
+{
+    notifications: Vec<EditorServerMessage>,
+}
+
+impl EditorRemoteOutbox
+{
+    fn push(&mut self, notification: EditorServerMessage)
+    {
+        if !self.notifications.contains(&notification) {
+            self.notifications.push(notification);
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn loadable_to_string(loadable: &CobLoadable) -> Result<String, String>
+{
+    let mut bytes = Vec::default();
+    loadable
+        .write_to(&mut DefaultRawSerializer::new(&mut bytes))
+        .map_err(|err| format!("failed serializing loadable: {err:?}"))?;
+    Ok(String::from_utf8_lossy(&bytes).trim().to_string())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets an editable file in the editor.
+fn get_file_data<'a>(world: &'a World, file: &str) -> Result<(CobFile, &'a CobFileData), String>
+{
+    let cob_file = CobFile::try_new(file).ok_or_else(|| format!("invalid file name {file:?}"))?;
+    if !world.resource::<CobEditorSaveTargets>().is_editable(&cob_file) {
+        return Err(format!("file {file:?} is not editable"));
+    }
+    let file_data = world
+        .resource::<CobEditor>()
+        .get_file(&cob_file)
+        .ok_or_else(|| format!("file {file:?} not found in editor"))?;
+    Ok((cob_file, file_data))
+}
+
+fn get_file(world: &World, file: &str) -> Result<CobFile, String>
+{
+    get_file_data(world, file).map(|(file, _)| file)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn collect_remote_nodes(nodes: &mut Vec<RemoteSceneNode>, path: String, layer: &CobSceneLayer)
+    -> Result<(), String>
+{
+    let mut loadables = Vec::default();
+    for entry in layer.entries.iter() {
+        let CobSceneLayerEntry::Loadable(loadable) = entry else { continue };
+        loadables.push(loadable_to_string(loadable)?);
+    }
+    nodes.push(RemoteSceneNode { path: path.clone(), loadables });
+
+    for entry in layer.entries.iter() {
+        let CobSceneLayerEntry::Layer(child) = entry else { continue };
+        let child_path = format!("{path}{SCENE_PATH_SEPARATOR}{}", child.name.as_str());
+        collect_remote_nodes(nodes, child_path, child)?;
+    }
+    Ok(())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn handle_message(world: &mut World, message: EditorClientMessage) -> Result<EditorServerMessage, String>
+{
+    let response = match message {
+        EditorClientMessage::ListFiles => {
+            let targets = world.resource::<CobEditorSaveTargets>();
+            let mut files: Vec<String> = world
+                .resource::<CobEditor>()
+                .iter_files()
+                .filter(|(file, _)| targets.is_editable(file))
+                .map(|(file, _)| file.as_str().to_string())
+                .collect();
+            files.sort_unstable();
+            EditorServerMessage::Files { files }
+        }
+        EditorClientMessage::GetFile { file } => {
+            let (cob_file, file_data) = get_file_data(world, &file)?;
+            let mut bytes = Vec::default();
+            file_data
+                .data
+                .write_to(&mut DefaultRawSerializer::new(&mut bytes))
+                .map_err(|err| format!("failed serializing file {file:?}: {err:?}"))?;
+            let unsaved = world.resource::<CobEditor>().is_unsaved(&cob_file);
+            EditorServerMessage::File { file, content: String::from_utf8_lossy(&bytes).into_owned(), unsaved }
+        }
+        EditorClientMessage::GetSceneTree { file } => {
+            let (_, file_data) = get_file_data(world, &file)?;
+            let mut commands = Vec::default();
+            let mut nodes = Vec::default();
+            for section in file_data.data.sections.iter() {
+                match section {
+                    CobSection::Commands(section) => {
+                        for entry in section.entries.iter() {
+                            let CobCommandEntry::Loadable(loadable) = entry else { continue };
+                            commands.push(loadable_to_string(loadable)?);
+                        }
+                    }
+                    CobSection::Scenes(scenes) => {
+                        for scene in scenes.scenes.iter() {
+                            collect_remote_nodes(&mut nodes, scene.name.as_str().to_string(), scene)?;
+                        }
+                    }
+                    _ => (),
+                }
+            }
+            EditorServerMessage::SceneTree { file, commands, nodes }
+        }
+        EditorClientMessage::SetLoadable { file, path, loadable } => {
+            let file = get_file(world, &file)?;
+            let loadable = parse_loadable(&file, &loadable)?;
+            let scene_ref = SceneRef { file: file.into(), path: ScenePath::new(path) };
+            world.commands().queue(SetLoadable { scene_ref, loadable });
+            EditorServerMessage::Accepted
+        }
+        EditorClientMessage::RemoveLoadable { file, path, name } => {
+            let file = get_file(world, &file)?;
+            let scene_ref = SceneRef { file: file.into(), path: ScenePath::new(path) };
+            world
+                .commands()
+                .queue(RemoveLoadable { scene_ref, loadable_name: name });
+            EditorServerMessage::Accepted
+        }
+        EditorClientMessage::EditConstant { file, name, value } => {
+            let file = get_file(world, &file)?;
+            world.commands().queue(EditConstant { file, name, value });
+            EditorServerMessage::Accepted
+        }
+        EditorClientMessage::SelectSceneNode { file, path } => {
+            let file = get_file(world, &file)?;
+            let scene_ref = SceneRef { file: file.into(), path: ScenePath::new(path) };
+            world.commands().queue(SelectSceneNode { scene_ref });
+            EditorServerMessage::Accepted
+        }
+        EditorClientMessage::Undo { file } => {
+            let file = get_file(world, &file)?;
+            world.commands().queue(UndoEditor { file });
+            EditorServerMessage::Accepted
+        }
+        EditorClientMessage::Redo { file } => {
+            let file = get_file(world, &file)?;
+            world.commands().queue(RedoEditor { file });
+            EditorServerMessage::Accepted
+        }
+        EditorClientMessage::Save => {
+            world.commands().queue(SaveEditor);
+            EditorServerMessage::Accepted
+        }
+    };
+
+    Ok(response)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn poll_editor_remote(world: &mut World)
+{
+    world.resource_scope(|world: &mut World, mut server: Mut<EditorRemoteServer>| {
+        server.accept();
+
+        for client in server.clients.iter_mut() {
+            client.read();
+            while let Some(line) = client.next_line() {
+                let line = match line {
+                    Ok(line) => line,
+                    Err(message) => {
+                        client.queue(&EditorServerMessage::Error { message });
+                        client.close_after_flush();
+                        break;
+                    }
+                };
+                if line.is_empty() {
+                    continue;
+                }
+                let response = match ron::de::from_str::<EditorClientMessage>(&line) {
+                    Ok(message) => handle_message(world, message),
+                    Err(err) => Err(format!("failed parsing editor remote message {line:?}: {err}")),
+                };
+                let response = response.unwrap_or_else(|message| EditorServerMessage::Error { message });
+                client.queue(&response);
+
+                // Apply queued edits before handling the next message so requests are applied in order.
+                world.flush();
+            }
+        }
+    });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn send_editor_remote(mut server: ResMut<EditorRemoteServer>, mut outbox: ResMut<EditorRemoteOutbox>)
+{
+    let notifications = std::mem::take(&mut outbox.notifications);
+    for client in server.clients.iter_mut() {
+        for notification in notifications.iter() {
+            client.queue(notification);
+        }
+        client.flush();
+    }
+
+    server.clients.retain(|client| {
+        if client.closed {
+            if let Ok(addr) = client.stream.peer_addr() {
+                tracing::info!("editor remote client disconnected: {addr}");
+            }
+        }
+        !client.closed
+    });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn notify_new_file(event: BroadcastEvent<EditorNewFile>, mut outbox: ResMut<EditorRemoteOutbox>)
+{
+    let Ok(event) = event.try_read() else { return };
+    outbox.push(EditorServerMessage::FileAdded { file: event.file.as_str().to_string() });
+}
+
+fn notify_external_change(event: BroadcastEvent<EditorFileExternalChange>, mut outbox: ResMut<EditorRemoteOutbox>)
+{
+    let Ok(event) = event.try_read() else { return };
+    outbox.push(EditorServerMessage::FileChanged { file: event.file.as_str().to_string() });
+}
+
+fn notify_history_changed(event: BroadcastEvent<EditorHistoryChanged>, mut outbox: ResMut<EditorRemoteOutbox>)
+{
+    let Ok(event) = event.try_read() else { return };
+    outbox.push(EditorServerMessage::FileChanged { file: event.file.as_str().to_string() });
+}
+
+fn notify_unsaved(event: BroadcastEvent<EditorFileUnsaved>, mut outbox: ResMut<EditorRemoteOutbox>)
+{
+    let Ok(event) = event.try_read() else { return };
+    outbox.push(EditorServerMessage::FileUnsaved { file: event.file.as_str().to_string() });
+}
+
+fn notify_saved(event: BroadcastEvent<EditorFileSaved>, mut outbox: ResMut<EditorRemoteOutbox>)
+{
+    let Ok(event) = event.try_read() else { return };
+    outbox.push(EditorServerMessage::FileSaved { file: event.file.as_str().to_string() });
+}
+
+fn notify_conflict(event: BroadcastEvent<EditorFileConflict>, mut outbox: ResMut<EditorRemoteOutbox>)
+{
+    let Ok(event) = event.try_read() else { return };
+    outbox.push(EditorServerMessage::FileConflict { file: event.file.as_str().to_string() });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Plugin that lets out-of-process tools drive the editor over a local TCP socket.
+///
+/// Clients send [`EditorClientMessage`]s and receive [`EditorServerMessage`]s, serialized as newline-delimited
+/// RON. See [`EditorRemoteClient`] for a simple blocking client.
+///
+/// Requires the `editor` feature.
+///
+/// Clients can write to your asset files, so the server only binds to loopback addresses unless
+/// [`allow_remote`](Self::allow_remote) is set.
+pub struct CobEditorRemotePlugin
+{
+    pub addr: SocketAddr,
+    /// Allows binding to addresses other than loopback (e.g. `0.0.0.0`), which exposes the editor to other
+    /// machines.
+    ///
+    /// Defaults to `false`.
+    pub allow_remote: bool,
+}
+
+impl Default for CobEditorRemotePlugin
+{
+    fn default() -> Self
+    {
+        Self {
+            addr: SocketAddr::from((Ipv4Addr::LOCALHOST, DEFAULT_EDITOR_REMOTE_PORT)),
+            allow_remote: false,
+        }
+    }
+}
+
+impl Plugin for CobEditorRemotePlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        if !self.addr.ip().is_loopback() && !self.allow_remote {
+            tracing::error!("failed starting editor remote server at {}: address is not loopback; set \
+                CobEditorRemotePlugin::allow_remote to expose the editor to other machines", self.addr);
+            return;
+        }
+
+        let listener = match TcpListener::bind(self.addr)
+            .and_then(|listener| listener.set_nonblocking(true).map(|_| listener))
+        {
+            Ok(listener) => listener,
+            Err(err) => {
+                tracing::warn!("failed starting editor remote server at {}: {err:?}", self.addr);
+                return;
+            }
+        };
+        let addr = listener.local_addr().unwrap_or(self.addr);
+        tracing::info!("editor remote server listening at {addr}");
+
+        app.insert_resource(EditorRemoteServer { listener, clients: Vec::default() })
+            .insert_resource(CobEditorRemoteAddr(addr))
+            .init_resource::<EditorRemoteOutbox>()
+            .react(|rc| rc.on_persistent(broadcast::<EditorNewFile>(), notify_new_file))
+            .react(|rc| rc.on_persistent(broadcast::<EditorFileExternalChange>(), notify_external_change))
+            .react(|rc| rc.on_persistent(broadcast::<EditorHistoryChanged>(), notify_history_changed))
+            .react(|rc| rc.on_persistent(broadcast::<EditorFileUnsaved>(), notify_unsaved))
+            .react(|rc| rc.on_persistent(broadcast::<EditorFileSaved>(), notify_saved))
+            .react(|rc| rc.on_persistent(broadcast::<EditorFileConflict>(), notify_conflict))
+            .add_systems(First, poll_editor_remote)
+            .add_systems(Last, send_editor_remote);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,355 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+
+use super::*;
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Maximum number of hits displayed for a search.
+const MAX_SEARCH_HITS: usize = 50;
+
+/// Search results are refreshed once files have stopped changing for this long.
+const SEARCH_REFRESH_DELAY: Duration = Duration::from_millis(300);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Reactive event broadcasted when the editor's search query changes, or when files changed and the search
+/// results need to be refreshed.
+#[derive(Debug, Clone)]
+pub(super) struct EditorSearchChanged;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Reactive event broadcasted to switch the editor to a file without selecting a scene node.
+#[derive(Debug, Clone)]
+pub(super) struct EditorOpenFileRequest
+{
+    pub(super) file: CobFile,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(super) enum SearchHitKind
+{
+    File,
+    ManifestKey,
+    SceneNode,
+    Loadable,
+    StringValue,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A search result found by [`search_editor`].
+#[derive(Debug, Clone)]
+pub(super) struct SearchHit
+{
+    pub(super) kind: SearchHitKind,
+    pub(super) file: CobFile,
+    /// The scene node to select when the hit is pressed. Hits outside `#scenes` only open the file.
+    pub(super) scene_ref: Option<SceneRef>,
+    /// The text that matched the query.
+    pub(super) text: String,
+    score: i32,
+}
+
+impl SearchHit
+{
+    /// Gets a short description of the hit for display.
+    pub(super) fn describe(&self) -> String
+    {
+        let location = match &self.scene_ref {
+            Some(scene_ref) => scene_ref.path.iter().collect::<Vec<_>>().join(SCENE_PATH_SEPARATOR),
+            None => String::from("#commands"),
+        };
+        match self.kind {
+            SearchHitKind::File => self.text.clone(),
+            SearchHitKind::ManifestKey => format!("{} ({})", self.text, self.file.as_str()),
+            SearchHitKind::SceneNode => format!("{}: \"{}\"", self.file.as_str(), location),
+            SearchHitKind::Loadable => format!("{}: {} {}", self.file.as_str(), location, self.text),
+            SearchHitKind::StringValue => format!("{}: {} \"{}\"", self.file.as_str(), location, self.text),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn is_word_boundary(c: char) -> bool
+{
+    matches!(c, ':' | '/' | '_' | '.' | ' ' | '<' | '>' | '-')
+}
+
+/// Scores `candidate` against a lowercase `query` if the query's characters appear in order in the candidate.
+///
+/// Consecutive matches and matches at the start of words score higher. Returns `None` if there is no match.
+///
+/// This is used to rank the editor's search results. It is only public for testing.
+#[doc(hidden)]
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i32>
+{
+    if query.is_empty() {
+        return None;
+    }
+
+    let mut query_chars = query.chars().peekable();
+    let mut score = 0;
+    let mut prev: Option<char> = None;
+    let mut prev_matched = false;
+    for c in candidate.chars() {
+        let Some(next) = query_chars.peek() else { break };
+        let matched = c.to_lowercase().eq(next.to_lowercase());
+        if matched {
+            query_chars.next();
+            score += 1;
+            if prev_matched {
+                score += 5;
+            }
+            let camel_case = c.is_uppercase() && prev.is_some_and(char::is_lowercase);
+            if prev.map(is_word_boundary).unwrap_or(true) || camel_case {
+                score += 3;
+            }
+        }
+        prev_matched = matched;
+        prev = Some(c);
+    }
+
+    if query_chars.peek().is_some() {
+        return None;
+    }
+
+    // Prefer shorter candidates.
+    Some(score * 4 - candidate.chars().count() as i32)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn visit_strings_map(map: &CobMap, f: &mut impl FnMut(&str))
+{
+    for entry in map.entries.iter() {
+        let CobMapEntry::KeyValue(key_value) = entry else { continue };
+        if let CobMapKey::Value(key) = &key_value.key {
+            visit_strings_value(key, f);
+        }
+        visit_strings_value(&key_value.value, f);
+    }
+}
+
+fn visit_strings_enum(value: &CobEnum, f: &mut impl FnMut(&str))
+{
+    match &value.variant {
+        CobEnumVariant::Unit => (),
+        CobEnumVariant::Tuple(tuple) => tuple.entries.iter().for_each(|v| visit_strings_value(v, f)),
+        CobEnumVariant::Array(array) => array.entries.iter().for_each(|v| visit_strings_value(v, f)),
+        CobEnumVariant::Map(map) => visit_strings_map(map, f),
+    }
+}
+
+fn visit_strings_value(value: &CobValue, f: &mut impl FnMut(&str))
+{
+    match value {
+        CobValue::Enum(value) => visit_strings_enum(value, f),
+        CobValue::Array(array) => array.entries.iter().for_each(|v| visit_strings_value(v, f)),
+        CobValue::Tuple(tuple) => tuple.entries.iter().for_each(|v| visit_strings_value(v, f)),
+        CobValue::Map(map) => visit_strings_map(map, f),
+        CobValue::String(string) => f(string.as_str()),
+        _ => (),
+    }
+}
+
+/// Calls `f` on every string value in a loadable.
+fn visit_strings(loadable: &CobLoadable, f: &mut impl FnMut(&str))
+{
+    match &loadable.variant {
+        CobLoadableVariant::Unit => (),
+        CobLoadableVariant::Tuple(tuple) => tuple.entries.iter().for_each(|v| visit_strings_value(v, f)),
+        CobLoadableVariant::Array(array) => array.entries.iter().for_each(|v| visit_strings_value(v, f)),
+        CobLoadableVariant::Map(map) => visit_strings_map(map, f),
+        CobLoadableVariant::Enum(value) => visit_strings_enum(value, f),
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+struct SearchCollector<'a>
+{
+    query: &'a str,
+    hits: Vec<SearchHit>,
+}
+
+impl SearchCollector<'_>
+{
+    fn try_add(&mut self, kind: SearchHitKind, file: &CobFile, scene_ref: Option<&SceneRef>, text: &str)
+    {
+        let Some(score) = fuzzy_score(self.query, text) else { return };
+        self.hits.push(SearchHit {
+            kind,
+            file: file.clone(),
+            scene_ref: scene_ref.cloned(),
+            text: text.to_string(),
+            score,
+        });
+    }
+
+    fn add_loadable(&mut self, file: &CobFile, scene_ref: Option<&SceneRef>, loadable: &CobLoadable)
+    {
+        let name = loadable.id.to_canonical(None);
+        self.try_add(SearchHitKind::Loadable, file, scene_ref, name.as_str());
+        visit_strings(loadable, &mut |string| {
+            self.try_add(SearchHitKind::StringValue, file, scene_ref, string);
+        });
+    }
+
+    fn add_layer(&mut self, file: &CobFile, scene_ref: SceneRef, layer: &CobSceneLayer)
+    {
+        let path = scene_ref.path.iter().collect::<Vec<_>>().join(SCENE_PATH_SEPARATOR);
+        self.try_add(SearchHitKind::SceneNode, file, Some(&scene_ref), path.as_str());
+
+        for entry in layer.entries.iter() {
+            match entry {
+                CobSceneLayerEntry::Loadable(loadable) => self.add_loadable(file, Some(&scene_ref), loadable),
+                CobSceneLayerEntry::Layer(child) => {
+                    self.add_layer(file, scene_ref.clone() + child.name.as_str(), child);
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Fuzzy-matches `query` against file names, manifest keys, scene paths, loadable names, and string values in all
+/// editable files.
+///
+/// Hits are sorted by descending score. At most [`MAX_SEARCH_HITS`] hits are returned.
+pub(super) fn search_editor(editor: &CobEditor, targets: &CobEditorSaveTargets, query: &str) -> Vec<SearchHit>
+{
+    let query = query.trim().to_lowercase();
+    let mut collector = SearchCollector { query: query.as_str(), hits: Vec::default() };
+
+    let mut files: Vec<(&CobFile, &CobFileData)> = editor
+        .iter_files()
+        .filter(|(f, _)| targets.is_editable(f))
+        .collect();
+    files.sort_unstable_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+
+    for (file, file_data) in files {
+        collector.try_add(SearchHitKind::File, file, None, file.as_str());
+
+        for section in file_data.data.sections.iter() {
+            match section {
+                CobSection::Manifest(manifest) => {
+                    for entry in manifest.entries.iter() {
+                        // Manifest keys are attributed to the file they point to if the editor has it.
+                        let target = match &entry.file {
+                            CobManifestFile::File(target) if editor.get_file(target).is_some() => target,
+                            _ => file,
+                        };
+                        collector.try_add(SearchHitKind::ManifestKey, target, None, entry.key.as_str());
+                    }
+                }
+                CobSection::Commands(commands) => {
+                    for entry in commands.entries.iter() {
+                        let CobCommandEntry::Loadable(loadable) = entry else { continue };
+                        collector.add_loadable(file, None, loadable);
+                    }
+                }
+                CobSection::Scenes(scenes) => {
+                    for scene in scenes.scenes.iter() {
+                        let scene_ref =
+                            SceneRef { file: file.clone().into(), path: ScenePath::new(scene.name.as_str()) };
+                        collector.add_layer(file, scene_ref, scene);
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    let mut hits = collector.hits;
+    hits.sort_by(|a, b| b.score.cmp(&a.score));
+    hits.truncate(MAX_SEARCH_HITS);
+    hits
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks the editor's search query.
+#[derive(Resource, Debug, Default)]
+pub(super) struct EditorSearchState
+{
+    query: String,
+    /// Time of the most recent file change that the search results don't include yet.
+    last_file_change: Option<Duration>,
+}
+
+impl EditorSearchState
+{
+    pub(super) fn query(&self) -> &str
+    {
+        self.query.as_str()
+    }
+
+    /// Sets the query. Does nothing if the query didn't change.
+    pub(super) fn set_query(&mut self, c: &mut Commands, query: String)
+    {
+        if self.query.trim() == query.trim() {
+            return;
+        }
+        self.query = query;
+        self.last_file_change = None;
+        c.react().broadcast(EditorSearchChanged);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn mark_search_stale(time: Res<Time<Real>>, mut state: ResMut<EditorSearchState>)
+{
+    if state.query.trim().is_empty() {
+        return;
+    }
+    state.last_file_change = Some(time.elapsed());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Refreshes search results once files stop changing, so rapid edits don't re-run the search every time.
+fn refresh_stale_search(mut c: Commands, time: Res<Time<Real>>, mut state: ResMut<EditorSearchState>)
+{
+    let Some(last_change) = state.last_file_change else { return };
+    if time.elapsed().saturating_sub(last_change) < SEARCH_REFRESH_DELAY {
+        return;
+    }
+    state.last_file_change = None;
+    c.react().broadcast(EditorSearchChanged);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(super) struct CobEditorSearchPlugin;
+
+impl Plugin for CobEditorSearchPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.init_resource::<EditorSearchState>()
+            .react(|rc| {
+                rc.on_persistent(
+                    (
+                        broadcast::<EditorNewFile>(),
+                        broadcast::<EditorFileExternalChange>(),
+                        broadcast::<EditorHistoryChanged>(),
+                    ),
+                    mark_search_stale,
+                )
+            })
+            .add_systems(Update, refresh_stale_search);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -1,4 +1,221 @@
 //! Tracks information needed to reconstruct an editor's 'view state' on reload. E.g. which tabs are open, which
 //! boxes checked, etc.
 
-// TODO
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::*;
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Name of the file in the asset directory where view state is saved.
+const STACK_FILE: &str = ".cob_editor_stack.ron";
+
+/// Minimum time between writes to the view state file.
+#[cfg(not(any(target_arch = "wasm32", target_os = "android")))]
+const SAVE_INTERVAL: Duration = Duration::from_secs(1);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Serialized form of [`EditorStack`] and [`EditorTreeState`].
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct EditorStackFile
+{
+    file: Option<String>,
+    scroll: Vec<(String, f32)>,
+    /// `(file, scene path)` for each folded scene node.
+    folded: Vec<(String, String)>,
+    selected: Option<(String, String)>,
+}
+
+#[cfg(not(any(target_arch = "wasm32", target_os = "android")))]
+fn scene_ref_to_strings(scene_ref: &SceneRef) -> (String, String)
+{
+    let path = scene_ref.path.iter().collect::<Vec<_>>().join(SCENE_PATH_SEPARATOR);
+    (scene_ref.file.as_str().into(), path)
+}
+
+fn scene_ref_from_strings((file, path): (String, String)) -> Option<SceneRef>
+{
+    let file = CobFile::try_new(file)?;
+    Some(SceneRef { file: file.into(), path: ScenePath::new(path) })
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn load_editor_stack(
+    settings: Res<EditorStackSettings>,
+    targets: Res<CobEditorSaveTargets>,
+    mut stack: ResMut<EditorStack>,
+    mut tree: ResMut<EditorTreeState>,
+)
+{
+    if !settings.persist {
+        return;
+    }
+    let Some(path) = targets.asset_dir().map(|dir| dir.join(STACK_FILE)) else { return };
+    stack.save_path = Some(path.clone());
+
+    let Ok(content) = std::fs::read_to_string(&path) else { return };
+    let saved: EditorStackFile = match ron::de::from_str(&content) {
+        Ok(saved) => saved,
+        Err(err) => {
+            tracing::warn!("failed loading editor view state from {path:?}, discarding it: {err:?}");
+            return;
+        }
+    };
+
+    stack.file = saved.file.and_then(CobFile::try_new);
+    stack.scroll = saved
+        .scroll
+        .into_iter()
+        .filter_map(|(file, scroll)| Some((CobFile::try_new(file)?, scroll)))
+        .collect();
+    tree.bypass_change_detection().restore(
+        saved.selected.and_then(scene_ref_from_strings),
+        saved.folded.into_iter().filter_map(scene_ref_from_strings),
+    );
+
+    // Loading is not a change that needs to be saved.
+    stack.dirty = false;
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Saves view state to the path set by [`load_editor_stack`].
+///
+/// Not available on `wasm32` or `android` targets, where files can't be written.
+#[cfg(not(any(target_arch = "wasm32", target_os = "android")))]
+fn save_editor_stack(time: Res<Time<Real>>, mut stack: ResMut<EditorStack>, tree: Res<EditorTreeState>)
+{
+    if tree.is_changed() && !tree.is_added() {
+        stack.dirty = true;
+    }
+    if !stack.dirty {
+        return;
+    }
+    let Some(path) = stack.save_path.clone() else { return };
+    let now = time.elapsed();
+    if stack
+        .last_save
+        .map(|last| now.saturating_sub(last) < SAVE_INTERVAL)
+        .unwrap_or(false)
+    {
+        return;
+    }
+    stack.dirty = false;
+    stack.last_save = Some(now);
+
+    let saved = EditorStackFile {
+        file: stack.file.as_ref().map(|f| f.as_str().into()),
+        scroll: stack
+            .scroll
+            .iter()
+            .map(|(file, scroll)| (file.as_str().into(), *scroll))
+            .collect(),
+        folded: tree.iter_folded().map(scene_ref_to_strings).collect(),
+        selected: tree.selected().map(scene_ref_to_strings),
+    };
+    let content = match ron::ser::to_string_pretty(&saved, ron::ser::PrettyConfig::default()) {
+        Ok(content) => content,
+        Err(err) => {
+            tracing::warn!("failed serializing editor view state: {err:?}");
+            return;
+        }
+    };
+
+    if let Err(err) = std::fs::write(&path, content) {
+        tracing::warn!("failed saving editor view state to {path:?}: {err:?}");
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Settings for persisting the editor's view state between sessions.
+///
+/// View state includes the selected file, folded and selected scene nodes, and scroll positions. It is always
+/// preserved when the editor rebuilds its view (e.g. after a hot reload or save).
+#[derive(Resource, Debug, Default, Clone)]
+pub struct EditorStackSettings
+{
+    /// If `true`, view state is saved to a `.cob_editor_stack.ron` dotfile in the asset directory and restored on
+    /// startup.
+    ///
+    /// Defaults to `false`.
+    pub persist: bool,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks editor view state that needs to survive view rebuilds.
+///
+/// Scene node state is tracked by [`EditorTreeState`].
+#[derive(Resource, Debug, Default)]
+#[cfg_attr(any(target_arch = "wasm32", target_os = "android"), allow(dead_code))]
+pub(super) struct EditorStack
+{
+    /// The file that was last selected.
+    file: Option<CobFile>,
+    /// Vertical scroll positions of file views, as normalized scrollbar values.
+    scroll: HashMap<CobFile, f32>,
+
+    /// Location where view state is saved. `None` if view state isn't persisted.
+    save_path: Option<PathBuf>,
+    dirty: bool,
+    last_save: Option<Duration>,
+}
+
+impl EditorStack
+{
+    pub(super) fn file(&self) -> Option<&CobFile>
+    {
+        self.file.as_ref()
+    }
+
+    pub(super) fn set_file(&mut self, file: Option<CobFile>)
+    {
+        if self.file == file {
+            return;
+        }
+        self.file = file;
+        self.dirty = true;
+    }
+
+    pub(super) fn scroll(&self, file: &CobFile) -> f32
+    {
+        self.scroll.get(file).copied().unwrap_or_default()
+    }
+
+    pub(super) fn set_scroll(&mut self, file: CobFile, scroll: f32)
+    {
+        if self.scroll.get(&file) == Some(&scroll) {
+            return;
+        }
+        self.scroll.insert(file, scroll);
+        self.dirty = true;
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(super) struct CobEditorStackPlugin;
+
+impl Plugin for CobEditorStackPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.init_resource::<EditorStackSettings>()
+            .init_resource::<EditorStack>()
+            .add_systems(Startup, load_editor_stack);
+
+        #[cfg(not(any(target_arch = "wasm32", target_os = "android")))]
+        app.add_systems(Last, save_editor_stack);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,129 @@
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+
+use super::*;
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Reactive event broadcasted when [`EditorTreeState`] changes.
+#[derive(Debug, Clone)]
+pub(super) struct EditorTreeChanged;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks the state of scene node trees in the editor.
+///
+/// Scene refs are stored with file paths (not manifest keys).
+#[derive(Resource, Debug, Default)]
+pub(super) struct EditorTreeState
+{
+    /// The currently-selected scene node.
+    selected: Option<SceneRef>,
+    /// Scene nodes whose content is hidden.
+    folded: HashSet<SceneRef>,
+}
+
+impl EditorTreeState
+{
+    pub(super) fn is_selected(&self, scene_ref: &SceneRef) -> bool
+    {
+        self.selected.as_ref() == Some(scene_ref)
+    }
+
+    pub(super) fn selected(&self) -> Option<&SceneRef>
+    {
+        self.selected.as_ref()
+    }
+
+    pub(super) fn is_folded(&self, scene_ref: &SceneRef) -> bool
+    {
+        self.folded.contains(scene_ref)
+    }
+
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android")))]
+    pub(super) fn iter_folded(&self) -> impl Iterator<Item = &SceneRef> + '_
+    {
+        self.folded.iter()
+    }
+
+    /// Replaces the tree state (e.g. with state saved in a previous session).
+    pub(super) fn restore(&mut self, selected: Option<SceneRef>, folded: impl IntoIterator<Item = SceneRef>)
+    {
+        self.selected = selected;
+        self.folded = folded.into_iter().collect();
+    }
+
+    pub(super) fn toggle_folded(&mut self, scene_ref: &SceneRef)
+    {
+        if !self.folded.remove(scene_ref) {
+            self.folded.insert(scene_ref.clone());
+        }
+    }
+
+    /// Selects a scene node and unfolds its ancestors so it is visible.
+    fn select(&mut self, scene_ref: SceneRef)
+    {
+        let mut path = scene_ref.path.parent();
+        while let Some(parent) = path {
+            self.folded
+                .remove(&SceneRef { file: scene_ref.file.clone(), path: parent.clone() });
+            path = parent.parent();
+        }
+        self.selected = Some(scene_ref);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Command for selecting a scene node in the editor.
+///
+/// Switches the editor to the node's file, unfolds the node's ancestors, and highlights the node.
+#[derive(Debug)]
+pub struct SelectSceneNode
+{
+    pub scene_ref: SceneRef,
+}
+
+impl Command for SelectSceneNode
+{
+    fn apply(self, world: &mut World)
+    {
+        let Some(file) = self.scene_ref.file.file().cloned() else {
+            tracing::warn!("failed selecting scene node {:?} in editor; scene ref has a manifest key instead of a \
+                file", self.scene_ref);
+            return;
+        };
+        if world.resource::<CobEditor>().get_file(&file).is_none()
+            || !world.resource::<CobEditorSaveTargets>().is_editable(&file)
+        {
+            tracing::warn!("failed selecting scene node {:?} in editor; file is not editable", self.scene_ref);
+            return;
+        }
+
+        world
+            .resource_mut::<EditorTreeState>()
+            .select(self.scene_ref.clone());
+
+        let mut c = world.commands();
+        c.react().broadcast(EditorTreeChanged);
+        c.react()
+            .broadcast(EditorSceneNodeSelected { file, scene_ref: self.scene_ref });
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(super) struct CobEditorTreePlugin;
+
+impl Plugin for CobEditorTreePlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.init_resource::<EditorTreeState>();
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -32,6 +32,18 @@ impl CobHashRegistry
         prev != hash
     }
 
+    /// Gets the most recent hash recorded for a file.
+    pub(super) fn get_file_hash(&self, file: &str) -> Option<CobFileHash>
+    {
+        let Ok(registry) = self.registry.lock() else {
+            warn_once!("CobHashRegistry's internal mutex is poisoned, which may reduce COB file loading perf \
+                slightly; this warning only prints once");
+            return None;
+        };
+
+        registry.get(file).copied()
+    }
+
     /// The saved hash is only changed if `old` matches the currently-saved hash.
     /// This is used to synchronize editor save operations with file refreshes that occur in the asset loader. It
     /// ensures if there is a file moving from the asset loader to the editor, that the editor's saved file will
@@ -0,0 +1,255 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn path_string(path: &ScenePath) -> String
+{
+    path.iter().collect::<Vec<_>>().join(SCENE_PATH_SEPARATOR)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Collects scene nodes in pre-order (parents before children).
+fn collect_nodes<'a>(data: &'a Cob, nodes: &mut Vec<(ScenePath, &'a CobSceneLayer)>)
+{
+    fn collect_layer<'a>(
+        path: ScenePath,
+        layer: &'a CobSceneLayer,
+        nodes: &mut Vec<(ScenePath, &'a CobSceneLayer)>,
+    )
+    {
+        nodes.push((path.clone(), layer));
+        for entry in layer.entries.iter() {
+            let CobSceneLayerEntry::Layer(child) = entry else { continue };
+            collect_layer(path.extend(child.name.as_str()), child, nodes);
+        }
+    }
+
+    for section in data.sections.iter() {
+        let CobSection::Scenes(scenes) = section else { continue };
+        for scene in scenes.scenes.iter() {
+            collect_layer(ScenePath::new(scene.name.as_str()), scene, nodes);
+        }
+    }
+}
+
+/// Gets a scene node's entries, excluding child nodes.
+pub(super) fn own_entries(layer: &CobSceneLayer) -> Vec<&CobSceneLayerEntry>
+{
+    layer
+        .entries
+        .iter()
+        .filter(|e| !matches!(e, CobSceneLayerEntry::Layer(_)))
+        .collect()
+}
+
+/// Replaces a scene node's entries with `entries`, keeping the node's children.
+///
+/// `entries` should not contain child nodes.
+pub(super) fn replace_own_entries(layer: &mut CobSceneLayer, entries: impl IntoIterator<Item = CobSceneLayerEntry>)
+{
+    let children = layer
+        .entries
+        .drain(..)
+        .filter(|e| matches!(e, CobSceneLayerEntry::Layer(_)));
+    let mut entries: Vec<CobSceneLayerEntry> = entries.into_iter().collect();
+    entries.extend(children);
+    layer.entries = entries;
+}
+
+/// Inserts a scene node without its children.
+fn insert_node(data: &mut Cob, path: &ScenePath, layer: &CobSceneLayer) -> bool
+{
+    let mut new_layer = layer.clone();
+    new_layer
+        .entries
+        .retain(|e| !matches!(e, CobSceneLayerEntry::Layer(_)));
+
+    match path.parent().filter(|p| p.len() > 0) {
+        Some(parent) => {
+            let Some(parent) = data.get_scene_layer_mut(&parent) else { return false };
+            parent.entries.push(CobSceneLayerEntry::Layer(new_layer));
+        }
+        None => {
+            let Some(scenes) = data.sections.iter_mut().rev().find_map(|s| match s {
+                CobSection::Scenes(scenes) => Some(scenes),
+                _ => None,
+            }) else {
+                return false;
+            };
+            scenes.scenes.push(new_layer);
+        }
+    }
+
+    true
+}
+
+/// Removes a scene node and its children.
+fn remove_node(data: &mut Cob, path: &ScenePath)
+{
+    let Some(name) = path.iter().last() else { return };
+
+    match path.parent().filter(|p| p.len() > 0) {
+        Some(parent) => {
+            let Some(parent) = data.get_scene_layer_mut(&parent) else { return };
+            parent.entries.retain(|e| match e {
+                CobSceneLayerEntry::Layer(layer) => layer.name.as_str() != name,
+                _ => true,
+            });
+        }
+        None => {
+            for section in data.sections.iter_mut() {
+                let CobSection::Scenes(scenes) = section else { continue };
+                scenes.scenes.retain(|s| s.name.as_str() != name);
+            }
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the name of a section's kind. Scenes sections are not merged by kind so they have no name.
+fn section_kind(section: &CobSection) -> Option<&'static str>
+{
+    match section {
+        CobSection::Manifest(_) => Some("#manifest"),
+        CobSection::Import(_) => Some("#import"),
+        CobSection::Defs(_) => Some("#defs"),
+        CobSection::Commands(_) => Some("#commands"),
+        CobSection::Scenes(_) => None,
+    }
+}
+
+pub(super) fn sections_of_kind<'a>(data: &'a Cob, kind: &str) -> Vec<&'a CobSection>
+{
+    data.sections
+        .iter()
+        .filter(|s| section_kind(s) == Some(kind))
+        .collect()
+}
+
+/// Replaces all sections of a kind with `replacements`.
+///
+/// Replacements are inserted where the first replaced section was, or before the first `#scenes` section.
+pub(super) fn replace_sections(data: &mut Cob, kind: &str, replacements: Vec<CobSection>)
+{
+    let index = data
+        .sections
+        .iter()
+        .position(|s| section_kind(s) == Some(kind))
+        .or_else(|| {
+            data.sections
+                .iter()
+                .position(|s| matches!(s, CobSection::Scenes(_)))
+        })
+        .unwrap_or(data.sections.len());
+    data.sections.retain(|s| section_kind(s) != Some(kind));
+    let index = index.min(data.sections.len());
+    data.sections.splice(index..index, replacements);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Merges edits from two versions of a file that were both derived from `base`.
+///
+/// Edits are merged at the granularity of scene nodes. A scene node's loadables are treated as one unit, and
+/// `#manifest`, `#import`, `#defs`, and `#commands` are each treated as one unit. Changes to the order of scene
+/// nodes in `mine` are not merged.
+///
+/// Returns descriptions of units that were edited in both versions if the merge fails.
+pub(super) fn merge_cob(base: &Cob, mine: &Cob, theirs: &Cob) -> Result<Cob, Vec<String>>
+{
+    let mut merged = theirs.clone();
+    let mut conflicts = Vec::default();
+
+    // Merge non-scene sections.
+    for kind in ["#manifest", "#import", "#defs", "#commands"] {
+        let base_sections = sections_of_kind(base, kind);
+        let my_sections = sections_of_kind(mine, kind);
+        if my_sections == base_sections {
+            continue;
+        }
+        let their_sections = sections_of_kind(theirs, kind);
+        if their_sections == my_sections {
+            continue;
+        }
+        if their_sections != base_sections {
+            conflicts.push(kind.into());
+            continue;
+        }
+        let replacements = my_sections.into_iter().cloned().collect();
+        replace_sections(&mut merged, kind, replacements);
+    }
+
+    // Collect scene nodes.
+    let mut base_nodes = Vec::default();
+    collect_nodes(base, &mut base_nodes);
+    let base_nodes: HashMap<ScenePath, &CobSceneLayer> = base_nodes.into_iter().collect();
+    let mut their_nodes = Vec::default();
+    collect_nodes(theirs, &mut their_nodes);
+    let their_nodes: HashMap<ScenePath, &CobSceneLayer> = their_nodes.into_iter().collect();
+    let mut my_nodes = Vec::default();
+    collect_nodes(mine, &mut my_nodes);
+
+    // Apply nodes that were edited or added in `mine`.
+    for (path, my_layer) in my_nodes.iter() {
+        let mine_own = own_entries(my_layer);
+        let base_own = base_nodes.get(path).map(|l| own_entries(l));
+        if base_own.as_ref() == Some(&mine_own) {
+            continue;
+        }
+
+        match (their_nodes.get(path), base_own) {
+            (Some(their_layer), base_own) => {
+                let their_own = own_entries(their_layer);
+                if their_own == mine_own {
+                    continue;
+                }
+                if base_own.as_ref() != Some(&their_own) {
+                    conflicts.push(format!("scene node \"{}\"", path_string(path)));
+                    continue;
+                }
+                if let Some(layer) = merged.get_scene_layer_mut(path) {
+                    replace_own_entries(layer, mine_own.iter().map(|e| (*e).clone()));
+                }
+            }
+            (None, Some(_)) => {
+                // Removed in `theirs`, edited in `mine`.
+                conflicts.push(format!("scene node \"{}\"", path_string(path)));
+            }
+            (None, None) => {
+                // Added in `mine`. Children were collected after their parents so they will be inserted after the
+                // parent is inserted.
+                if merged.get_scene_layer_mut(path).is_none() && !insert_node(&mut merged, path, my_layer) {
+                    conflicts.push(format!("scene node \"{}\"", path_string(path)));
+                }
+            }
+        }
+    }
+
+    // Remove nodes that were removed in `mine`.
+    let my_paths: HashSet<&ScenePath> = my_nodes.iter().map(|(p, _)| p).collect();
+    for (path, base_layer) in base_nodes.iter() {
+        if my_paths.contains(path) {
+            continue;
+        }
+        let Some(their_layer) = their_nodes.get(path) else { continue };
+        if their_layer != base_layer {
+            conflicts.push(format!("scene node \"{}\"", path_string(path)));
+            continue;
+        }
+        remove_node(&mut merged, path);
+    }
+
+    if !conflicts.is_empty() {
+        conflicts.sort_unstable();
+        conflicts.dedup();
+        return Err(conflicts);
+    }
+
+    Ok(merged)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
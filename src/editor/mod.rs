@@ -4,12 +4,22 @@ use crate as bevy_cobweb_ui;
 
 mod build;
 mod death_signal;
+mod default_widgets;
 mod editor;
 mod editor_commands;
+mod editor_defs;
 mod editor_events;
-//mod editor_stack;
+mod editor_history;
+mod editor_picker;
+mod editor_protocol;
+mod editor_remote;
+mod editor_search;
+mod editor_stack;
+mod editor_tree;
 mod hash_registry;
+mod merge;
 mod plugin;
+mod save_targets;
 mod template;
 mod utils;
 mod widget_interop;
@@ -17,12 +27,22 @@ mod widget_registry;
 
 pub(self) use build::*;
 pub(self) use death_signal::*;
+pub(self) use default_widgets::*;
 pub(crate) use editor::*;
 pub use editor_commands::*;
+pub(self) use editor_defs::*;
 pub use editor_events::*;
-//pub(self) use editor_stack::*;
+pub(self) use editor_history::*;
+pub(self) use editor_picker::*;
+pub use editor_protocol::*;
+pub use editor_remote::*;
+pub use editor_search::*;
+pub use editor_stack::*;
+pub use editor_tree::*;
 pub(crate) use hash_registry::*;
+pub(self) use merge::*;
 pub(crate) use plugin::*;
+pub use save_targets::*;
 pub(self) use template::*;
 pub(self) use utils::*;
 pub use widget_interop::*;
@@ -11,8 +11,15 @@ impl Plugin for CobEditorPlugin
     fn build(&self, app: &mut App)
     {
         app.add_plugins(CobWidgetRegistryPlugin)
+            .add_plugins(CobDefaultWidgetsPlugin)
             .add_plugins(CobHashRegistryPlugin)
+            .add_plugins(CobEditorSaveTargetsPlugin)
             .add_plugins(CobEditorImplPlugin)
+            .add_plugins(CobEditorTreePlugin)
+            .add_plugins(CobEditorDefsPlugin)
+            .add_plugins(CobEditorSearchPlugin)
+            .add_plugins(CobEditorStackPlugin)
+            .add_plugins(CobEditorPickerPlugin)
             .add_plugins(CobEditorBuildPlugin);
     }
 }
@@ -0,0 +1,171 @@
+use std::path::{Path, PathBuf};
+
+use bevy::asset::io::file::FileAssetReader;
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Custom save target resolver. See [`CobEditorSaveTargets::set_resolver`].
+pub type CobSaveResolverFn = dyn Fn(&CobFile) -> Option<PathBuf> + Send + Sync + 'static;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resolves the filesystem paths where the editor saves COB files.
+///
+/// Only files with a save target can be edited. By default, files from the default asset source are saved to the
+/// asset directory, and files from named asset sources (e.g. `embedded://`) have no save target.
+///
+/// Files are resolved in this order:
+/// 1. The custom resolver, if one is set.
+/// 2. The longest prefix added with [`Self::add_prefix`] that matches the file name.
+/// 3. The asset directory, if the file is in the default asset source.
+///
+/// Files cannot be saved on `wasm32` or `android` targets.
+#[derive(Resource, Default)]
+pub struct CobEditorSaveTargets
+{
+    /// Asset directory location for the default asset source.
+    asset_dir: Option<PathBuf>,
+    /// [ file name prefix : directory ]
+    ///
+    /// Sorted by descending prefix length so the longest match is found first.
+    prefixes: Vec<(String, PathBuf)>,
+    resolver: Option<Box<CobSaveResolverFn>>,
+}
+
+impl CobEditorSaveTargets
+{
+    /// Maps files whose names start with `prefix` to `dir`. The rest of the file name is appended to `dir`.
+    ///
+    /// File names include their asset source, so the prefix can be an asset source (e.g. `shared://`), or an
+    /// embedded crate (e.g. `embedded://my_ui/` with `dir` pointing to the directory passed to
+    /// `embedded_asset!`). Enable bevy's `embedded_watcher` feature so saved embedded files are hot-reloaded.
+    ///
+    /// Replaces the directory if the prefix was already added.
+    pub fn add_prefix(&mut self, prefix: impl Into<String>, dir: impl Into<PathBuf>) -> &mut Self
+    {
+        let prefix = prefix.into();
+        let dir = dir.into();
+        match self.prefixes.iter_mut().find(|(p, _)| *p == prefix) {
+            Some((_, existing)) => *existing = dir,
+            None => {
+                self.prefixes.push((prefix, dir));
+                self.prefixes
+                    .sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()));
+            }
+        }
+        self
+    }
+
+    /// Sets a custom resolver that takes precedence over prefixes and the asset directory.
+    ///
+    /// If the resolver returns `None` for a file, then the file is resolved normally.
+    pub fn set_resolver(
+        &mut self,
+        resolver: impl Fn(&CobFile) -> Option<PathBuf> + Send + Sync + 'static,
+    ) -> &mut Self
+    {
+        self.resolver = Some(Box::new(resolver));
+        self
+    }
+
+    /// Gets the path where a file will be saved.
+    ///
+    /// Returns `None` if the file can't be saved.
+    pub fn resolve(&self, file: &CobFile) -> Option<PathBuf>
+    {
+        if let Some(path) = self.resolver.as_ref().and_then(|r| (r)(file)) {
+            return Some(path);
+        }
+
+        let name = file.as_str();
+        if let Some((prefix, dir)) = self.prefixes.iter().find(|(p, _)| name.starts_with(p.as_str())) {
+            return Some(dir.join(name[prefix.len()..].trim_start_matches('/')));
+        }
+
+        if name.contains("://") {
+            return None;
+        }
+        self.asset_dir.as_ref().map(|dir| dir.join(name))
+    }
+
+    /// Returns `true` if the file has a save target.
+    pub fn is_editable(&self, file: &CobFile) -> bool
+    {
+        self.resolve(file).is_some()
+    }
+
+    /// Gets the asset directory location, if available.
+    pub(super) fn asset_dir(&self) -> Option<&Path>
+    {
+        self.asset_dir.as_deref()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// App extension trait for configuring where the editor saves files.
+pub trait CobEditorSaveTargetsAppExt
+{
+    /// Maps files whose names start with `prefix` to `dir`. See [`CobEditorSaveTargets::add_prefix`].
+    fn add_editor_save_prefix(&mut self, prefix: impl Into<String>, dir: impl Into<PathBuf>) -> &mut Self;
+
+    /// Sets a custom save target resolver. See [`CobEditorSaveTargets::set_resolver`].
+    fn set_editor_save_resolver(
+        &mut self,
+        resolver: impl Fn(&CobFile) -> Option<PathBuf> + Send + Sync + 'static,
+    ) -> &mut Self;
+}
+
+impl CobEditorSaveTargetsAppExt for App
+{
+    fn add_editor_save_prefix(&mut self, prefix: impl Into<String>, dir: impl Into<PathBuf>) -> &mut Self
+    {
+        self.world_mut()
+            .get_resource_or_init::<CobEditorSaveTargets>()
+            .add_prefix(prefix, dir);
+        self
+    }
+
+    fn set_editor_save_resolver(
+        &mut self,
+        resolver: impl Fn(&CobFile) -> Option<PathBuf> + Send + Sync + 'static,
+    ) -> &mut Self
+    {
+        self.world_mut()
+            .get_resource_or_init::<CobEditorSaveTargets>()
+            .set_resolver(resolver);
+        self
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(super) struct CobEditorSaveTargetsPlugin;
+
+impl Plugin for CobEditorSaveTargetsPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        let added = app.get_added_plugins::<AssetPlugin>();
+        let asset_plugin = added
+            .get(0)
+            .expect("AssetPlugin should be added before CobwebUiPlugin");
+
+        #[cfg(not(any(target_arch = "wasm32", target_os = "android")))]
+        let asset_dir = Some(FileAssetReader::new(asset_plugin.file_path.clone()).root_path().clone());
+        #[cfg(any(target_arch = "wasm32", target_os = "android"))]
+        let asset_dir = {
+            let _ = asset_plugin;
+            None
+        };
+
+        app.world_mut()
+            .get_resource_or_init::<CobEditorSaveTargets>()
+            .asset_dir = asset_dir;
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
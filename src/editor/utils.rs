@@ -1,7 +1,9 @@
 use std::any::TypeId;
 
+use bevy::prelude::*;
 use bevy::reflect::serde::TypedReflectDeserializer;
 use bevy::reflect::{TypeRegistration, TypeRegistry};
+use serde::de::DeserializeSeed;
 
 use super::*;
 use crate::prelude::*;
@@ -10,14 +12,101 @@ use crate::prelude::*;
 
 pub(super) fn get_targeted<'a>(data: &'a mut Cob, editor_ref: &CobEditorRef) -> Option<&'a mut CobLoadable>
 {
-    match editor_ref.is_command() {
-        true => data.get_command_loadable_mut(&*editor_ref.loadable_name),
-        false => data.get_scene_loadable_mut(&editor_ref.scene_ref.path, &*editor_ref.loadable_name),
+    get_targeted_at(data, &editor_ref.scene_ref, editor_ref.loadable_name)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(super) fn get_targeted_at<'a>(
+    data: &'a mut Cob,
+    scene_ref: &SceneRef,
+    loadable_name: &str,
+) -> Option<&'a mut CobLoadable>
+{
+    match scene_ref.path.iter().next() == Some("#commands") {
+        true => data.get_command_loadable_mut(loadable_name),
+        false => data.get_scene_loadable_mut(&scene_ref.path, loadable_name),
     }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Gets the definition of the constant `$name` in a file's `#defs` sections.
+pub(super) fn get_constant_def_mut<'a>(data: &'a mut Cob, name: &str) -> Option<&'a mut CobConstantDef>
+{
+    data.sections
+        .iter_mut()
+        .filter_map(|s| match s {
+            CobSection::Defs(defs) => Some(defs),
+            _ => None,
+        })
+        .flat_map(|defs| defs.entries.iter_mut())
+        .filter_map(|e| match e {
+            CobDefEntry::Constant(def) => Some(def),
+            _ => None,
+        })
+        .find(|def| def.name.as_str() == name)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Deserializes the value of a constant that is edited with widgets.
+///
+/// Fails if the constant is a value group or if its value doesn't match `type_id`.
+pub(super) fn reflect_constant(
+    type_registry: &TypeRegistry,
+    type_id: TypeId,
+    value: &CobConstantValue,
+) -> Result<Box<dyn PartialReflect>, String>
+{
+    let CobConstantValue::Value(value) = value else {
+        return Err("value groups can't be reflected".into());
+    };
+    let Some(registration) = type_registry.get(type_id) else {
+        return Err(format!("type {type_id:?} is not registered"));
+    };
+    TypedReflectDeserializer::new(registration, type_registry)
+        .deserialize(value)
+        .map_err(|err| format!("value failed to deserialize: {err:?}"))
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the value that `editor_ref` points to from the current file data in the editor.
+///
+/// Use this to build patches from the latest value instead of a copy captured when a widget was spawned.
+pub(super) fn get_editor_value(world: &mut World, editor_ref: &CobEditorRef) -> Option<Box<dyn PartialReflect>>
+{
+    let SceneFile::File(file) = &editor_ref.scene_ref.file else { return None };
+
+    world.resource_scope::<CobEditor, _>(|world: &mut World, mut editor: Mut<CobEditor>| {
+        let file_data = editor.get_file_mut(file)?;
+
+        if let Some(constant) = &editor_ref.constant {
+            let def = get_constant_def_mut(&mut file_data.data, &constant.name)?;
+            let type_registry = world.resource::<AppTypeRegistry>().read();
+            let mut reflected = reflect_constant(&type_registry, constant.type_id, &def.value).ok()?;
+            return editor_ref
+                .structure_path
+                .get_value_mut(reflected.as_mut())
+                .map(|value| value.clone_value());
+        }
+
+        let targeted = get_targeted(&mut file_data.data, editor_ref)?;
+
+        let loadables = world.resource::<LoadableRegistry>();
+        let type_registry = world.resource::<AppTypeRegistry>().read();
+        let (deserializer, ..) = get_deserializer(&type_registry, editor_ref.loadable_name, loadables)?;
+        let mut reflected = deserializer.deserialize(&*targeted).ok()?;
+        editor_ref
+            .structure_path
+            .get_value_mut(reflected.as_mut())
+            .map(|value| value.clone_value())
+    })
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 pub(super) fn get_registration<'a>(
     type_registry: &'a TypeRegistry,
     short_name: &str,
@@ -1,9 +1,12 @@
 #![allow(dead_code)] // TODO: remove
 
+use std::any::TypeId;
 use std::sync::Arc;
 
 use bevy::prelude::*;
-use bevy::reflect::{ApplyError, ReflectMut};
+use bevy::reflect::{ApplyError, ReflectMut, ReflectRef};
+use bevy_cobweb::prelude::*;
+use smol_str::SmolStr;
 
 use super::*;
 use crate::prelude::*;
@@ -11,7 +14,7 @@ use crate::prelude::*;
 //-------------------------------------------------------------------------------------------------------------------
 
 /// A structure point is a specific item inside some container.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub(super) enum ReflectStructurePoint
 {
     /// Includes the field name.
@@ -95,7 +98,7 @@ impl ReflectStructurePoint
 /// Records a 'path' into a reflected type where a [`CobEditorWidget`] is editing.
 ///
 /// Used to target-patch parts of a reflected type.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub(super) struct ReflectStructurePath
 {
     pub(super) path: Arc<[ReflectStructurePoint]>,
@@ -103,6 +106,30 @@ pub(super) struct ReflectStructurePath
 
 impl ReflectStructurePath
 {
+    /// Makes a new path that points to `point` inside the current location.
+    pub(super) fn extend(&self, point: ReflectStructurePoint) -> Self
+    {
+        let mut path = Vec::with_capacity(self.path.len() + 1);
+        path.extend(self.path.iter().cloned());
+        path.push(point);
+        Self { path: Arc::from(path) }
+    }
+
+    /// Gets the value in `target` that the path points to.
+    ///
+    /// Returns `None` if the path doesn't match the structure of `target`.
+    pub(super) fn get_value_mut<'a>(
+        &self,
+        target: &'a mut (dyn PartialReflect + 'static),
+    ) -> Option<&'a mut (dyn PartialReflect + 'static)>
+    {
+        let mut target_part = target;
+        for point in self.path.iter() {
+            target_part = point.destructure(target_part)?;
+        }
+        Some(target_part)
+    }
+
     /// Finds location in target value to patch in the new value.
     ///
     /// If `path` is empty, then `value` is directly assigned to `target`. Otherwise [`PartialReflect::try_apply`]
@@ -131,12 +158,33 @@ impl ReflectStructurePath
             target_part = next;
         }
 
-        target.try_apply(value.as_ref()).map_err(|e| Some(e))
+        // Lists need to be truncated manually since `try_apply` only appends new entries.
+        if let (ReflectMut::List(target_list), ReflectRef::List(value_list)) =
+            (target_part.reflect_mut(), value.reflect_ref())
+        {
+            while target_list.len() > value_list.len() {
+                target_list.pop();
+            }
+        }
+
+        target_part.try_apply(value.as_ref()).map_err(|e| Some(e))
     }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// A `#defs` constant edited with widgets.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct EditorConstantRef
+{
+    /// Name of the constant without the leading `$`.
+    pub(super) name: SmolStr,
+    /// The constant's reflected type, inferred from the loadables that reference it.
+    pub(super) type_id: TypeId,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 #[derive(Clone, Debug)]
 pub struct CobEditorRef
 {
@@ -152,12 +200,18 @@ pub struct CobEditorRef
     pub(super) loadable_name: &'static str,
     /// If the path is empty then the ref points to a loadable, otherwise it points to a value inside a loadable.
     pub(super) structure_path: ReflectStructurePath,
+    /// Set if the reference points to a constant's value instead of a loadable.
+    ///
+    /// `scene_ref` then has the path `#defs`, and `loadable_name` is the short name of the constant's type.
+    pub(super) constant: Option<EditorConstantRef>,
     /// Death signal used to block patch submissions if the widget has been killed by the editor.
     ///
     /// The editor kills widgets whenever a structural change is made to a destructured loadable, to ensure
     /// editor refs are recreated accurately (e.g. when inserting/removing list elements, the old widgets
     /// may have stale indices recorded).
     pub(super) death_signal: DeathSignal,
+    /// Entity that holds the loadable's widgets.
+    pub(super) widgets_entity: Entity,
 }
 
 impl CobEditorRef
@@ -180,10 +234,41 @@ impl CobEditorRef
     {
         self.scene_ref.path.iter().next() == Some("#commands")
     }
+
+    /// Returns `true` if this reference points to the value of a constant in a `#defs` section instead of a
+    /// loadable.
+    pub fn is_constant(&self) -> bool
+    {
+        self.constant.is_some()
+    }
+
+    /// Queues a rebuild of all widgets for the referenced loadable.
+    ///
+    /// Use this after submitting a [`SubmitPatch`] that changes the structure of the loadable (e.g. switching an
+    /// enum variant or adding a list entry). Widgets are rebuilt after queued patches have been applied, and
+    /// existing widgets are marked 'dead'.
+    pub fn rebuild_widgets(&self, c: &mut Commands)
+    {
+        c.react()
+            .entity_event(self.widgets_entity, RebuildEditorWidgets);
+    }
+
+    /// Makes a reference to `point` inside the currently-referenced value.
+    pub(super) fn extend(&self, point: ReflectStructurePoint) -> Self
+    {
+        let mut editor_ref = self.clone();
+        editor_ref.structure_path = self.structure_path.extend(point);
+        editor_ref
+    }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Entity event sent to a loadable's widgets entity to rebuild its widgets.
+pub(super) struct RebuildEditorWidgets;
+
+//-------------------------------------------------------------------------------------------------------------------
+
 /// Trait for editor widgets that want to edit specific loadables or values that can appear in loadables.
 ///
 /// ## Limitations
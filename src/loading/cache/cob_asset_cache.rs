@@ -142,6 +142,38 @@ impl CobAssetCache
         None
     }
 
+    /// Replaces a file's data with data edited in the editor, and queues the file to be re-processed as if it had
+    /// been hot-reloaded.
+    ///
+    /// Returns `false` if the file is unknown or if its hash doesn't match `hash` (e.g. because a hot-reloaded
+    /// version of the file is being processed).
+    #[cfg(feature = "editor")]
+    pub(crate) fn reprocess_file_for_editor(
+        &mut self,
+        hash: crate::editor::CobFileHash,
+        data: Cob,
+        commands_buffer: &mut CommandsBuffer,
+    ) -> bool
+    {
+        if let Some(processed) = self.processed.get(&data.file) {
+            if processed.hash != hash {
+                return false;
+            }
+            let file = data.file.clone();
+            let processed = self.processed.remove(&file).unwrap();
+            commands_buffer.prep_commands_refresh(file.clone());
+            self.add_preprocessed_file(file, processed.imports, data, hash);
+            return true;
+        }
+
+        let Some(preprocessed) = self.preprocessed.iter_mut().find(|p| p.file == data.file) else { return false };
+        if preprocessed.hash != hash {
+            return false;
+        }
+        preprocessed.data = data;
+        true
+    }
+
     /// Prepares a cobweb asset file.
     pub(crate) fn prepare_file(&mut self, file: CobFile)
     {
@@ -308,6 +340,8 @@ impl CobAssetCache
         commands_buffer: &mut CommandsBuffer,
         _scene_buffer: &mut SceneBuffer,
         _scene_loader: &mut SceneLoader,
+        conditions: &CommandConditionContext,
+        themes: &mut CobThemes,
     )
     {
         // Initialize constants map from dependencies.
@@ -355,6 +389,7 @@ impl CobAssetCache
             &mut constants_buff,
             &mut specs,
         );
+        themes.try_extract_tokens(&preprocessed.file, &constants_buff);
 
         extract_cob_commands(
             type_registry,
@@ -363,6 +398,7 @@ impl CobAssetCache
             &mut preprocessed.data,
             loadables,
             &constants_buff,
+            conditions,
             &specs,
         );
 
@@ -378,6 +414,7 @@ impl CobAssetCache
                 preprocessed.data,
                 loadables,
                 &constants_buff,
+                themes.active_tokens(),
                 &specs,
             );
         }
@@ -444,6 +481,8 @@ impl CobAssetCache
         commands_buffer: &mut CommandsBuffer,
         scene_buffer: &mut SceneBuffer,
         scene_loader: &mut SceneLoader,
+        conditions: &CommandConditionContext,
+        themes: &mut CobThemes,
     ) -> bool
     {
         // Loop preprocessed until nothing can be processed.
@@ -487,6 +526,8 @@ impl CobAssetCache
                     commands_buffer,
                     scene_buffer,
                     scene_loader,
+                    conditions,
+                    themes,
                 );
                 num_processed += 1;
             }
@@ -528,6 +569,7 @@ impl CobAssetCache
         c: &mut Commands,
         scene_buffer: &mut SceneBuffer,
         scene_loader: &mut SceneLoader,
+        themes: &CobThemes,
         #[cfg(feature = "editor")] editor: &mut crate::editor::CobEditor,
     )
     {
@@ -544,6 +586,7 @@ impl CobAssetCache
                 data,
                 loadables,
                 &processed.constants_buff,
+                themes.active_tokens(),
                 &processed.specs,
             );
 
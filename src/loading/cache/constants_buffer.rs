@@ -81,6 +81,15 @@ impl ConstantsBuffer
         })
     }
 
+    /// Iterates constants defined in the most recently collected file.
+    pub(crate) fn iter_file_constants(&self) -> impl Iterator<Item = (&SmolStr, &CobConstantValue)> + '_
+    {
+        self.stack
+            .last()
+            .into_iter()
+            .flat_map(|(_, map)| map.iter())
+    }
+
     pub(crate) fn append(&mut self, alias: &CobImportAlias, to_append: &Self)
     {
         let alias = alias.as_str();
@@ -70,9 +70,13 @@ fn process_cobweb_asset_files(
     mut commands_buffer: ResMut<CommandsBuffer>,
     mut scene_buffer: ResMut<SceneBuffer>,
     mut scene_loader: ResMut<SceneLoader>,
+    cfgs: Res<CobCfgs>,
+    locale: Option<Res<Locale>>,
+    mut themes: ResMut<CobThemes>,
 )
 {
     let type_registry = types.read();
+    let conditions = CommandConditionContext { cfgs: &cfgs, locale: locale.as_deref() };
 
     if cob_cache.process_cobweb_asset_files(
         &type_registry,
@@ -81,6 +85,8 @@ fn process_cobweb_asset_files(
         &mut commands_buffer,
         &mut scene_buffer,
         &mut scene_loader,
+        &conditions,
+        &mut themes,
     ) {
         c.react().broadcast(CobCacheUpdated);
     }
@@ -127,6 +133,7 @@ fn apply_pending_node_updates_extract(
     commands_buffer: Res<CommandsBuffer>,
     mut scene_buffer: ResMut<SceneBuffer>,
     mut scene_loader: ResMut<SceneLoader>,
+    themes: Res<CobThemes>,
     #[cfg(feature = "editor")] mut editor: ResMut<crate::editor::CobEditor>,
 )
 {
@@ -143,6 +150,7 @@ fn apply_pending_node_updates_extract(
         &mut c,
         &mut scene_buffer,
         &mut scene_loader,
+        &themes,
         #[cfg(feature = "editor")]
         &mut editor,
     );
@@ -210,6 +218,10 @@ impl Plugin for CobAssetCachePlugin
 {
     fn build(&self, app: &mut App)
     {
+        if !app.world().contains_resource::<CobCfgs>() {
+            app.init_resource::<CobCfgs>();
+        }
+
         let manifest_map = Arc::new(Mutex::new(ManifestMap::default()));
         app.insert_resource(CobAssetCache::new(manifest_map.clone()))
             .register_asset_tracker::<CobAssetCache>()
@@ -220,6 +232,7 @@ impl Plugin for CobAssetCachePlugin
                 (
                     preprocess_cobweb_asset_files,
                     process_cobweb_asset_files.run_if(|s: Res<CobAssetCache>| s.num_preprocessed_pending() > 0),
+                    refresh_themed_loadables.run_if(|t: Res<CobThemes>| t.needs_refresh()),
                     #[cfg(feature = "hot_reload")]
                     cleanup_despawned_loaded_entities,
                     apply_pending_commands,
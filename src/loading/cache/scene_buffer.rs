@@ -112,14 +112,12 @@ enum InsertNodeResult
 
 //-------------------------------------------------------------------------------------------------------------------
 
-#[cfg(feature = "hot_reload")]
 struct RevertCommand
 {
     entity: Entity,
     reverter: fn(Entity, &mut World),
 }
 
-#[cfg(feature = "hot_reload")]
 impl Command for RevertCommand
 {
     fn apply(self, world: &mut World)
@@ -233,6 +231,13 @@ pub struct SceneBuffer
     /// Records loadables that need to be reverted/updated.
     #[cfg(feature = "hot_reload")]
     refresh_ctx: RefreshCtx,
+
+    /// Tracks loadables that reference theme tokens, with their values before theme tokens were resolved.
+    themed: HashMap<SceneRef, SmallVec<[(TypeId, CobLoadable); 1]>>,
+    /// Tracks entities subscribed to scene nodes with themed loadables.
+    /// - When hot reloading is enabled, `subscriptions` is used instead.
+    #[cfg(not(feature = "hot_reload"))]
+    themed_subscriptions: HashMap<SceneRef, SmallVec<[SubscriptionRef; 1]>>,
 }
 
 impl SceneBuffer
@@ -295,6 +300,93 @@ impl SceneBuffer
         }
     }
 
+    /// Records or clears the pre-resolution value of a loadable that may reference theme tokens.
+    pub(crate) fn set_themed_loadable(&mut self, scene_ref: &SceneRef, type_id: TypeId, raw: Option<CobLoadable>)
+    {
+        match raw {
+            Some(raw) => {
+                let themed = self.themed.entry(scene_ref.clone()).or_default();
+                match themed.iter_mut().find(|(id, _)| *id == type_id) {
+                    Some((_, prev)) => *prev = raw,
+                    None => themed.push((type_id, raw)),
+                }
+            }
+            None => {
+                let Some(themed) = self.themed.get_mut(scene_ref) else { return };
+                themed.retain(|(id, _)| *id != type_id);
+                if themed.is_empty() {
+                    self.themed.remove(scene_ref);
+                }
+            }
+        }
+    }
+
+    /// Re-resolves loadables that reference theme tokens, then reverts and reloads changed loadables on
+    /// subscribed entities.
+    ///
+    /// Uses the same revert/reload procedure as hot reloading.
+    pub(crate) fn refresh_themed_loadables(
+        &mut self,
+        _c: &mut Commands,
+        _callbacks: &LoadableRegistry,
+        mut resolve: impl FnMut(&SceneRef, TypeId, &CobLoadable) -> ReflectedLoadable,
+    )
+    {
+        #[cfg(not(feature = "hot_reload"))]
+        let mut needs_updates = vec![];
+
+        for (scene_ref, themed) in self.themed.iter() {
+            let Some(node_loadables) = self.loadables.get_mut(scene_ref) else { continue };
+
+            // Update loadable values.
+            let mut changed: SmallVec<[TypeId; 4]> = SmallVec::default();
+            for (type_id, raw) in themed.iter() {
+                let Some(erased) = node_loadables.iter_mut().find(|e| e.type_id == *type_id) else { continue };
+                let loadable = (resolve)(scene_ref, *type_id, raw);
+                if erased.loadable.equals(&loadable) == Some(true) {
+                    continue;
+                }
+                erased.loadable = loadable;
+                changed.push(*type_id);
+            }
+
+            if changed.is_empty() {
+                continue;
+            }
+
+            // Queue reverts and updates for subscribed entities.
+            #[cfg(feature = "hot_reload")]
+            {
+                let Some(subscriptions) = self.subscriptions.get(scene_ref) else { continue };
+                for subscription in subscriptions {
+                    for type_id in changed.iter() {
+                        self.refresh_ctx.add_revert(*subscription, *type_id);
+                    }
+                    self.refresh_ctx
+                        .add_update(*subscription, scene_ref.clone());
+                }
+            }
+            #[cfg(not(feature = "hot_reload"))]
+            {
+                let Some(subscriptions) = self.themed_subscriptions.get_mut(scene_ref) else { continue };
+                subscriptions.retain(|s| _c.get_entity(s.entity).is_some());
+                for subscription in subscriptions.iter() {
+                    for type_id in changed.iter() {
+                        let Some(reverter) = _callbacks.get_for_revert(*type_id) else { continue };
+                        _c.queue(RevertCommand { entity: subscription.entity, reverter });
+                    }
+                    needs_updates.push((*subscription, scene_ref.clone()));
+                }
+            }
+        }
+
+        // Reload entities immediately since there is no refresh step without hot reloading.
+        #[cfg(not(feature = "hot_reload"))]
+        for (subscription, scene_ref) in needs_updates {
+            self.load_entity(subscription, scene_ref, _callbacks, _c);
+        }
+    }
+
     /// Cleans up any removed loadables if the loadable set became smaller after a hot reload.
     ///
     /// Runs after all loadables in a scene node have been inserted.
@@ -405,6 +497,15 @@ impl SceneBuffer
             self.subscriptions_rev
                 .insert(entity, (scene_ref.clone(), initializer));
         }
+        #[cfg(not(feature = "hot_reload"))]
+        {
+            if self.themed.contains_key(&scene_ref) {
+                self.themed_subscriptions
+                    .entry(scene_ref.clone())
+                    .or_default()
+                    .push(subscription);
+            }
+        }
 
         // Load the entity immediately.
         self.load_entity(subscription, scene_ref, callbacks, c);
@@ -456,6 +557,17 @@ impl SceneBuffer
             .add_update(SubscriptionRef { entity, initializer: *initializer }, scene_ref.clone());
     }
 
+    /// Gets the scene node an entity is subscribed to.
+    ///
+    /// The scene ref's file is a file path, not a manifest key.
+    #[cfg(feature = "hot_reload")]
+    pub(crate) fn get_tracked(&self, entity: Entity) -> Option<&SceneRef>
+    {
+        self.subscriptions_rev
+            .get(&entity)
+            .map(|(scene_ref, _)| scene_ref)
+    }
+
     #[cfg(feature = "hot_reload")]
     pub(super) fn apply_pending_node_updates(&mut self, c: &mut Commands, callbacks: &LoadableRegistry)
     {
@@ -483,6 +595,7 @@ impl SceneBuffer
         let Some(subscriptions) = self.subscriptions.get(&scene_ref) else { return };
 
         // Revert all loadables on the node.
+        self.themed.remove(&scene_ref);
         let mut loadables = self.loadables.remove(&scene_ref);
         for removed in loadables.as_mut().into_iter().flat_map(|l| l.drain(..)) {
             let Some(reverter) = callbacks.get_for_revert(removed.type_id) else { continue };
@@ -35,6 +35,22 @@ fn get_scene_loadable_from_layer<'a, 'b>(
 
 //-------------------------------------------------------------------------------------------------------------------
 
+fn get_scene_layer_from_layer<'a, 'b>(
+    layer: &'a mut CobSceneLayer,
+    mut path_iter: impl Iterator<Item = &'b str> + 'b,
+) -> Option<&'a mut CobSceneLayer>
+{
+    let Some(next_name) = path_iter.next() else { return Some(layer) };
+    let next_layer = layer.entries.iter_mut().find_map(|entry| {
+        let CobSceneLayerEntry::Layer(next_layer) = entry else { return None };
+        (next_layer.name.as_str() == next_name).then_some(next_layer)
+    })?;
+
+    get_scene_layer_from_layer(next_layer, path_iter)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum CobSection
 {
@@ -195,6 +211,55 @@ impl Cob
 
         None
     }
+
+    pub fn get_scene_layer_mut(&mut self, path: &ScenePath) -> Option<&mut CobSceneLayer>
+    {
+        let mut path_iter = path.iter();
+        let root_name = path_iter.next()?;
+
+        for section in self.sections.iter_mut() {
+            let CobSection::Scenes(scenes) = section else { continue };
+            let Some(root) = scenes
+                .scenes
+                .iter_mut()
+                .find(|s| s.name.as_str() == root_name)
+            else {
+                continue;
+            };
+
+            return get_scene_layer_from_layer(root, path_iter);
+        }
+
+        None
+    }
+
+    /// Gets the first `#commands` section without a condition, or inserts a new one after the manifest, import,
+    /// and defs sections.
+    pub fn unconditional_commands_mut(&mut self) -> &mut CobCommands
+    {
+        let idx = match self.sections.iter().position(|s| match s {
+            CobSection::Commands(commands) => commands.condition.is_none(),
+            _ => false,
+        }) {
+            Some(idx) => idx,
+            None => {
+                let idx = self
+                    .sections
+                    .iter()
+                    .rposition(|s| {
+                        matches!(s, CobSection::Manifest(_) | CobSection::Import(_) | CobSection::Defs(_))
+                    })
+                    .map(|i| i + 1)
+                    .unwrap_or(0);
+                self.sections
+                    .insert(idx, CobSection::Commands(CobCommands::default()));
+                idx
+            }
+        };
+
+        let CobSection::Commands(commands) = &mut self.sections[idx] else { unreachable!() };
+        commands
+    }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -186,6 +186,17 @@ impl CobLoadableVariant
             Self::Enum(variant) => variant.resolve(constants),
         }
     }
+
+    pub fn resolve_theme_tokens(&mut self, tokens: Option<&CobThemeTokens>) -> Result<bool, String>
+    {
+        match self {
+            Self::Unit => Ok(false),
+            Self::Tuple(tuple) => tuple.resolve_theme_tokens(tokens),
+            Self::Array(array) => array.resolve_theme_tokens(tokens),
+            Self::Map(map) => map.resolve_theme_tokens(tokens),
+            Self::Enum(variant) => variant.resolve_theme_tokens(tokens),
+        }
+    }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -229,6 +240,15 @@ impl CobLoadable
         self.variant.resolve(constants)
     }
 
+    /// Replaces theme tokens in the loadable with values from `tokens`. If `tokens` is `None`, then tokens are
+    /// left as-is.
+    ///
+    /// Returns `true` if the loadable contains theme tokens.
+    pub fn resolve_theme_tokens(&mut self, tokens: Option<&CobThemeTokens>) -> Result<bool, String>
+    {
+        self.variant.resolve_theme_tokens(tokens)
+    }
+
     pub fn extract<T: Serialize + 'static>(value: &T, registry: &TypeRegistry) -> CobResult<Self>
     {
         let type_info = registry
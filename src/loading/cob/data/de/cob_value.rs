@@ -44,6 +44,7 @@ impl<'de> serde::Deserializer<'de> for &'de CobValue
             CobValue::None(_) => visitor.visit_none(),
             CobValue::String(s) => visitor.visit_borrowed_str(s.as_str()),
             CobValue::Constant(_) => Err(self.invalid_type(&visitor)),
+            CobValue::ThemeToken(_) => Err(self.invalid_type(&visitor)),
             CobValue::DataMacro(_) => Err(self.invalid_type(&visitor)),
             CobValue::MacroParam(_) => Err(self.invalid_type(&visitor)),
         }
@@ -286,6 +287,7 @@ impl CobValue
             CobValue::None(_) => Unexpected::Option,
             CobValue::String(s) => Unexpected::Str(s.as_str()),
             CobValue::Constant(_) => Unexpected::Other("constant"),
+            CobValue::ThemeToken(_) => Unexpected::Other("theme token"),
             CobValue::DataMacro(_) => Unexpected::Other("data macro"),
             CobValue::MacroParam(_) => Unexpected::Other("macro param"),
         }
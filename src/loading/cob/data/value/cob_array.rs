@@ -119,6 +119,15 @@ impl CobArray
 
         Ok(())
     }
+
+    pub fn resolve_theme_tokens(&mut self, tokens: Option<&CobThemeTokens>) -> Result<bool, String>
+    {
+        let mut found = false;
+        for entry in self.entries.iter_mut() {
+            found |= entry.resolve_theme_tokens(tokens)?;
+        }
+        Ok(found)
+    }
 }
 
 impl From<Vec<CobValue>> for CobArray
@@ -104,6 +104,16 @@ impl CobEnumVariant
             Self::Map(map) => map.resolve(constants),
         }
     }
+
+    pub fn resolve_theme_tokens(&mut self, tokens: Option<&CobThemeTokens>) -> Result<bool, String>
+    {
+        match self {
+            Self::Unit => Ok(false),
+            Self::Array(arr) => arr.resolve_theme_tokens(tokens),
+            Self::Tuple(tup) => tup.resolve_theme_tokens(tokens),
+            Self::Map(map) => map.resolve_theme_tokens(tokens),
+        }
+    }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -151,6 +161,11 @@ impl CobEnum
         self.variant.resolve(constants)
     }
 
+    pub fn resolve_theme_tokens(&mut self, tokens: Option<&CobThemeTokens>) -> Result<bool, String>
+    {
+        self.variant.resolve_theme_tokens(tokens)
+    }
+
     pub fn unit(variant: &str) -> Self
     {
         Self {
@@ -93,6 +93,14 @@ impl CobMapKey
         Ok(())
     }
 
+    pub fn resolve_theme_tokens(&mut self, tokens: Option<&CobThemeTokens>) -> Result<bool, String>
+    {
+        match self {
+            Self::Value(value) => value.resolve_theme_tokens(tokens),
+            Self::FieldName { .. } => Ok(false),
+        }
+    }
+
     pub fn value(value: CobValue) -> Self
     {
         Self::Value(value)
@@ -186,6 +194,13 @@ impl CobMapKeyValue
         Ok(())
     }
 
+    pub fn resolve_theme_tokens(&mut self, tokens: Option<&CobThemeTokens>) -> Result<bool, String>
+    {
+        let found_key = self.key.resolve_theme_tokens(tokens)?;
+        let found_value = self.value.resolve_theme_tokens(tokens)?;
+        Ok(found_key || found_value)
+    }
+
     pub fn struct_field(key: &str, value: CobValue) -> Self
     {
         Self {
@@ -337,6 +352,14 @@ impl CobMapEntry
         Ok(None)
     }
 
+    pub fn resolve_theme_tokens(&mut self, tokens: Option<&CobThemeTokens>) -> Result<bool, String>
+    {
+        match self {
+            Self::KeyValue(kv) => kv.resolve_theme_tokens(tokens),
+            Self::Constant(_) | Self::MacroParam(_) => Ok(false),
+        }
+    }
+
     pub fn struct_field(key: &str, value: CobValue) -> Self
     {
         Self::KeyValue(CobMapKeyValue::struct_field(key, value))
@@ -482,6 +505,15 @@ impl CobMap
 
         Ok(())
     }
+
+    pub fn resolve_theme_tokens(&mut self, tokens: Option<&CobThemeTokens>) -> Result<bool, String>
+    {
+        let mut found = false;
+        for entry in self.entries.iter_mut() {
+            found |= entry.resolve_theme_tokens(tokens)?;
+        }
+        Ok(found)
+    }
 }
 
 impl From<Vec<CobMapEntry>> for CobMap
@@ -0,0 +1,85 @@
+use nom::character::complete::char;
+use nom::combinator::recognize;
+use nom::Parser;
+use smol_str::SmolStr;
+
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Theme token name must be `@` followed by an identifier.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CobThemeTokenName
+{
+    pub name: SmolStr,
+}
+
+impl CobThemeTokenName
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        writer.write_bytes("@".as_bytes())?;
+        writer.write_bytes(self.name.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn parse(content: Span) -> Result<(Self, Span), SpanError>
+    {
+        let (post_symbol, _) = char('@').parse(content)?;
+        recognize(anything_identifier)
+            .parse(post_symbol)
+            .map(|(r, k)| (Self { name: SmolStr::from(*k.fragment()) }, r))
+    }
+
+    pub fn as_str(&self) -> &str
+    {
+        self.name.as_str()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A reference to a theme token, e.g. `@primary_bg`.
+///
+/// Theme tokens are resolved at runtime using the active theme. See [`CobThemes`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CobThemeToken
+{
+    pub start_fill: CobFill,
+    pub name: CobThemeTokenName,
+}
+
+impl CobThemeToken
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        self.write_to_with_space(writer, "")
+    }
+
+    pub fn write_to_with_space(&self, writer: &mut impl RawSerializer, space: &str) -> Result<(), std::io::Error>
+    {
+        self.start_fill.write_to_or_else(writer, space)?;
+        self.name.write_to(writer)?;
+
+        Ok(())
+    }
+
+    pub fn try_parse(start_fill: CobFill, content: Span) -> Result<(Option<Self>, CobFill, Span), SpanError>
+    {
+        let Ok((name, remaining)) = rc(content, |c| CobThemeTokenName::parse(c)) else {
+            return Ok((None, start_fill, content));
+        };
+        let (end_fill, remaining) = CobFill::parse(remaining);
+
+        let token = Self { start_fill, name };
+        Ok((Some(token), end_fill, remaining))
+    }
+
+    pub fn recover_fill(&mut self, other: &Self)
+    {
+        self.start_fill.recover(&other.start_fill);
+        // Name doesn't have fill
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
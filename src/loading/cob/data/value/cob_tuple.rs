@@ -120,6 +120,15 @@ impl CobTuple
         Ok(())
     }
 
+    pub fn resolve_theme_tokens(&mut self, tokens: Option<&CobThemeTokens>) -> Result<bool, String>
+    {
+        let mut found = false;
+        for entry in self.entries.iter_mut() {
+            found |= entry.resolve_theme_tokens(tokens)?;
+        }
+        Ok(found)
+    }
+
     pub fn single(value: CobValue) -> Self
     {
         Self {
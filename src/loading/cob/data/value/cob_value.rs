@@ -20,6 +20,8 @@ pub enum CobValue
     None(CobNone),
     String(CobString),
     Constant(CobConstant),
+    /// Resolved at runtime from the active theme.
+    ThemeToken(CobThemeToken),
     DataMacro(CobDataMacroCall),
     /// Only valid inside a macro definition.
     MacroParam(CobMacroParam),
@@ -65,6 +67,9 @@ impl CobValue
             Self::Constant(val) => {
                 val.write_to_with_space(writer, space)?;
             }
+            Self::ThemeToken(val) => {
+                val.write_to_with_space(writer, space)?;
+            }
             Self::DataMacro(val) => {
                 val.write_to_with_space(writer, space)?;
             }
@@ -117,6 +122,10 @@ impl CobValue
             (Some(value), fill, remaining) => return Ok((Some(Self::Constant(value)), fill, remaining)),
             (None, fill, _) => fill,
         };
+        let fill = match rc(content, move |c| CobThemeToken::try_parse(fill, c))? {
+            (Some(value), fill, remaining) => return Ok((Some(Self::ThemeToken(value)), fill, remaining)),
+            (None, fill, _) => fill,
+        };
         let fill = match rc(content, move |c| CobDataMacroCall::try_parse(fill, c))? {
             (Some(value), fill, remaining) => return Ok((Some(Self::DataMacro(value)), fill, remaining)),
             (None, fill, _) => fill,
@@ -162,6 +171,9 @@ impl CobValue
             (Self::Constant(val), Self::Constant(other_val)) => {
                 val.recover_fill(other_val);
             }
+            (Self::ThemeToken(val), Self::ThemeToken(other_val)) => {
+                val.recover_fill(other_val);
+            }
             (Self::DataMacro(val), Self::DataMacro(other_val)) => {
                 val.recover_fill(other_val);
             }
@@ -206,6 +218,25 @@ impl CobValue
         Ok(None)
     }
 
+    pub fn resolve_theme_tokens(&mut self, tokens: Option<&CobThemeTokens>) -> Result<bool, String>
+    {
+        match self {
+            Self::Enum(val) => val.resolve_theme_tokens(tokens),
+            Self::Array(val) => val.resolve_theme_tokens(tokens),
+            Self::Tuple(val) => val.resolve_theme_tokens(tokens),
+            Self::Map(val) => val.resolve_theme_tokens(tokens),
+            Self::ThemeToken(token) => {
+                let Some(tokens) = tokens else { return Ok(true) };
+                let Some(value) = tokens.get(token.name.as_str()) else {
+                    return Err(format!("theme token lookup failed for @{}", token.name.as_str()));
+                };
+                *self = value.clone();
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
     pub fn extract<T: ?Sized + Serialize>(value: &T) -> CobResult<Self>
     {
         value.serialize(CobValueSerializer)
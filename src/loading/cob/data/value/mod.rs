@@ -6,6 +6,7 @@ mod cob_map;
 mod cob_none;
 mod cob_number;
 mod cob_string;
+mod cob_theme_token;
 mod cob_tuple;
 mod cob_value;
 
@@ -17,5 +18,6 @@ pub use cob_map::*;
 pub use cob_none::*;
 pub use cob_number::*;
 pub use cob_string::*;
+pub use cob_theme_token::*;
 pub use cob_tuple::*;
 pub use cob_value::*;
@@ -67,6 +67,10 @@ impl CobCommandEntry
 pub struct CobCommands
 {
     pub start_fill: CobFill,
+    /// Optional condition map written directly after the section tag, e.g. `#commands{platform:"wasm"}`.
+    ///
+    /// The section's commands are only extracted if the condition is satisfied.
+    pub condition: Option<CobMap>,
     pub entries: Vec<CobCommandEntry>,
 }
 
@@ -77,6 +81,9 @@ impl CobCommands
         let space = if first_section { "" } else { "\n\n" };
         self.start_fill.write_to_or_else(writer, space)?;
         writer.write_bytes("#commands".as_bytes())?;
+        if let Some(condition) = &self.condition {
+            condition.write_to(writer)?;
+        }
         for entry in self.entries.iter() {
             entry.write_to(writer)?;
         }
@@ -95,7 +102,15 @@ impl CobCommands
             return Err(span_verify_error(content));
         }
 
-        let (mut item_fill, mut remaining) = CobFill::parse(remaining);
+        // Condition map must be attached directly to the section tag.
+        let (condition, mut item_fill, mut remaining) =
+            match rc(remaining, move |rm| CobMap::try_parse(CobFill::default(), rm))? {
+                (Some(condition), item_fill, remaining) => (Some(condition), item_fill, remaining),
+                (None, _, _) => {
+                    let (item_fill, remaining) = CobFill::parse(remaining);
+                    (None, item_fill, remaining)
+                }
+            };
         let mut entries = vec![];
 
         let end_fill = loop {
@@ -112,7 +127,7 @@ impl CobCommands
             }
         };
 
-        let command = Self { start_fill, entries };
+        let command = Self { start_fill, condition, entries };
         Ok((Some(command), end_fill, remaining))
     }
 }
@@ -121,7 +136,7 @@ impl Default for CobCommands
 {
     fn default() -> Self
     {
-        Self { start_fill: CobFill::default(), entries: Vec::default() }
+        Self { start_fill: CobFill::default(), condition: None, entries: Vec::default() }
     }
 }
 
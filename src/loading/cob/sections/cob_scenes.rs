@@ -231,6 +231,60 @@ impl CobSceneLayer
         Ok((Some(Self { name_fill, name, entries }), end_fill, remaining))
     }
 
+    /// Number of spaces before the layer's name.
+    pub fn indent(&self) -> usize
+    {
+        self.name_fill.ends_newline_then_num_spaces().unwrap_or(0)
+    }
+
+    /// Number of spaces before the layer's entries.
+    ///
+    /// Uses the indent of existing entries if possible, otherwise this layer's indent plus four.
+    pub fn entry_indent(&self) -> usize
+    {
+        self.entries
+            .iter()
+            .find_map(|e| match e {
+                CobSceneLayerEntry::Loadable(loadable) => loadable.fill.ends_newline_then_num_spaces(),
+                CobSceneLayerEntry::Layer(layer) => layer.name_fill.ends_newline_then_num_spaces(),
+                _ => None,
+            })
+            .unwrap_or(self.indent() + 4)
+    }
+
+    /// Makes a new empty layer with fill for the given indent.
+    pub fn new_empty(name: impl Into<SmolStr>, indent: usize) -> Self
+    {
+        Self {
+            name_fill: CobFill::new(format!("\n\n{}", " ".repeat(indent))),
+            name: CobSceneNodeName(name.into()),
+            entries: vec![],
+        }
+    }
+
+    /// Finds the entry index of the child layer with the given name.
+    pub fn find_layer(&self, name: &str) -> Option<usize>
+    {
+        self.entries.iter().position(|e| match e {
+            CobSceneLayerEntry::Layer(layer) => layer.name.as_str() == name,
+            _ => false,
+        })
+    }
+
+    /// Finds the entry index of the loadable with the given canonical name (see
+    /// [`CobLoadableIdentifier::to_canonical`]).
+    pub fn find_loadable(&self, name: &str) -> Option<usize>
+    {
+        let mut id_scratch = String::default();
+        self.entries.iter().position(|e| match e {
+            CobSceneLayerEntry::Loadable(loadable) => {
+                id_scratch = loadable.id.to_canonical(Some(std::mem::take(&mut id_scratch)));
+                id_scratch == name
+            }
+            _ => false,
+        })
+    }
+
     pub fn recover_fill(&mut self, other: &Self)
     {
         self.name_fill.recover(&other.name_fill);
@@ -0,0 +1,200 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use bevy::prelude::*;
+use fluent_langneg::negotiate::filter_matches;
+use fluent_langneg::{LanguageIdentifier, NegotiationStrategy};
+use smol_str::SmolStr;
+
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the platform name matched by `platform` conditions.
+fn current_platform() -> &'static str
+{
+    if cfg!(target_family = "wasm") {
+        "wasm"
+    } else {
+        std::env::consts::OS
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the build profile name matched by `build` conditions.
+fn current_build() -> &'static str
+{
+    if cfg!(debug_assertions) {
+        "debug"
+    } else {
+        "release"
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Extracts the options of a condition entry. Options can be a single string or an array of strings.
+fn condition_options(value: &CobValue) -> Option<Vec<&str>>
+{
+    match value {
+        CobValue::String(string) => Some(vec![string.as_str()]),
+        CobValue::Array(array) => array
+            .entries
+            .iter()
+            .map(|entry| match entry {
+                CobValue::String(string) => Some(string.as_str()),
+                _ => None,
+            })
+            .collect(),
+        _ => None,
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Context for evaluating `#commands` section conditions.
+pub(crate) struct CommandConditionContext<'a>
+{
+    pub(crate) cfgs: &'a CobCfgs,
+    pub(crate) locale: Option<&'a Locale>,
+}
+
+impl CommandConditionContext<'_>
+{
+    /// Returns `true` if all entries in the condition map are satisfied.
+    pub(crate) fn evaluate(&self, file: &CobFile, condition: &CobMap) -> bool
+    {
+        condition
+            .entries
+            .iter()
+            .all(|entry| self.evaluate_entry(file, entry))
+    }
+
+    fn evaluate_entry(&self, file: &CobFile, entry: &CobMapEntry) -> bool
+    {
+        let CobMapEntry::KeyValue(CobMapKeyValue { key: CobMapKey::FieldName { name, .. }, value, .. }) = entry
+        else {
+            tracing::warn!("failed evaluating #commands condition in {:?}; condition entries must be \
+                key:value pairs with field-name keys", file);
+            return false;
+        };
+
+        if name == "not" {
+            let CobValue::Map(inner) = value else {
+                tracing::warn!("failed evaluating #commands condition in {:?}; 'not' requires a map", file);
+                return false;
+            };
+            return !self.evaluate(file, inner);
+        }
+
+        let Some(options) = condition_options(value) else {
+            tracing::warn!("failed evaluating #commands condition {:?} in {:?}; expected a string or array of \
+                strings", name.as_str(), file);
+            return false;
+        };
+
+        match name.as_str() {
+            "cfg" => options.iter().any(|option| self.cfgs.contains(option)),
+            "platform" => options.iter().any(|option| *option == current_platform()),
+            "build" => options.iter().any(|option| *option == current_build()),
+            "locale" => options.iter().any(|option| self.matches_locale(file, option)),
+            _ => {
+                tracing::warn!("failed evaluating #commands condition in {:?}; unknown condition {:?} (expected \
+                    cfg, platform, build, locale, or not)", file, name.as_str());
+                false
+            }
+        }
+    }
+
+    /// Only the most-preferred requested language is checked. A condition like `"en"` matches `en-US`.
+    fn matches_locale(&self, file: &CobFile, option: &str) -> bool
+    {
+        let Ok(lang_id) = LanguageIdentifier::from_str(option) else {
+            tracing::warn!("failed evaluating #commands locale condition {:?} in {:?}; invalid language id",
+                option, file);
+            return false;
+        };
+        let Some(requested) = self.locale.and_then(|l| l.requested.first()) else { return false };
+
+        !filter_matches(&[requested], &[lang_id], NegotiationStrategy::Filtering).is_empty()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource that stores the COB cfgs used to evaluate `cfg` conditions in `#commands` sections.
+///
+/// Built-in cfgs:
+/// - `"hot_reload"` if the `hot_reload` feature is enabled.
+/// - `"editor"` if the `editor` feature is enabled.
+///
+/// Add your own cfgs with [`CobCfgAppExt::enable_cob_cfg`].
+#[derive(Resource, Debug, Clone)]
+pub struct CobCfgs
+{
+    cfgs: HashSet<SmolStr>,
+}
+
+impl CobCfgs
+{
+    /// Adds a cfg.
+    pub fn insert(&mut self, cfg: impl AsRef<str>)
+    {
+        self.cfgs.insert(SmolStr::from(cfg.as_ref()));
+    }
+
+    /// Removes a cfg.
+    pub fn remove(&mut self, cfg: impl AsRef<str>)
+    {
+        self.cfgs.remove(cfg.as_ref());
+    }
+
+    /// Checks if a cfg is enabled.
+    pub fn contains(&self, cfg: impl AsRef<str>) -> bool
+    {
+        self.cfgs.contains(cfg.as_ref())
+    }
+}
+
+impl Default for CobCfgs
+{
+    fn default() -> Self
+    {
+        let mut cfgs = HashSet::default();
+        if cfg!(feature = "hot_reload") {
+            cfgs.insert(SmolStr::from("hot_reload"));
+        }
+        if cfg!(feature = "editor") {
+            cfgs.insert(SmolStr::from("editor"));
+        }
+        Self { cfgs }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Extends `App` with methods for controlling `#commands` section conditions.
+pub trait CobCfgAppExt
+{
+    /// Enables a cfg that can be matched by `#commands{cfg:"name"}` sections.
+    ///
+    /// Cfgs should be enabled before COB files are loaded. You can forward cargo features with
+    /// `#[cfg(feature = "my_feature")] app.enable_cob_cfg("my_feature");`.
+    fn enable_cob_cfg(&mut self, cfg: impl AsRef<str>) -> &mut Self;
+}
+
+impl CobCfgAppExt for App
+{
+    fn enable_cob_cfg(&mut self, cfg: impl AsRef<str>) -> &mut Self
+    {
+        if !self.world().contains_resource::<CobCfgs>() {
+            self.init_resource::<CobCfgs>();
+        }
+
+        self.world_mut().resource_mut::<CobCfgs>().insert(cfg);
+        self
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -102,6 +102,7 @@ pub(crate) fn extract_cob_commands(
     data: &mut Cob,
     loadables: &LoadableRegistry,
     constants_buffer: &ConstantsBuffer,
+    conditions: &CommandConditionContext,
     // tracks specs
     _specs: &SpecsMap,
 )
@@ -117,6 +118,7 @@ pub(crate) fn extract_cob_commands(
                 section,
                 loadables,
                 constants_buffer,
+                conditions,
             ),
             _ => (),
         }
@@ -137,6 +139,7 @@ pub(crate) fn extract_cob_scenes(
     mut data: Cob,
     loadables: &LoadableRegistry,
     constants_buffer: &ConstantsBuffer,
+    theme_tokens: Option<&CobThemeTokens>,
     // tracks specs
     _specs: &SpecsMap,
 )
@@ -152,6 +155,7 @@ pub(crate) fn extract_cob_scenes(
                 section,
                 loadables,
                 constants_buffer,
+                theme_tokens,
             ),
             _ => (),
         }
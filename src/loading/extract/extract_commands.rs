@@ -12,12 +12,26 @@ pub(super) fn extract_commands_section(
     section: &mut CobCommands,
     loadables: &LoadableRegistry,
     constants_buffer: &ConstantsBuffer,
+    conditions: &CommandConditionContext,
 )
 {
     if section.entries.is_empty() {
         return;
     }
 
+    // Check the section condition.
+    if let Some(condition) = &mut section.condition {
+        if let Err(err) = condition.resolve(constants_buffer) {
+            tracing::warn!("ignoring #commands section in {:?}; error resolving defs in condition: {:?}",
+                file, err.as_str());
+            return;
+        }
+        if !conditions.evaluate(file, condition) {
+            tracing::debug!("skipping #commands section in {:?}; condition not satisfied", file);
+            return;
+        }
+    }
+
     let mock_path = ScenePath::new("#commands");
     let mut shortname = String::default();
     let mut seen_shortnames = vec![];
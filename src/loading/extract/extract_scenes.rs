@@ -16,6 +16,7 @@ fn handle_loadable(
     loadable: &mut CobLoadable,
     loadables: &LoadableRegistry,
     constants_buffer: &ConstantsBuffer,
+    theme_tokens: Option<&CobThemeTokens>,
 ) -> String
 {
     // Get the loadable's longname.
@@ -40,18 +41,27 @@ fn handle_loadable(
         return id_scratch;
     }
 
+    let scene_ref = SceneRef { file: SceneFile::File(file.clone()), path: current_path.clone() };
+
     // Get the loadable's value.
-    let loadable_value = get_loadable_value(deserializer, loadable);
+    // - Loadables with theme tokens are cached so they can be re-resolved when the theme changes.
+    let loadable_value = match loadable.resolve_theme_tokens(None) {
+        Ok(true) => {
+            scene_buffer.set_themed_loadable(&scene_ref, type_id, Some(loadable.clone()));
+            resolve_themed_loadable(type_registry, &scene_ref, type_id, loadable, theme_tokens)
+        }
+        _ => {
+            scene_buffer.set_themed_loadable(&scene_ref, type_id, None);
+            get_loadable_value(deserializer, loadable)
+        }
+    };
 
     // Save this loadable.
     let loadable_index = seen_shortnames.len();
     seen_shortnames.push(short_name);
 
     scene_buffer.insert_loadable(
-        &SceneRef {
-            file: SceneFile::File(file.clone()),
-            path: current_path.clone(),
-        },
+        &scene_ref,
         Some(loadable_index),
         loadable_value,
         type_id,
@@ -76,6 +86,7 @@ fn handle_scene_node(
     cob_layer: &mut CobSceneLayer,
     loadables: &LoadableRegistry,
     constants_buffer: &ConstantsBuffer,
+    theme_tokens: Option<&CobThemeTokens>,
     anonymous_count: &mut usize,
 ) -> String
 {
@@ -127,6 +138,7 @@ fn handle_scene_node(
         cob_layer,
         loadables,
         constants_buffer,
+        theme_tokens,
     )
 }
 
@@ -145,6 +157,7 @@ fn extract_scene_layer(
     cob_layer: &mut CobSceneLayer,
     loadables: &LoadableRegistry,
     constants_buffer: &ConstantsBuffer,
+    theme_tokens: Option<&CobThemeTokens>,
 ) -> String
 {
     // Prep the node.
@@ -173,6 +186,7 @@ fn extract_scene_layer(
                     loadable,
                     loadables,
                     constants_buffer,
+                    theme_tokens,
                 );
             }
             // Do this one after we are done using the `seen_shortnames` buffer.
@@ -213,6 +227,7 @@ fn extract_scene_layer(
                     next_cob_layer,
                     loadables,
                     constants_buffer,
+                    theme_tokens,
                     &mut anonymous_count,
                 );
             }
@@ -248,6 +263,7 @@ pub(super) fn extract_scenes(
     section: &mut CobScenes,
     loadables: &LoadableRegistry,
     constants_buffer: &ConstantsBuffer,
+    theme_tokens: Option<&CobThemeTokens>,
 )
 {
     let mut scene_registry = scene_loader.take_scene_registry();
@@ -278,6 +294,7 @@ pub(super) fn extract_scenes(
             cob_layer,
             loadables,
             constants_buffer,
+            theme_tokens,
         );
     }
 
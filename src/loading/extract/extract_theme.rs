@@ -0,0 +1,62 @@
+use std::any::TypeId;
+use std::sync::Arc;
+
+use bevy::reflect::serde::TypedReflectDeserializer;
+use bevy::reflect::TypeRegistry;
+
+use super::*;
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Extracts theme tokens from a theme file's own `#defs` constants.
+///
+/// Only constants that are plain values can be used as theme tokens. Imported constants are ignored.
+pub(crate) fn extract_theme_tokens(file: &CobFile, constants_buffer: &ConstantsBuffer) -> CobThemeTokens
+{
+    let mut tokens = CobThemeTokens::default();
+
+    for (name, value) in constants_buffer.iter_file_constants() {
+        match value {
+            CobConstantValue::Value(value) => tokens.insert(name.clone(), value.clone()),
+            CobConstantValue::ValueGroup(_) => {
+                tracing::warn!("ignoring constant ${} in theme file {:?}; value groups can't be used as theme \
+                    tokens", name.as_str(), file);
+            }
+        }
+    }
+
+    tokens
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resolves theme tokens in a themed loadable and deserializes it.
+///
+/// If a token can't be resolved (e.g. there is no active theme or the theme doesn't define it), then the loadable
+/// is marked as failed so it won't be applied.
+pub(crate) fn resolve_themed_loadable(
+    type_registry: &TypeRegistry,
+    scene_ref: &SceneRef,
+    type_id: TypeId,
+    raw: &CobLoadable,
+    tokens: Option<&CobThemeTokens>,
+) -> ReflectedLoadable
+{
+    let mut loadable = raw.clone();
+    if let Err(err) = loadable.resolve_theme_tokens(tokens) {
+        tracing::warn!("failed resolving theme tokens for loadable {:?} at {:?}; {:?}",
+            raw.id.to_canonical(None), scene_ref, err.as_str());
+        return ReflectedLoadable::DeserializationFailed(Arc::new(CobError::Message(err)));
+    }
+
+    let Some(registration) = type_registry.get(type_id) else {
+        tracing::error!("failed getting type registration for themed loadable {:?} at {:?} (this is a bug)",
+            raw.id.to_canonical(None), scene_ref);
+        return ReflectedLoadable::DeserializationFailed(Arc::new(CobError::LoadableNotRegistered));
+    };
+
+    get_loadable_value(TypedReflectDeserializer::new(registration, type_registry), &loadable)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
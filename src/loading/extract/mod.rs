@@ -4,6 +4,7 @@ mod extract_defs;
 mod extract_import;
 mod extract_manifest;
 mod extract_scenes;
+mod extract_theme;
 mod reflected_loadable;
 mod utils;
 
@@ -13,5 +14,6 @@ pub(self) use extract_defs::*;
 pub(self) use extract_import::*;
 pub(self) use extract_manifest::*;
 pub(self) use extract_scenes::*;
+pub(crate) use extract_theme::*;
 pub(crate) use reflected_loadable::*;
 pub(self) use utils::*;
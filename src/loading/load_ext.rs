@@ -268,6 +268,12 @@ impl LoadableRegistry
     {
         self.loadables.get(id.as_ref()).copied()
     }
+
+    /// Returns `true` if the loadable was registered as a command.
+    pub(crate) fn is_command(&self, type_id: TypeId) -> bool
+    {
+        self.command_callbacks.contains_key(&type_id)
+    }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -7,6 +7,7 @@ mod app_load_ext;
 mod cache;
 pub mod cob;
 mod cob_asset_loader;
+mod command_conditions;
 mod extract;
 mod load_ext;
 mod load_progress;
@@ -14,12 +15,14 @@ mod loadable;
 mod plugin;
 mod references;
 mod scene;
+mod theme;
 
 pub use app_load_ext::*;
 pub use cache::*;
 pub use cob::Cob;
 pub(crate) use cob::*;
 pub(crate) use cob_asset_loader::*;
+pub use command_conditions::*;
 pub(crate) use extract::*;
 pub use load_ext::*;
 pub use load_progress::*;
@@ -27,3 +30,4 @@ pub use loadable::*;
 pub(crate) use plugin::*;
 pub use references::*;
 pub use scene::*;
+pub use theme::*;
@@ -14,6 +14,7 @@ impl Plugin for LoadingPlugin
             .add_plugins(LoadExtPlugin)
             .add_plugins(CobAssetLoaderPlugin)
             .add_plugins(AppLoadExtPlugin)
+            .add_plugins(ThemePlugin)
             .add_plugins(CobAssetCachePlugin)
             .add_plugins(SceneLoaderPlugin) // Must be after the COB cache plugin.
             ;
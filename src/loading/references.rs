@@ -207,6 +207,15 @@ impl ScenePath
         Self { path: Arc::from(path.as_slice()) }
     }
 
+    /// Gets the path without its last segment.
+    ///
+    /// Returns `None` if the path is empty.
+    pub fn parent(&self) -> Option<Self>
+    {
+        let (_, parent) = self.path.split_last()?;
+        Some(Self { path: Arc::from(parent) })
+    }
+
     /// Gets the number of path segments.
     pub fn len(&self) -> usize
     {
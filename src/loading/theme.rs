@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+
+use bevy::ecs::world::Command;
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn set_theme(In(theme): In<String>, mut c: Commands, mut themes: ResMut<CobThemes>)
+{
+    if !themes.set_active(theme.as_str()) {
+        return;
+    }
+
+    c.react().broadcast(ThemeChanged);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Re-resolves themed loadables after the active theme changed or the active theme's tokens were updated.
+pub(crate) fn refresh_themed_loadables(
+    mut c: Commands,
+    types: Res<AppTypeRegistry>,
+    loadables: Res<LoadableRegistry>,
+    mut themes: ResMut<CobThemes>,
+    mut scene_buffer: ResMut<SceneBuffer>,
+)
+{
+    themes.needs_refresh = false;
+
+    let type_registry = types.read();
+    let tokens = themes.active_tokens();
+    scene_buffer.refresh_themed_loadables(&mut c, &loadables, |scene_ref, type_id, raw| {
+        resolve_themed_loadable(&type_registry, scene_ref, type_id, raw, tokens)
+    });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Token values for a theme.
+///
+/// Tokens are extracted from the `#defs` section of a theme file. A constant `$primary_bg` in the theme file can
+/// be referenced as `@primary_bg` in scene nodes.
+#[derive(Debug, Default, Clone)]
+pub struct CobThemeTokens
+{
+    tokens: HashMap<SmolStr, CobValue>,
+}
+
+impl CobThemeTokens
+{
+    /// Gets the value of a token.
+    pub fn get(&self, token: impl AsRef<str>) -> Option<&CobValue>
+    {
+        self.tokens.get(token.as_ref())
+    }
+
+    /// Iterates all tokens.
+    pub fn iter(&self) -> impl Iterator<Item = (&SmolStr, &CobValue)> + '_
+    {
+        self.tokens.iter()
+    }
+
+    pub(crate) fn insert(&mut self, token: SmolStr, value: CobValue)
+    {
+        self.tokens.insert(token, value);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource that tracks registered themes and the active theme.
+///
+/// Scene nodes can reference theme tokens with `@token_name`. Token references are resolved using the active
+/// theme when scenes are extracted, and all loadables with theme tokens are re-applied to live entities when the
+/// active theme changes.
+///
+/// Themes are registered with [`CobThemeAppExt::register_theme`]. Use the [`SetTheme`] command to change the
+/// active theme.
+#[derive(Resource, Debug, Default)]
+pub struct CobThemes
+{
+    /// [ theme file : theme name ]
+    files: HashMap<CobFile, SmolStr>,
+    /// [ theme name : tokens ]
+    themes: HashMap<SmolStr, CobThemeTokens>,
+    active: Option<SmolStr>,
+    needs_refresh: bool,
+}
+
+impl CobThemes
+{
+    /// Gets the name of the active theme.
+    pub fn active(&self) -> Option<&str>
+    {
+        self.active.as_ref().map(|a| a.as_str())
+    }
+
+    /// Gets the tokens of a theme.
+    ///
+    /// Returns `None` if the theme's file has not been loaded yet.
+    pub fn get(&self, theme: impl AsRef<str>) -> Option<&CobThemeTokens>
+    {
+        self.themes.get(theme.as_ref())
+    }
+
+    pub(crate) fn active_tokens(&self) -> Option<&CobThemeTokens>
+    {
+        self.themes.get(self.active.as_ref()?)
+    }
+
+    pub(crate) fn needs_refresh(&self) -> bool
+    {
+        self.needs_refresh
+    }
+
+    fn register(&mut self, theme: &str, file: CobFile)
+    {
+        let theme = SmolStr::from(theme);
+        if self.active.is_none() {
+            self.active = Some(theme.clone());
+        }
+        if let Some(prev) = self.files.insert(file.clone(), theme.clone()) {
+            tracing::warn!("overwriting theme {:?} with {:?} for theme file {:?}", prev.as_str(), theme.as_str(),
+                file.as_str());
+        }
+    }
+
+    /// Returns `false` if the theme is unknown or already active.
+    fn set_active(&mut self, theme: &str) -> bool
+    {
+        if !self.files.values().any(|t| t.as_str() == theme) {
+            tracing::warn!("failed setting theme {:?}; the theme is not registered", theme);
+            return false;
+        }
+        if self.active() == Some(theme) {
+            return false;
+        }
+
+        self.active = Some(SmolStr::from(theme));
+        self.needs_refresh = true;
+        true
+    }
+
+    /// Saves tokens if `file` is a theme file.
+    pub(crate) fn try_extract_tokens(&mut self, file: &CobFile, constants_buffer: &ConstantsBuffer)
+    {
+        let Some(theme) = self.files.get(file) else { return };
+
+        tracing::info!("extracting tokens for theme {:?} from {:?}", theme.as_str(), file.as_str());
+        let tokens = extract_theme_tokens(file, constants_buffer);
+        if self.active.as_ref() == Some(theme) {
+            self.needs_refresh = true;
+        }
+        self.themes.insert(theme.clone(), tokens);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Loadable command for changing the active theme.
+///
+/// Loadables that reference theme tokens will be re-applied to all entities that use them. Reactive event
+/// [`ThemeChanged`] is broadcast if the active theme changed.
+#[derive(Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SetTheme(pub String);
+
+impl Command for SetTheme
+{
+    fn apply(self, world: &mut World)
+    {
+        world.syscall(self.0, set_theme);
+        if world.resource::<CobThemes>().needs_refresh() {
+            world.syscall((), refresh_themed_loadables);
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Reactive event broadcast when the active theme changes.
+pub struct ThemeChanged;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Extends `App` with methods for registering themes.
+pub trait CobThemeAppExt
+{
+    /// Registers a COB file as the source of a theme's tokens.
+    ///
+    /// Constants defined in the file's `#defs` sections become theme tokens. The file must be loaded with
+    /// [`LoadedCobAssetFilesAppExt::load`] or a `#manifest` entry.
+    ///
+    /// The first registered theme is active by default.
+    fn register_theme(&mut self, theme: impl AsRef<str>, file: impl AsRef<str>) -> &mut Self;
+}
+
+impl CobThemeAppExt for App
+{
+    fn register_theme(&mut self, theme: impl AsRef<str>, file: impl AsRef<str>) -> &mut Self
+    {
+        let Some(file) = CobFile::try_new(file.as_ref()) else {
+            tracing::warn!("failed registering theme {:?}; file {:?} does not have '.cob' extension",
+                theme.as_ref(), file.as_ref());
+            return self;
+        };
+
+        if !self.world().contains_resource::<CobThemes>() {
+            self.init_resource::<CobThemes>();
+        }
+
+        self.world_mut()
+            .resource_mut::<CobThemes>()
+            .register(theme.as_ref(), file);
+        self
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) struct ThemePlugin;
+
+impl Plugin for ThemePlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        if !app.world().contains_resource::<CobThemes>() {
+            app.init_resource::<CobThemes>();
+        }
+
+        app.register_command_type::<SetTheme>();
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
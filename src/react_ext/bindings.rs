@@ -0,0 +1,605 @@
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use bevy::ecs::component::ComponentId;
+use bevy::ecs::world::DeferredWorld;
+use bevy::prelude::*;
+use bevy::reflect::{GetPath, GetTypeRegistration};
+use bevy_cobweb::prelude::*;
+use smol_str::SmolStr;
+
+use crate::prelude::*;
+use crate::sickle::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn find_resource_source(_: &World, _: Entity) -> Option<Option<Entity>>
+{
+    Some(None)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn find_component_source<C: ReactComponent>(world: &World, entity: Entity) -> Option<Option<Entity>>
+{
+    let mut current = entity;
+    loop {
+        if world.get::<React<C>>(current).is_some() {
+            return Some(Some(current));
+        }
+        current = world.get::<Parent>(current)?.get();
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn read_resource_source<R: ReactResource + Reflect>(world: &World, _: Option<Entity>) -> Option<&dyn Reflect>
+{
+    world
+        .get_react_resource::<R>()
+        .map(|r| r as &dyn Reflect)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn read_component_source<C: ReactComponent + Reflect>(world: &World, source: Option<Entity>) -> Option<&dyn Reflect>
+{
+    world
+        .get::<React<C>>(source?)
+        .map(|c| c.get() as &dyn Reflect)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn register_resource_reactor<R: ReactResource>(
+    c: &mut Commands,
+    _: Option<Entity>,
+    syscommand: SystemCommand,
+) -> Option<RevokeToken>
+{
+    c.react()
+        .with(resource_mutation::<R>(), syscommand, ReactorMode::Revokable)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn register_component_reactor<C: ReactComponent>(
+    c: &mut Commands,
+    source: Option<Entity>,
+    syscommand: SystemCommand,
+) -> Option<RevokeToken>
+{
+    let source = source?;
+    c.react().with(
+        (entity_insertion::<C>(source), entity_mutation::<C>(source)),
+        syscommand,
+        ReactorMode::Revokable,
+    )
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resolves bindings to `C` on the entity and its descendants again when `React<C>` is inserted, in case a closer
+/// source appeared.
+fn retry_component_bindings<C: ReactComponent>(event: InsertionEvent<C>, mut c: Commands)
+{
+    let Ok(entity) = event.get() else { return };
+    c.queue(move |world: &mut World| {
+        retry_bindings(world, entity, TypeId::of::<C>());
+    });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Converts a reflected value to a string for display in text.
+fn reflect_to_string(value: &dyn PartialReflect) -> String
+{
+    macro_rules! try_display {
+        ($($t:ty),*) => {
+            $(
+                if let Some(value) = value.try_downcast_ref::<$t>() {
+                    return value.to_string();
+                }
+            )*
+        };
+    }
+
+    try_display!(
+        String, SmolStr, bool, char, f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+    );
+    format!("{value:?}")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Revokes a binding's reactor and despawns its update system.
+fn cleanup_binding(world: &mut World, binding: Binding)
+{
+    if let Some(token) = binding.reactor {
+        world.react(|rc| rc.revoke(token));
+    }
+    if let Ok(emut) = world.get_entity_mut(*binding.update) {
+        emut.despawn();
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Cleans up all bindings on an entity when [`Bindings`] is removed (e.g. when the entity is despawned).
+fn cleanup_bindings_on_remove(mut world: DeferredWorld, entity: Entity, _: ComponentId)
+{
+    let Some(mut bindings) = world.get_mut::<Bindings>(entity) else { return };
+    let bindings = std::mem::take(&mut bindings.bindings);
+    if bindings.is_empty() {
+        return;
+    }
+    world.commands().queue(move |world: &mut World| {
+        for binding in bindings.into_values() {
+            cleanup_binding(world, binding);
+        }
+    });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Sets up a reactor that calls `update` on `entity` whenever the value at `source` changes.
+///
+/// Replaces the previous binding with the same `binding_id` on the entity.
+fn bind_entity(
+    world: &mut World,
+    entity: Entity,
+    binding_id: TypeId,
+    source: &str,
+    update: impl Fn(&mut World, Entity, &dyn PartialReflect) + Send + Sync + 'static,
+)
+{
+    // Clean up the previous binding.
+    unbind_entity(world, entity, binding_id);
+    if !world.entities().contains(entity) {
+        return;
+    }
+
+    // Look up the source.
+    let (type_name, path) = source.split_once('.').unwrap_or((source, ""));
+    let Some(bind_source) = world
+        .resource::<BindSourceRegistry>()
+        .sources
+        .get(type_name)
+        .copied()
+    else {
+        tracing::warn!("failed binding {entity:?} to {source:?}, {type_name:?} was not registered with \
+            register_bind_resource or register_bind_component");
+        return;
+    };
+
+    // Prepare the update system.
+    // - The source entity is read from the binding since it can change when the source is resolved again.
+    let source = String::from(source);
+    let path = String::from(path);
+    let callback = move |world: &mut World| {
+        let Some(binding) = world
+            .get::<Bindings>(entity)
+            .and_then(|b| b.bindings.get(&binding_id))
+        else {
+            return;
+        };
+        let Some(value) = (bind_source.read)(world, binding.source) else { return };
+        let value = match path.is_empty() {
+            true => value.as_partial_reflect(),
+            false => match value.reflect_path(path.as_str()) {
+                Ok(value) => value,
+                Err(err) => {
+                    tracing::warn!("failed updating binding on {entity:?}, source {source:?} is invalid: {err:?}");
+                    return;
+                }
+            },
+        };
+        let value = value.clone_value();
+        (update)(world, entity, value.as_ref());
+    };
+
+    let apply: Arc<dyn Fn(&mut World) + Send + Sync> = Arc::new(callback);
+    let apply_clone = apply.clone();
+    let update = world
+        .commands()
+        .spawn_system_command(move |world: &mut World| (apply_clone)(world));
+    world.flush();
+
+    // Save the binding so it can be reverted.
+    let binding = Binding { bind_source, source: None, apply, update, reactor: None };
+    let Ok(mut emut) = world.get_entity_mut(entity) else { return };
+    match emut.get_mut::<Bindings>() {
+        Some(mut bindings) => {
+            bindings.bindings.insert(binding_id, binding);
+        }
+        None => {
+            let mut bindings = Bindings::default();
+            bindings.bindings.insert(binding_id, binding);
+            emut.insert(bindings);
+        }
+    }
+
+    resolve_binding(world, entity, binding_id, bind_source);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Finds the source of a binding and registers a reactor for it if the source changed.
+///
+/// If no source is found, the binding waits until it is resolved again.
+fn resolve_binding(world: &mut World, entity: Entity, binding_id: TypeId, bind_source: BindSource)
+{
+    let Some(binding) = world
+        .get::<Bindings>(entity)
+        .and_then(|b| b.bindings.get(&binding_id))
+    else {
+        return;
+    };
+    let found = (bind_source.find)(world, entity);
+    if binding.reactor.is_some() && found == Some(binding.source) {
+        return;
+    }
+    let prev_reactor = binding.reactor.clone();
+    let update = binding.update;
+    let apply = binding.apply.clone();
+
+    // Register the reactor for the new source.
+    if let Some(token) = prev_reactor {
+        world.react(|rc| rc.revoke(token));
+    }
+    let reactor =
+        found.and_then(|source_entity| (bind_source.register)(&mut world.commands(), source_entity, update));
+    world.flush();
+
+    // Clean up the reactor if the binding was removed while registering it.
+    let Some(mut binding) = world
+        .get_mut::<Bindings>(entity)
+        .and_then(|b| b.filter_map_unchanged(|b| b.bindings.get_mut(&binding_id)))
+    else {
+        if let Some(token) = reactor {
+            world.react(|rc| rc.revoke(token));
+        }
+        return;
+    };
+    binding.source = found.flatten();
+    binding.reactor = reactor.clone();
+    if reactor.is_none() {
+        return;
+    }
+
+    // Apply the initial value.
+    // - This is called directly since the update system may be deferred when resolving inside a reaction.
+    (apply)(world);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resolves the sources of bindings with source type `source_type` on `root` and its descendants.
+fn retry_bindings(world: &mut World, root: Entity, source_type: TypeId)
+{
+    let mut pending = vec![];
+    let mut stack = vec![root];
+    while let Some(entity) = stack.pop() {
+        if let Some(bindings) = world.get::<Bindings>(entity) {
+            pending.extend(
+                bindings
+                    .bindings
+                    .iter()
+                    .filter(|(_, b)| b.bind_source.type_id == source_type)
+                    .map(|(id, b)| (entity, *id, b.bind_source)),
+            );
+        }
+        if let Some(children) = world.get::<Children>(entity) {
+            stack.extend(children.iter().copied());
+        }
+    }
+
+    for (entity, binding_id, bind_source) in pending {
+        resolve_binding(world, entity, binding_id, bind_source);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Removes the binding with `binding_id` from an entity.
+fn unbind_entity(world: &mut World, entity: Entity, binding_id: TypeId)
+{
+    let Some(binding) = world
+        .get_mut::<Bindings>(entity)
+        .and_then(|mut b| b.bindings.remove(&binding_id))
+    else {
+        return;
+    };
+    cleanup_binding(world, binding);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Copy, Clone)]
+struct BindSource
+{
+    /// The type of the source resource/component.
+    type_id: TypeId,
+    /// Finds the source entity for a binding on the given entity.
+    ///
+    /// Returns `Some(None)` for resource sources.
+    find: fn(&World, Entity) -> Option<Option<Entity>>,
+    /// Reads the source value.
+    read: fn(&World, Option<Entity>) -> Option<&dyn Reflect>,
+    /// Registers a revokable reactor that runs the system command when the source changes.
+    register: fn(&mut Commands, Option<Entity>, SystemCommand) -> Option<RevokeToken>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Resource, Default)]
+struct BindSourceRegistry
+{
+    /// [ short name : source ]
+    sources: HashMap<&'static str, BindSource>,
+}
+
+impl BindSourceRegistry
+{
+    /// Returns `false` if the source was already registered.
+    fn insert<T: TypePath>(&mut self, source: BindSource) -> bool
+    {
+        let shortname = T::short_type_path();
+        if self.sources.insert(shortname, source).is_some() {
+            tracing::warn!("overwriting bind source registration for {shortname}");
+            return false;
+        }
+        true
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A binding on an entity.
+struct Binding
+{
+    bind_source: BindSource,
+    /// The entity with the source component. `None` for resource sources.
+    source: Option<Entity>,
+    /// Updates the bound entity with the current source value.
+    apply: Arc<dyn Fn(&mut World) + Send + Sync>,
+    /// System command that runs `apply`.
+    update: SystemCommand,
+    /// Runs `update` when the source changes. `None` if the source hasn't been found.
+    reactor: Option<RevokeToken>,
+}
+
+/// Component that tracks the reactors of binding loadables on an entity.
+///
+/// The reactors are cleaned up when the component is removed.
+#[derive(Component, Default)]
+#[component(on_remove = cleanup_bindings_on_remove)]
+struct Bindings
+{
+    bindings: HashMap<TypeId, Binding>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Instruction loadable that binds a [`TextLine`] to a reflected value.
+///
+/// The `source` has the format `"TypeName.path.to.field"`, where `TypeName` is the short name of a type registered
+/// with [`register_bind_resource`](BindSourceAppExt::register_bind_resource) or
+/// [`register_bind_component`](BindSourceAppExt::register_bind_component). Component sources are looked up on
+/// the current entity and then its ancestors. If the component is missing, or is inserted on a closer ancestor
+/// later, the binding switches to the new source when it is inserted. The field path uses [`GetPath`] syntax and
+/// may be omitted to bind the entire value.
+///
+/// The text is updated whenever the source value changes.
+#[derive(Reflect, Default, Debug, Clone, PartialEq)]
+pub struct BindText
+{
+    /// Location of the value to display.
+    pub source: String,
+    /// Format string for the text. The first `{}` is replaced with the value.
+    ///
+    /// Defaults to `"{}"`.
+    #[reflect(default = "BindText::default_format")]
+    pub format: String,
+}
+
+impl BindText
+{
+    fn default_format() -> String
+    {
+        "{}".into()
+    }
+}
+
+impl Instruction for BindText
+{
+    fn apply(self, entity: Entity, world: &mut World)
+    {
+        let format = self.format;
+        bind_entity(world, entity, TypeId::of::<Self>(), &self.source, move |world, entity, value| {
+            let text = format.replacen("{}", &reflect_to_string(value), 1);
+            world.syscall((entity, text), |In((id, text)): In<(Entity, String)>, mut e: TextEditor| {
+                write_text!(e, id, "{}", text.as_str());
+            });
+        });
+    }
+
+    fn revert(entity: Entity, world: &mut World)
+    {
+        unbind_entity(world, entity, TypeId::of::<Self>());
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Instruction loadable that binds a [`PseudoState`] to a reflected `bool`.
+///
+/// The pseudo state is added to the entity when the source value is `true` and removed when it is `false`.
+///
+/// See [`BindText`] for the `source` format.
+#[derive(Reflect, Default, Debug, Clone, PartialEq)]
+pub struct BindPseudoState
+{
+    /// Location of the `bool` that controls the state.
+    pub source: String,
+    /// The state to add/remove.
+    pub state: PseudoState,
+}
+
+impl Instruction for BindPseudoState
+{
+    fn apply(self, entity: Entity, world: &mut World)
+    {
+        // Clean up the previous state in case it changed.
+        Self::revert(entity, world);
+
+        let Ok(mut emut) = world.get_entity_mut(entity) else { return };
+        emut.insert(BoundPseudoState(self.state.clone()));
+
+        let state = self.state;
+        bind_entity(world, entity, TypeId::of::<Self>(), &self.source, move |world, entity, value| {
+            let Some(enabled) = value.try_downcast_ref::<bool>() else {
+                tracing::warn!("failed updating BindPseudoState on {entity:?}, source value {value:?} is not a bool");
+                return;
+            };
+            let Ok(mut emut) = world.get_entity_mut(entity) else { return };
+            match *enabled {
+                true => emut.add_pseudo_state(state.clone()),
+                false => emut.remove_pseudo_state(state.clone()),
+            };
+        });
+    }
+
+    fn revert(entity: Entity, world: &mut World)
+    {
+        unbind_entity(world, entity, TypeId::of::<Self>());
+        let Ok(mut emut) = world.get_entity_mut(entity) else { return };
+        let Some(BoundPseudoState(state)) = emut.take::<BoundPseudoState>() else { return };
+        emut.remove_pseudo_state(state);
+    }
+}
+
+/// Records the pseudo state controlled by [`BindPseudoState`] so it can be cleaned up.
+#[derive(Component)]
+struct BoundPseudoState(PseudoState);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Instruction loadable that binds a [`StaticAttribute`] to a reflected value.
+///
+/// The source value must have the same type as the attribute's [`StaticAttribute::Value`]. For example,
+/// `Bind<Width>` needs a [`Val`] and `Bind<BackgroundColor>` needs a [`Color`].
+///
+/// `Bind<T>` is registered automatically for attributes registered with
+/// [`register_static`](ControlRegistrationExt::register_static).
+///
+/// See [`BindText`] for the `source` format.
+#[derive(Reflect, Default, Debug, Clone, PartialEq)]
+pub struct Bind<T: StaticAttribute>
+{
+    /// Location of the value to apply.
+    pub source: String,
+    #[reflect(ignore)]
+    _p: PhantomData<T>,
+}
+
+impl<T: StaticAttribute> Bind<T>
+{
+    /// Makes a new binding for the given source.
+    pub fn new(source: impl Into<String>) -> Self
+    {
+        Self { source: source.into(), _p: PhantomData }
+    }
+}
+
+impl<T: StaticAttribute> Instruction for Bind<T>
+{
+    fn apply(self, entity: Entity, world: &mut World)
+    {
+        bind_entity(world, entity, TypeId::of::<Self>(), &self.source, |world, entity, value| {
+            let Some(value) = T::Value::from_reflect(value) else {
+                tracing::warn!("failed updating {} on {entity:?}, source value {value:?} has the wrong type",
+                    std::any::type_name::<Self>());
+                return;
+            };
+            T::construct(value).apply(entity, world);
+        });
+    }
+
+    fn revert(entity: Entity, world: &mut World)
+    {
+        unbind_entity(world, entity, TypeId::of::<Self>());
+        T::revert(entity, world);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Extends `App` with methods for registering data sources for binding loadables like [`BindText`].
+pub trait BindSourceAppExt
+{
+    /// Registers a [`ReactResource`] that binding loadables can read from.
+    ///
+    /// Bindings are refreshed whenever the resource is mutated.
+    fn register_bind_resource<R: ReactResource + Reflect + TypePath + GetTypeRegistration>(&mut self) -> &mut Self;
+
+    /// Registers a [`ReactComponent`] that binding loadables can read from.
+    ///
+    /// Bindings look for the [`React<C>`] component on their own entity and then their ancestors, and are
+    /// refreshed whenever it is inserted or mutated. The lookup is repeated whenever `React<C>` is inserted on
+    /// the bound entity or one of its ancestors.
+    fn register_bind_component<C: ReactComponent + Reflect + TypePath + GetTypeRegistration>(&mut self) -> &mut Self;
+}
+
+impl BindSourceAppExt for App
+{
+    fn register_bind_resource<R: ReactResource + Reflect + TypePath + GetTypeRegistration>(&mut self) -> &mut Self
+    {
+        self.register_type::<R>();
+        self.world_mut()
+            .get_resource_or_insert_with::<BindSourceRegistry>(Default::default)
+            .insert::<R>(BindSource {
+                type_id: TypeId::of::<R>(),
+                find: find_resource_source,
+                read: read_resource_source::<R>,
+                register: register_resource_reactor::<R>,
+            });
+        self
+    }
+
+    fn register_bind_component<C: ReactComponent + Reflect + TypePath + GetTypeRegistration>(&mut self) -> &mut Self
+    {
+        self.register_type::<C>();
+        let is_new = self
+            .world_mut()
+            .get_resource_or_insert_with::<BindSourceRegistry>(Default::default)
+            .insert::<C>(BindSource {
+                type_id: TypeId::of::<C>(),
+                find: find_component_source::<C>,
+                read: read_component_source::<C>,
+                register: register_component_reactor::<C>,
+            });
+        if is_new {
+            self.react(|rc| rc.on_persistent(insertion::<C>(), retry_component_bindings::<C>));
+        }
+        self
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) struct BindingsPlugin;
+
+impl Plugin for BindingsPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.init_resource::<BindSourceRegistry>()
+            .register_instruction_type::<BindText>()
+            .register_instruction_type::<BindPseudoState>();
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -1,7 +1,9 @@
+mod bindings;
 mod plugin;
 mod reactor_ext;
 mod utils;
 
+pub use bindings::*;
 pub(crate) use plugin::*;
 pub use reactor_ext::*;
 pub use utils::*;
@@ -10,7 +10,8 @@ impl Plugin for ReactExtPlugin
 {
     fn build(&self, app: &mut App)
     {
-        app.add_plugins(ReactorExtPlugin);
+        app.add_plugins(ReactorExtPlugin)
+            .add_plugins(BindingsPlugin);
     }
 }
 
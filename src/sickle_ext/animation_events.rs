@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+
+use crate::sickle::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Re-sends animation lifecycle observer events as reactive entity events so they can be used with
+/// `entity_event::<AnimationFinished>(entity)` triggers.
+fn forward_animation_event<E: Event + Clone>(trigger: Trigger<E>, mut c: Commands)
+{
+    c.react().entity_event(trigger.entity(), trigger.event().clone());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Sets up animation lifecycle events.
+///
+/// [`AnimationStarted`], [`LoopIterationFinished`], and [`AnimationFinished`] are triggered as observer events on
+/// the animated entity by [`Animated`](crate::prelude::Animated) and [`Keyframes`](crate::prelude::Keyframes)
+/// attributes, and are also sent as reactive entity events.
+pub(crate) struct AnimationEventsPlugin;
+
+impl Plugin for AnimationEventsPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.add_observer(forward_animation_event::<AnimationStarted>)
+            .add_observer(forward_animation_event::<LoopIterationFinished>)
+            .add_observer(forward_animation_event::<AnimationFinished>);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,213 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use smol_str::SmolStr;
+
+use crate::prelude::*;
+use crate::sickle::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn get_viewport_width(
+    ui_camera: &DefaultUiCamera,
+    cameras: &Query<&Camera>,
+    primary_window: &Query<&Window, With<PrimaryWindow>>,
+    maybe_target_camera: Option<&TargetCamera>,
+) -> Option<f32>
+{
+    let camera_entity = maybe_target_camera
+        .map(|t| t.entity())
+        .or_else(|| ui_camera.get());
+    if let Some(size) = camera_entity
+        .and_then(|e| cameras.get(e).ok())
+        .and_then(|c| c.logical_viewport_size())
+    {
+        return Some(size.x);
+    }
+
+    primary_window.get_single().ok().map(|w| w.width())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn refresh_breakpoint_pseudo_states(
+    mut c: Commands,
+    defaults: Res<UiBreakpoints>,
+    ui_camera: DefaultUiCamera,
+    cameras: Query<&Camera>,
+    primary_window: Query<&Window, With<PrimaryWindow>>,
+    mut nodes: Query<(Entity, &mut BreakpointTracker, Option<&TargetCamera>)>,
+)
+{
+    for (entity, mut tracker, maybe_target_camera) in nodes.iter_mut() {
+        let Some(width) = get_viewport_width(&ui_camera, &cameras, &primary_window, maybe_target_camera) else {
+            continue;
+        };
+
+        let breakpoints = match tracker.breakpoints.is_empty() {
+            true => &defaults.breakpoints,
+            false => &tracker.breakpoints,
+        };
+        let next = find_breakpoint(breakpoints, width).map(SmolStr::from);
+        if next == tracker.current && !defaults.is_changed() {
+            continue;
+        }
+
+        let mut ec = c.entity(entity);
+        if let Some(prev) = tracker.current.take() {
+            ec.remove_pseudo_state(PseudoState::Custom(prev));
+        }
+        if let Some(next) = &next {
+            ec.add_pseudo_state(PseudoState::Custom(next.clone()));
+        }
+        tracker.current = next;
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Selects the breakpoint with the largest `min_width` that is `<=` the viewport width.
+fn find_breakpoint(breakpoints: &[Breakpoint], width: f32) -> Option<&str>
+{
+    breakpoints
+        .iter()
+        .filter(|b| b.min_width <= width)
+        .max_by(|a, b| a.min_width.total_cmp(&b.min_width))
+        .map(|b| b.name.as_str())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A named viewport-width threshold.
+#[derive(Reflect, Default, Debug, Clone, PartialEq)]
+pub struct Breakpoint
+{
+    /// Name of the [`PseudoState::Custom`] state added to entities while this breakpoint is active.
+    pub name: String,
+    /// Minimum logical viewport width where this breakpoint is active.
+    pub min_width: f32,
+}
+
+impl Breakpoint
+{
+    /// Makes a new breakpoint.
+    pub fn new(name: impl Into<String>, min_width: f32) -> Self
+    {
+        Self { name: name.into(), min_width }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource with the default breakpoints used by [`BreakpointStates`].
+///
+/// Defaults to `"compact"` below `800` logical pixels and `"wide"` at or above `800` logical pixels.
+#[derive(Resource, Debug, Clone)]
+pub struct UiBreakpoints
+{
+    pub breakpoints: Vec<Breakpoint>,
+}
+
+impl Default for UiBreakpoints
+{
+    fn default() -> Self
+    {
+        Self {
+            breakpoints: vec![Breakpoint::new("compact", 0.), Breakpoint::new("wide", 800.)],
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Instruction loadable that adds a [`PseudoState::Custom`] to the entity based on the viewport width.
+///
+/// The viewport is the entity's [`TargetCamera`] if it has one, otherwise the default UI camera or primary
+/// window. Only the active breakpoint's state is added at any time.
+///
+/// If `breakpoints` is empty, the [`UiBreakpoints`] resource is used.
+///
+/// Example:
+/// ```rust
+/// #scenes
+/// "menu"
+///     BreakpointStates
+///     ControlRoot
+///     Multi<Static<FlexNode>>[
+///         {value:{flex_direction:Column}}
+///         {state:[Custom("wide")] value:{flex_direction:Row}}
+///     ]
+/// ```
+#[derive(Reflect, Default, Debug, Clone, PartialEq)]
+pub struct BreakpointStates
+{
+    #[reflect(default)]
+    pub breakpoints: Vec<Breakpoint>,
+}
+
+impl Instruction for BreakpointStates
+{
+    fn apply(self, entity: Entity, world: &mut World)
+    {
+        // Clean up the previous state in case breakpoints changed.
+        Self::revert(entity, world);
+
+        let Ok(mut emut) = world.get_entity_mut(entity) else { return };
+        emut.insert(BreakpointTracker { breakpoints: self.breakpoints, current: None });
+    }
+
+    fn revert(entity: Entity, world: &mut World)
+    {
+        let Ok(mut emut) = world.get_entity_mut(entity) else { return };
+        let Some(tracker) = emut.take::<BreakpointTracker>() else { return };
+        let Some(current) = tracker.current else { return };
+        emut.remove_pseudo_state(PseudoState::Custom(current));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks the active breakpoint of an entity with [`BreakpointStates`].
+#[derive(Component, Debug)]
+struct BreakpointTracker
+{
+    breakpoints: Vec<Breakpoint>,
+    current: Option<SmolStr>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Extends `App` with methods for configuring viewport breakpoints.
+pub trait UiBreakpointsAppExt
+{
+    /// Sets the default breakpoints used by [`BreakpointStates`].
+    fn set_ui_breakpoints(&mut self, breakpoints: impl IntoIterator<Item = Breakpoint>) -> &mut Self;
+}
+
+impl UiBreakpointsAppExt for App
+{
+    fn set_ui_breakpoints(&mut self, breakpoints: impl IntoIterator<Item = Breakpoint>) -> &mut Self
+    {
+        self.insert_resource(UiBreakpoints { breakpoints: breakpoints.into_iter().collect() });
+        self
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) struct BreakpointsPlugin;
+
+impl Plugin for BreakpointsPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        if !app.world().contains_resource::<UiBreakpoints>() {
+            app.init_resource::<UiBreakpoints>();
+        }
+
+        app.register_type::<Breakpoint>()
+            .register_instruction_type::<BreakpointStates>()
+            .add_systems(PostUpdate, refresh_breakpoint_pseudo_states.in_set(RefreshPseudoStates));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
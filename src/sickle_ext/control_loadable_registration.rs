@@ -35,6 +35,7 @@ impl ControlRegistrationExt for App
         self.register_instruction_type::<T>()
             .register_instruction_type::<Static<T>>()
             .register_instruction_type::<Multi<Static<T>>>()
+            .register_instruction_type::<Bind<T>>()
     }
 
     fn register_responsive<T: GetTypeRegistration + Instruction + StaticAttribute + ResponsiveAttribute>(
@@ -59,6 +60,7 @@ impl ControlRegistrationExt for App
         self.register_responsive::<T>()
             .register_instruction_type::<Animated<T>>()
             .register_instruction_type::<Multi<Animated<T>>>()
+            .register_instruction_type::<Keyframes<T>>()
     }
 }
 
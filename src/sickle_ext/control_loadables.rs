@@ -3,7 +3,6 @@ use std::any::type_name;
 use bevy::ecs::entity::Entities;
 use bevy::prelude::*;
 use bevy_cobweb::prelude::*;
-use smallvec::SmallVec;
 use smol_str::SmolStr;
 
 use crate::prelude::*;
@@ -89,11 +88,11 @@ pub struct Static<T: StaticAttribute>
     pub name: Option<SmolStr>,
 
     /// Specifies which [`PseudoStates`](PseudoState) the root node of the control group this entity is a member
-    /// of must be in for this to become active.
+    /// of must be in for this to become active. See [`PseudoStateSelector`].
     ///
     /// Only used if this struct is applied to an entity with a [`ControlMember`].
     #[reflect(default)]
-    pub state: Option<SmallVec<[PseudoState; 3]>>,
+    pub state: Option<PseudoStateSelector>,
 
     /// The value that will be applied to the entity with `T`.
     pub value: T::Value,
@@ -150,11 +149,11 @@ pub struct Responsive<T: ResponsiveAttribute>
     pub name: Option<SmolStr>,
 
     /// Specifies which [`PseudoStates`](PseudoState) the root node of the control group this entity is a member
-    /// of must be in for this to become active.
+    /// of must be in for this to become active. See [`PseudoStateSelector`].
     ///
     /// Only used if this struct is applied to an entity with a [`ControlMember`].
     #[reflect(default)]
-    pub state: Option<SmallVec<[PseudoState; 3]>>,
+    pub state: Option<PseudoStateSelector>,
 
     /// The [`ControlMember`] of a non-root entity in the current widget. This attribute responds to interactions
     /// on that entity.
@@ -246,11 +245,11 @@ pub struct Animated<T: AnimatedAttribute>
     pub name: Option<SmolStr>,
 
     /// Specifies which [`PseudoStates`](PseudoState) the root node of the control group this entity is a member
-    /// of must be in for this animation to become active.
+    /// of must be in for this animation to become active. See [`PseudoStateSelector`].
     ///
     /// Only used if this struct is applied to an entity with a [`ControlMember`].
     #[reflect(default)]
-    pub state: Option<SmallVec<[PseudoState; 3]>>,
+    pub state: Option<PseudoStateSelector>,
 
     /// The [`ControlMember`] of a non-root entity in the current widget. Interactions on that entity will control
     /// this value.
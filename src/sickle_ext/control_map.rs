@@ -382,15 +382,15 @@ impl EntityCommand for RefreshControlledStyles
             let Some(attrs) = world.get::<NodeAttributes>(*entity) else { continue };
 
             for pt in attrs.iter_themes() {
-                let Some(count) = pt.is_subset(pseudo_states) else { continue };
-                pseudo_themes.push((count, idx, label, pt));
+                let Some(specificity) = pt.match_specificity(pseudo_states) else { continue };
+                pseudo_themes.push((specificity, idx, label, pt));
                 idx += 1;
             }
         }
 
-        // Sort themes by how well they match the pseudo states.
-        pseudo_themes.sort_unstable_by(|(count, idx, _, _), (count_b, idx_b, _, _)| {
-            count.cmp(count_b).then(idx.cmp(idx_b))
+        // Sort themes by selector specificity, then by insertion order so later themes win ties.
+        pseudo_themes.sort_unstable_by(|(specificity, idx, _, _), (specificity_b, idx_b, _, _)| {
+            specificity.cmp(specificity_b).then(idx.cmp(idx_b))
         });
 
         // Merge attributes, overwriting per-attribute as more specific pseudo themes are encountered.
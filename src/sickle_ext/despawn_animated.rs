@@ -0,0 +1,148 @@
+use bevy::prelude::*;
+use bevy::ui::UiSystem;
+
+use crate::prelude::*;
+use crate::sickle::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Default timeout for [`DespawnAnimatedExt::despawn_animated`], in seconds.
+pub const DEFAULT_DESPAWN_ANIMATED_TIMEOUT: f32 = 2.;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Component inserted on the root of a UI tree that is waiting for exit animations before despawning.
+#[derive(Component, Debug)]
+struct DespawnAnimated
+{
+    elapsed: f32,
+    timeout: f32,
+    /// Set after the first check so pseudo-state changes have a chance to reach node attributes.
+    checked: bool,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn is_animating(
+    entity: Entity,
+    styles: &Query<&DynamicStyle>,
+    keyframes: &Query<&KeyframeAnimations>,
+) -> bool
+{
+    // Only animations that require the Dying state are waited on. Other animations, such as continuous loops,
+    // keep playing until the entity is despawned.
+    if styles
+        .get(entity)
+        .is_ok_and(|s| s.is_exit_animating())
+    {
+        return true;
+    }
+    keyframes
+        .get(entity)
+        .map(|k| k.is_running_in_state(&PseudoState::Dying))
+        .unwrap_or(false)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn despawn_animated_entities(
+    mut c: Commands,
+    mut clocks: AnimationClocks,
+    mut roots: Query<(Entity, &mut DespawnAnimated)>,
+    children: Query<&Children>,
+    styles: Query<&DynamicStyle>,
+    keyframes: Query<&KeyframeAnimations>,
+)
+{
+    for (entity, mut despawn) in roots.iter_mut() {
+        despawn.elapsed += clocks.delta(entity).as_secs_f32();
+        if !despawn.checked {
+            despawn.checked = true;
+            continue;
+        }
+
+        if despawn.elapsed < despawn.timeout
+            && std::iter::once(entity)
+                .chain(children.iter_descendants(entity))
+                .any(|e| is_animating(e, &styles, &keyframes))
+        {
+            continue;
+        }
+
+        if let Some(e) = c.get_entity(entity) {
+            e.despawn_recursive();
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Extension trait for despawning UI trees after their exit animations finish.
+pub trait DespawnAnimatedExt
+{
+    /// Adds [`PseudoState::Dying`] to the entity and all its descendants, then despawns the entity recursively
+    /// once the tree's `Dying` animations have finished or a timeout elapses.
+    ///
+    /// Use [`Animated`] or [`Keyframes`] attributes with `state:[Dying]` to define exit animations, e.g.
+    /// `Animated<PropagateOpacity>{state:[Dying] idle:0 enter_idle_with:{duration:0.2}}`. Only attributes whose
+    /// state selector requires `Dying` are waited on. Other animations in the tree (e.g. continuous loops) don't
+    /// delay the despawn.
+    ///
+    /// The entity is despawned after [`DEFAULT_DESPAWN_ANIMATED_TIMEOUT`] seconds even if animations are still
+    /// running. The timeout is measured with the entity's [`AnimationClock`].
+    fn despawn_animated(&mut self);
+
+    /// Equivalent to [`Self::despawn_animated`] with a custom timeout in seconds.
+    fn despawn_animated_with_timeout(&mut self, timeout: f32);
+}
+
+impl DespawnAnimatedExt for EntityCommands<'_>
+{
+    fn despawn_animated(&mut self)
+    {
+        self.despawn_animated_with_timeout(DEFAULT_DESPAWN_ANIMATED_TIMEOUT);
+    }
+
+    fn despawn_animated_with_timeout(&mut self, timeout: f32)
+    {
+        self.queue(move |entity: Entity, world: &mut World| {
+            let Ok(mut emut) = world.get_entity_mut(entity) else { return };
+            if emut.contains::<DespawnAnimated>() {
+                return;
+            }
+            emut.insert(DespawnAnimated { elapsed: 0., timeout, checked: false });
+
+            let mut descendants = vec![entity];
+            let mut idx = 0;
+            while let Some(next) = descendants.get(idx).copied() {
+                idx += 1;
+                let Some(children) = world.get::<Children>(next) else { continue };
+                descendants.extend(children.iter().copied());
+            }
+            for descendant in descendants {
+                let Ok(mut emut) = world.get_entity_mut(descendant) else { continue };
+                emut.add_pseudo_state(PseudoState::Dying);
+            }
+        });
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) struct DespawnAnimatedPlugin;
+
+impl Plugin for DespawnAnimatedPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.add_systems(
+            PostUpdate,
+            despawn_animated_entities
+                .after(DynamicStylePostUpdate)
+                .after(KeyframesPostUpdate)
+                .before(UiSystem::Prepare),
+        );
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
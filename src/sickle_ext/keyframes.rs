@@ -0,0 +1,603 @@
+use std::any::{type_name, TypeId};
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use bevy::prelude::*;
+use bevy::ui::UiSystem;
+use bevy_cobweb::prelude::*;
+use smallvec::SmallVec;
+use smol_str::SmolStr;
+
+use crate::prelude::*;
+use crate::sickle::{Ease, *};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+trait KeyframesObject: Debug + Send + Sync + 'static
+{
+    /// Gets the time of the last keyframe.
+    fn duration(&self) -> f32;
+    /// Applies the value at `time` to the entity.
+    fn apply(&self, entity: Entity, world: &mut World, time: f32);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+struct CachedKeyframes<T: AnimatedAttribute>
+{
+    /// Sorted by time.
+    frames: Vec<Keyframe<T>>,
+}
+
+impl<T: AnimatedAttribute> CachedKeyframes<T>
+{
+    fn sample(&self, time: f32) -> Option<T::Value>
+    {
+        let next = self.frames.iter().position(|f| f.time > time);
+        match next {
+            Some(0) => self.frames.first().map(|f| f.value.clone()),
+            Some(idx) => {
+                let from = &self.frames[idx - 1];
+                let to = &self.frames[idx];
+                let span = to.time - from.time;
+                let t = match span > 0. {
+                    true => ((time - from.time) / span).clamp(0., 1.),
+                    false => 1.,
+                };
+                Some(from.value.lerp(to.value.clone(), t.ease(to.ease)))
+            }
+            None => self.frames.last().map(|f| f.value.clone()),
+        }
+    }
+}
+
+impl<T: AnimatedAttribute> Debug for CachedKeyframes<T>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        f.write_str("CachedKeyframes<")?;
+        f.write_str(type_name::<T>())?;
+        f.write_str(">")?;
+        Ok(())
+    }
+}
+
+impl<T: AnimatedAttribute> KeyframesObject for CachedKeyframes<T>
+{
+    fn duration(&self) -> f32
+    {
+        self.frames.last().map(|f| f.time).unwrap_or_default().max(0.)
+    }
+
+    fn apply(&self, entity: Entity, world: &mut World, time: f32)
+    {
+        let Some(value) = self.sample(time) else { return };
+        T::update(entity, world, value);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Controls for keyframe animations. See [`KeyframeAnimations::control`] and [`ControlKeyframes`].
+#[derive(Reflect, Default, Debug, Copy, Clone, PartialEq)]
+pub enum KeyframesControl
+{
+    /// Resumes a paused animation.
+    #[default]
+    Play,
+    /// Pauses an animation at its current time.
+    Pause,
+    /// Jumps to a time in seconds, measured from the start of the animation (including its delay and previous
+    /// loop iterations).
+    Seek(f32),
+    /// Restarts an animation from the beginning. Paused animations stay paused.
+    Restart,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Entity event for controlling [`Keyframes`] animations on an entity.
+///
+/// Can be sent with `c.react().entity_event(entity, ControlKeyframes::new(KeyframesControl::Pause))`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ControlKeyframes
+{
+    /// The name of the animation to control. If `None`, then all keyframe animations on the entity are controlled.
+    pub name: Option<SmolStr>,
+    pub control: KeyframesControl,
+}
+
+impl ControlKeyframes
+{
+    /// Makes a control event for all keyframe animations on an entity.
+    pub fn new(control: KeyframesControl) -> Self
+    {
+        Self { name: None, control }
+    }
+
+    /// Makes a control event for the keyframe animation with the given name.
+    pub fn named(name: impl Into<SmolStr>, control: KeyframesControl) -> Self
+    {
+        Self { name: Some(name.into()), control }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Runtime state of a [`Keyframes`] animation.
+#[derive(Debug)]
+struct KeyframePlayer
+{
+    name: Option<SmolStr>,
+    type_id: TypeId,
+    state: Option<PseudoStateSelector>,
+    loop_type: AnimationLoop,
+    delay: f32,
+    loop_gap: f32,
+    frames: Arc<dyn KeyframesObject>,
+    /// Whether this player was added by the latest [`Keyframes`] instruction of its type applied to the entity.
+    ///
+    /// Only this player is removed when the instruction is reverted.
+    latest: bool,
+
+    elapsed: f32,
+    paused: bool,
+    /// Whether the player's pseudo states matched the entity last tick.
+    active: bool,
+    finished: bool,
+    /// Set when the sampled value needs to be re-applied even if time didn't advance.
+    dirty: bool,
+    /// Whether [`AnimationLifecycle::Started`] was recorded since the animation last (re)started.
+    started: bool,
+    /// The number of completed loop iterations.
+    iteration: u32,
+    /// Lifecycle changes recorded by the last tick.
+    lifecycle: SmallVec<[AnimationLifecycle; 2]>,
+}
+
+impl KeyframePlayer
+{
+    fn control(&mut self, control: KeyframesControl)
+    {
+        match control {
+            KeyframesControl::Play => self.paused = false,
+            KeyframesControl::Pause => self.paused = true,
+            KeyframesControl::Seek(time) => {
+                self.elapsed = time.max(0.);
+                self.finished = false;
+            }
+            KeyframesControl::Restart => {
+                self.elapsed = 0.;
+                self.finished = false;
+                self.started = false;
+            }
+        }
+        self.dirty = true;
+    }
+
+    /// Converts elapsed time to a time within the keyframes.
+    ///
+    /// Returns `(time, finished, completed loop iterations)`.
+    fn sample_time(&self) -> (f32, bool, u32)
+    {
+        let duration = self.frames.duration();
+        let elapsed = self.elapsed - self.delay;
+        if elapsed <= 0. {
+            return (0., false, 0);
+        }
+        if duration <= 0. {
+            return (duration, true, 0);
+        }
+
+        let period = duration + self.loop_gap.max(0.);
+        let iteration = (elapsed / period).floor() as u32;
+        let offset = (elapsed - (iteration as f32 * period)).min(duration);
+        let reversed = iteration % 2 == 1;
+
+        match self.loop_type {
+            AnimationLoop::None => match elapsed >= duration {
+                true => (duration, true, 0),
+                false => (elapsed, false, 0),
+            },
+            AnimationLoop::Continous => (offset, false, iteration),
+            AnimationLoop::Times(times, reset) => match iteration >= times as u32 {
+                true => (if reset { 0. } else { duration }, true, times as u32),
+                false => (offset, false, iteration),
+            },
+            AnimationLoop::PingPongContinous => {
+                (if reversed { duration - offset } else { offset }, false, iteration)
+            }
+            AnimationLoop::PingPong(times) => match iteration >= times as u32 {
+                true => (if times % 2 == 0 { 0. } else { duration }, true, times as u32),
+                false => (if reversed { duration - offset } else { offset }, false, iteration),
+            },
+        }
+    }
+
+    /// Advances the animation and records lifecycle changes.
+    ///
+    /// Returns the keyframe time that should be applied to the entity, if any.
+    fn tick(&mut self, delta: f32, states: Option<&PseudoStates>) -> Option<f32>
+    {
+        let active = match &self.state {
+            Some(selector) => selector.matches(states.map(|s| s.get().as_slice()).unwrap_or_default()),
+            None => true,
+        };
+        if active != self.active {
+            self.active = active;
+            if active {
+                self.elapsed = 0.;
+                self.finished = false;
+                self.dirty = true;
+                self.started = false;
+            }
+        }
+        if !active {
+            return None;
+        }
+
+        if !self.paused && !self.finished {
+            if !self.started {
+                self.started = true;
+                self.lifecycle.push(AnimationLifecycle::Started);
+            }
+            self.elapsed += delta;
+            self.dirty = true;
+        }
+        if !self.dirty {
+            return None;
+        }
+        self.dirty = false;
+
+        let (time, finished, iteration) = self.sample_time();
+        if iteration > self.iteration {
+            self.lifecycle.push(AnimationLifecycle::LoopIterationFinished(iteration));
+        }
+        self.iteration = iteration;
+        if finished && !self.finished {
+            self.lifecycle.push(AnimationLifecycle::Finished);
+        }
+        self.finished = finished;
+        Some(time)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Component that stores the [`Keyframes`] animations on an entity.
+///
+/// Use [`Self::control`] or the [`ControlKeyframes`] entity event to play, pause, seek, or restart animations.
+#[derive(Component, Debug, Default)]
+pub struct KeyframeAnimations
+{
+    players: SmallVec<[KeyframePlayer; 1]>,
+}
+
+impl KeyframeAnimations
+{
+    /// Applies a control to the animation with the given name, or to all animations if `name` is `None`.
+    ///
+    /// Returns `false` if no animation was found.
+    pub fn control(&mut self, name: Option<&str>, control: KeyframesControl) -> bool
+    {
+        let mut found = false;
+        for player in self
+            .players
+            .iter_mut()
+            .filter(|p| name.is_none() || p.name.as_deref() == name)
+        {
+            player.control(control);
+            found = true;
+        }
+        found
+    }
+
+    /// Gets the elapsed time of a named animation, including its delay.
+    pub fn elapsed(&self, name: impl AsRef<str>) -> Option<f32>
+    {
+        self.get(name.as_ref()).map(|p| p.elapsed)
+    }
+
+    /// Returns `true` if a named animation is paused.
+    pub fn is_paused(&self, name: impl AsRef<str>) -> Option<bool>
+    {
+        self.get(name.as_ref()).map(|p| p.paused)
+    }
+
+    /// Gets the delay in seconds of a named animation.
+    pub fn delay(&self, name: impl AsRef<str>) -> Option<f32>
+    {
+        self.get(name.as_ref()).map(|p| p.delay)
+    }
+
+    /// Sets the delay in seconds of a named animation.
+    ///
+    /// Returns `false` if no animation was found.
+    pub fn set_delay(&mut self, name: impl AsRef<str>, delay: f32) -> bool
+    {
+        let name = name.as_ref();
+        let Some(player) = self
+            .players
+            .iter_mut()
+            .find(|p| p.name.as_deref() == Some(name))
+        else {
+            return false;
+        };
+        player.delay = delay;
+        true
+    }
+
+    /// Returns `true` if a named animation has finished all of its loop iterations.
+    ///
+    /// Continuously-looping animations never finish.
+    pub fn is_finished(&self, name: impl AsRef<str>) -> Option<bool>
+    {
+        self.get(name.as_ref()).map(|p| p.finished)
+    }
+
+    /// Returns `true` if an animation that requires `state` is active and not finished (including if it is
+    /// paused).
+    pub fn is_running_in_state(&self, state: &PseudoState) -> bool
+    {
+        self.players.iter().any(|p| {
+            p.active && !p.finished && p.state.as_ref().is_some_and(|s| s.requires(state))
+        })
+    }
+
+    fn get(&self, name: &str) -> Option<&KeyframePlayer>
+    {
+        self.players.iter().find(|p| p.name.as_deref() == Some(name))
+    }
+
+    fn insert(&mut self, player: KeyframePlayer) -> Option<KeyframePlayer>
+    {
+        match self
+            .players
+            .iter_mut()
+            .find(|p| p.type_id == player.type_id && p.state == player.state && p.name == player.name)
+        {
+            Some(existing) => Some(std::mem::replace(existing, player)),
+            None => {
+                self.players.push(player);
+                None
+            }
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A keyframe in a [`Keyframes`] animation.
+#[derive(Reflect, Default, Debug, Clone, PartialEq)]
+pub struct Keyframe<T: AnimatedAttribute>
+{
+    /// Time of the keyframe in seconds, relative to the start of a loop iteration.
+    pub time: f32,
+    /// The value at this keyframe.
+    pub value: T::Value,
+    /// Easing used to interpolate from the previous keyframe to this one.
+    ///
+    /// Defaults to linear easing.
+    #[reflect(default)]
+    pub ease: Ease,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Instruction for timeline animations that run independently of interactions.
+///
+/// The attribute is interpolated between keyframes. The value of the first keyframe is held until the first
+/// keyframe's time, and one iteration ends at the last keyframe's time.
+///
+/// Adds an animation to the [`KeyframeAnimations`] component on the target entity. Animations with the same
+/// attribute type, `name`, and `state` replace each other. Reverting the instruction removes only the animation added
+/// by the latest `Keyframes<T>` applied to the entity.
+///
+/// Example:
+/// ```rust
+/// #scenes
+/// "pulse"
+///     Keyframes<BackgroundColor>{
+///         frames:[{time:0 value:#FF0000} {time:0.5 value:#FFFF00 ease:InOutSine} {time:1 value:#FF0000}]
+///         loop_type:Continous
+///     }
+/// ```
+#[derive(Reflect, Default, Debug, Clone, PartialEq)]
+pub struct Keyframes<T: AnimatedAttribute>
+{
+    /// Sets the animation name.
+    ///
+    /// Can be used to control the animation with [`ControlKeyframes`].
+    #[reflect(default)]
+    pub name: Option<SmolStr>,
+
+    /// Specifies which [`PseudoStates`](PseudoState) the entity must be in for this animation to play. See
+    /// [`PseudoStateSelector`].
+    ///
+    /// The animation restarts every time the entity enters the states. It does nothing while the entity is not in
+    /// the states.
+    #[reflect(default)]
+    pub state: Option<PseudoStateSelector>,
+
+    /// Keyframes of the animation. They are sorted by time when the instruction is applied.
+    pub frames: Vec<Keyframe<T>>,
+
+    /// Controls how the animation repeats.
+    ///
+    /// Ping-pong loops play every odd iteration in reverse. Defaults to playing once.
+    #[reflect(default)]
+    pub loop_type: AnimationLoop,
+    /// Delay in seconds before the first iteration.
+    #[reflect(default)]
+    pub delay: f32,
+    /// Pause in seconds between iterations.
+    #[reflect(default)]
+    pub loop_gap: f32,
+    /// If `true`, then the animation starts paused.
+    #[reflect(default)]
+    pub paused: bool,
+}
+
+impl<T: AnimatedAttribute> Instruction for Keyframes<T>
+{
+    fn apply(mut self, entity: Entity, world: &mut World)
+    {
+        let Ok(mut emut) = world.get_entity_mut(entity) else { return };
+
+        self.frames.sort_by(|a, b| a.time.total_cmp(&b.time));
+        if let Some(selector) = self.state.as_mut() {
+            selector.normalize();
+        }
+
+        let player = KeyframePlayer {
+            name: self.name,
+            type_id: TypeId::of::<T>(),
+            state: self.state,
+            loop_type: self.loop_type,
+            delay: self.delay,
+            loop_gap: self.loop_gap,
+            frames: Arc::new(CachedKeyframes::<T> { frames: self.frames }),
+            latest: true,
+            elapsed: 0.,
+            paused: self.paused,
+            active: false,
+            finished: false,
+            dirty: true,
+            started: false,
+            iteration: 0,
+            lifecycle: SmallVec::new(),
+        };
+
+        if let Some(mut animations) = emut.get_mut::<KeyframeAnimations>() {
+            for existing in animations
+                .players
+                .iter_mut()
+                .filter(|p| p.type_id == TypeId::of::<T>())
+            {
+                existing.latest = false;
+            }
+            if let Some(_) = animations.insert(player) {
+                tracing::warn!("overwriting keyframes {} on {:?}", type_name::<Self>(), entity);
+            }
+        } else {
+            let mut animations = KeyframeAnimations::default();
+            animations.insert(player);
+            emut.insert(animations);
+        }
+    }
+
+    fn revert(entity: Entity, world: &mut World)
+    {
+        // Remove the animation added by this instruction.
+        // - Other animations of `T` on the entity are kept, e.g. animations with other names.
+        let Ok(mut emut) = world.get_entity_mut(entity) else { return };
+        let mut others = false;
+        if let Some(mut animations) = emut.get_mut::<KeyframeAnimations>() {
+            animations
+                .players
+                .retain(|p| p.type_id != TypeId::of::<T>() || !p.latest);
+            others = animations
+                .players
+                .iter()
+                .any(|p| p.type_id == TypeId::of::<T>());
+            if animations.players.is_empty() {
+                emut.remove::<KeyframeAnimations>();
+            }
+        }
+
+        // Revert instruction.
+        // - The remaining animations of `T` still control the attribute.
+        if !others {
+            T::revert(entity, world);
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn tick_keyframe_animations(
+    world: &mut World,
+    mut scratch: Local<Vec<(Entity, Arc<dyn KeyframesObject>, f32)>>,
+    mut lifecycles: Local<Vec<(Entity, Option<SmolStr>, AnimationLifecycle)>>,
+    mut deltas: Local<Vec<f32>>,
+)
+{
+    let mut query = world.query::<(Entity, &mut KeyframeAnimations, Option<&PseudoStates>)>();
+
+    // Look up animation clocks first since they are inherited from ancestors.
+    deltas.clear();
+    world.resource_scope(|world, mut cache: Mut<AnimationClockCache>| {
+        for (entity, ..) in query.iter(world) {
+            deltas.push(cache.delta_in_world(world, entity).as_secs_f32());
+        }
+    });
+
+    for ((entity, mut animations, states), delta) in query.iter_mut(world).zip(deltas.iter().copied()) {
+        // Ticking doesn't count as a change, so `Changed<KeyframeAnimations>` only detects new animations and
+        // controls.
+        for player in animations.bypass_change_detection().players.iter_mut() {
+            let time = player.tick(delta, states);
+            for lifecycle in player.lifecycle.drain(..) {
+                lifecycles.push((entity, player.name.clone(), lifecycle));
+            }
+            let Some(time) = time else { continue };
+            scratch.push((entity, player.frames.clone(), time));
+        }
+    }
+
+    for (entity, frames, time) in scratch.drain(..) {
+        frames.apply(entity, world, time);
+    }
+
+    if lifecycles.is_empty() {
+        return;
+    }
+    {
+        let mut c = world.commands();
+        for (entity, name, lifecycle) in lifecycles.drain(..) {
+            lifecycle.trigger(&mut c, entity, name);
+        }
+    }
+    world.flush();
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn control_keyframes_reactor(event: EntityEvent<ControlKeyframes>, mut animations: Query<&mut KeyframeAnimations>)
+{
+    let (entity, event) = event.read();
+    let Ok(mut animations) = animations.get_mut(entity) else { return };
+    if !animations.control(event.name.as_deref(), event.control) {
+        tracing::warn!("failed controlling keyframes on {entity:?}; no animation named {:?}", event.name);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// System set in `PostUpdate` where [`Keyframes`] animations are applied.
+///
+/// Runs after [`DynamicStylePostUpdate`], so keyframes override [`Animated`] values for the same attribute.
+#[derive(SystemSet, Debug, Clone, Eq, PartialEq, Hash)]
+pub struct KeyframesPostUpdate;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) struct KeyframesPlugin;
+
+impl Plugin for KeyframesPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.register_type::<KeyframesControl>()
+            .configure_sets(
+                PostUpdate,
+                KeyframesPostUpdate
+                    .after(DynamicStylePostUpdate)
+                    .before(UiSystem::Prepare),
+            )
+            .add_reactor(any_entity_event::<ControlKeyframes>(), control_keyframes_reactor)
+            .add_systems(PostUpdate, tick_keyframe_animations.in_set(KeyframesPostUpdate));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -2,24 +2,36 @@
 #[allow(unused_imports)]
 use crate as bevy_cobweb_ui;
 
+mod animation_events;
+mod breakpoints;
 mod builder_ext;
 mod control;
 mod control_loadable_registration;
 mod control_loadables;
 mod control_map;
 mod control_traits;
+mod despawn_animated;
 mod interaction_ext;
+mod keyframes;
 mod node_attributes;
 mod plugin;
+mod pseudo_state_selector;
 mod pseudo_states_ext;
+mod stagger;
 
+pub(crate) use animation_events::*;
+pub use breakpoints::*;
 pub use builder_ext::*;
 pub use control::*;
 pub use control_loadable_registration::*;
 pub use control_loadables::*;
 pub(crate) use control_map::*;
 pub use control_traits::*;
+pub use despawn_animated::*;
 pub use interaction_ext::*;
+pub use keyframes::*;
 pub use node_attributes::*;
 pub(crate) use plugin::*;
+pub use pseudo_state_selector::*;
 pub use pseudo_states_ext::*;
+pub use stagger::*;
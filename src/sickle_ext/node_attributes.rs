@@ -189,23 +189,34 @@ impl<T: AnimatedAttribute> AnimatedAttributeObject for CachedAnimatedAttribute<T
 
 //-------------------------------------------------------------------------------------------------------------------
 
+fn normalized(state: Option<&PseudoStateSelector>) -> Option<PseudoStateSelector>
+{
+    let mut state = state.cloned();
+    if let Some(selector) = state.as_mut() {
+        selector.normalize();
+    }
+    state
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 #[derive(Debug)]
 pub(super) struct PseudoTheme
 {
-    state: Option<SmallVec<[PseudoState; 3]>>,
+    state: Option<PseudoStateSelector>,
     style: SmallVec<[NodeAttribute; 3]>,
 }
 
 impl PseudoTheme
 {
-    fn new(state: Option<SmallVec<[PseudoState; 3]>>, attribute: NodeAttribute) -> Self
+    fn new(state: Option<PseudoStateSelector>, attribute: NodeAttribute) -> Self
     {
         let mut style = SmallVec::new();
         style.push(attribute);
         Self { state, style }
     }
 
-    fn matches(&self, state: &Option<SmallVec<[PseudoState; 3]>>) -> bool
+    fn matches(&self, state: &Option<PseudoStateSelector>) -> bool
     {
         self.state == *state
     }
@@ -226,15 +237,15 @@ impl PseudoTheme
         }
     }
 
-    pub(super) fn is_subset(&self, node_states: &[PseudoState]) -> Option<usize>
+    /// Returns the specificity of the theme's selector if it matches the node's pseudo states.
+    pub(super) fn match_specificity(&self, node_states: &[PseudoState]) -> Option<usize>
     {
         match &self.state {
-            // Only consider pseudo themes that are specific to an inclusive subset of the themed element's pseudo
-            // states. A theme for [Checked, Disabled] will apply to elements with [Checked, Disabled,
-            // FirstChild], but will not apply to elements with [Checked] (because the theme targets
-            // more specific elements) or [Checked, FirstChild] (because they are disjoint).
-            Some(theme_states) => match theme_states.iter().all(|state| node_states.contains(state)) {
-                true => Some(theme_states.len()),
+            // A theme for [Checked Disabled] will apply to elements with [Checked Disabled FirstChild], but will
+            // not apply to elements with [Checked]. A theme for Any[Checked Disabled] will apply to both. When
+            // several themes match, the one with the highest specificity wins.
+            Some(selector) => match selector.matches(node_states) {
+                true => Some(selector.specificity()),
                 false => None,
             },
             None => Some(0),
@@ -264,7 +275,16 @@ impl PseudoTheme
             style_builder.switch_target_with(label.clone());
 
             // Insert attribute.
-            style_builder.add(attribute.dynamic_style_attribute());
+            let mut dynamic_attribute = attribute.dynamic_style_attribute();
+            if let DynamicStyleAttribute::Animated { attribute: animated, .. } = &mut dynamic_attribute {
+                // Animations that only play while dying are waited on by `despawn_animated`.
+                let exit = self
+                    .state
+                    .as_ref()
+                    .is_some_and(|s| s.requires(&PseudoState::Dying));
+                *animated = animated.clone().with_exit(exit);
+            }
+            style_builder.add(dynamic_attribute);
         }
     }
 
@@ -276,10 +296,10 @@ impl PseudoTheme
     }
 
     /// Gets the attribute's info if this theme contains an attribute with the given name.
-    fn get_info(&self, name: &str) -> Option<(Option<&[PseudoState]>, &NodeAttribute)>
+    fn get_info(&self, name: &str) -> Option<(Option<&PseudoStateSelector>, &NodeAttribute)>
     {
         if let Some(attr) = self.style.iter().find(|a| a.name() == Some(name)) {
-            Some((self.state.as_ref().map(|s| s.as_slice()), attr))
+            Some((self.state.as_ref(), attr))
         } else {
             None
         }
@@ -292,9 +312,9 @@ impl PseudoTheme
     }
 
     /// Gets an attribute with state and type id.
-    fn get_with<'a>(&'a self, state: Option<&[PseudoState]>, type_id: TypeId) -> Option<&'a NodeAttribute>
+    fn get_with<'a>(&'a self, state: Option<&PseudoStateSelector>, type_id: TypeId) -> Option<&'a NodeAttribute>
     {
-        if self.state.as_ref().map(|s| s.as_slice()) != state {
+        if self.state.as_ref() != state {
             return None;
         }
         for attr in self.style.iter() {
@@ -306,9 +326,13 @@ impl PseudoTheme
     }
 
     /// Gets an attribute mutably with state and type id.
-    fn get_with_mut(&mut self, state: Option<&[PseudoState]>, type_id: TypeId) -> Option<&mut NodeAttribute>
+    fn get_with_mut(
+        &mut self,
+        state: Option<&PseudoStateSelector>,
+        type_id: TypeId,
+    ) -> Option<&mut NodeAttribute>
     {
-        if self.state.as_ref().map(|s| s.as_slice()) != state {
+        if self.state.as_ref() != state {
             return None;
         }
         self.style.iter_mut().find(|a| a.attr_type_id() == type_id)
@@ -549,7 +573,7 @@ impl NodeAttribute
                 DynamicStyleAttribute::Responsive(ResponsiveStyleAttribute::new(self.type_id, attr))
             }
             CachedAttribute::Animated(attr) => DynamicStyleAttribute::Animated {
-                attribute: AnimatedStyleAttribute::new(self.type_id, attr),
+                attribute: AnimatedStyleAttribute::new(self.type_id, attr).with_name(self.name.clone()),
                 controller: DynamicStyleController::new(
                     self.settings.clone().unwrap_or_default(),
                     AnimationState::default(),
@@ -589,17 +613,15 @@ impl NodeAttributes
     /// Logs a warning if the attribute has the same name as an existing attribute.
     pub fn insert(
         &mut self,
-        mut state: Option<SmallVec<[PseudoState; 3]>>,
+        state: Option<PseudoStateSelector>,
         attribute: NodeAttribute,
     ) -> Option<NodeAttribute>
     {
-        if let Some(states) = state.as_deref_mut() {
-            states.sort_unstable();
-        }
+        let state = normalized(state.as_ref());
 
         if let Some(name) = attribute.name() {
             if let Some((prev_state, prev)) = self.get_info(name) {
-                if state.as_ref().map(|s| s.as_slice()) != prev_state || !attribute.logical_eq(prev) {
+                if state.as_ref() != prev_state || !attribute.logical_eq(prev) {
                     tracing::warn!("adding node attribute to entity that already has an attribute with the same name ({}); \
                         only the older attribute will be accessible by NodeAttributes::get, etc.", name);
                 }
@@ -638,7 +660,7 @@ impl NodeAttributes
     /// Gets an attribute and its state by name.
     ///
     /// If multiple attributes have the same name, this will return the state of the one that was inserted first.
-    pub fn get_info(&self, name: impl AsRef<str>) -> Option<(Option<&[PseudoState]>, &NodeAttribute)>
+    pub fn get_info(&self, name: impl AsRef<str>) -> Option<(Option<&PseudoStateSelector>, &NodeAttribute)>
     {
         let name = name.as_ref();
         self.themes.iter().filter_map(|t| t.get_info(name)).next()
@@ -790,20 +812,30 @@ impl NodeAttributes
     }
 
     /// Gets an attribute with state and type id.
-    pub fn get_with(&self, state: Option<&[PseudoState]>, type_id: TypeId) -> Option<&NodeAttribute>
+    ///
+    /// The state is [normalized](PseudoStateSelector::normalize) before looking up the attribute.
+    pub fn get_with(&self, state: Option<&PseudoStateSelector>, type_id: TypeId) -> Option<&NodeAttribute>
     {
+        let state = normalized(state);
         self.themes
             .iter()
-            .filter_map(|t| t.get_with(state, type_id))
+            .filter_map(|t| t.get_with(state.as_ref(), type_id))
             .next()
     }
 
     /// Gets an attribute mutably with state and type id.
-    pub fn get_with_mut(&mut self, state: Option<&[PseudoState]>, type_id: TypeId) -> Option<&mut NodeAttribute>
+    ///
+    /// The state is [normalized](PseudoStateSelector::normalize) before looking up the attribute.
+    pub fn get_with_mut(
+        &mut self,
+        state: Option<&PseudoStateSelector>,
+        type_id: TypeId,
+    ) -> Option<&mut NodeAttribute>
     {
+        let state = normalized(state);
         self.themes
             .iter_mut()
-            .filter_map(|t| t.get_with_mut(state, type_id))
+            .filter_map(|t| t.get_with_mut(state.as_ref(), type_id))
             .next()
     }
 
@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 
 use crate::prelude::*;
-use crate::sickle::{Ease, *};
+use crate::sickle::{Ease, StepJump, *};
 
 //-------------------------------------------------------------------------------------------------------------------
 
@@ -11,16 +11,25 @@ impl Plugin for SickleExtPlugin
 {
     fn build(&self, app: &mut App)
     {
-        app.add_plugins((FluxInteractionPlugin, PseudoStatePlugin, DynamicStylePlugin));
+        app.add_plugins((AnimationClockPlugin, FluxInteractionPlugin, PseudoStatePlugin, DynamicStylePlugin));
         app.register_type::<Ease>()
+            .register_type::<StepJump>()
             .register_type::<PseudoState>()
+            .register_type::<PseudoStateSelector>()
             .register_type::<AnimationSettings>()
             .register_type::<AnimationConfig>()
             .register_type::<AnimationLoop>()
+            .register_type::<AnimationTime>()
+            .register_component_type::<AnimationClock>()
+            .add_plugins(AnimationEventsPlugin)
             .add_plugins(ControlPlugin)
             .add_plugins(ControlMapPlugin)
             .add_plugins(UiInteractionExtPlugin)
-            .add_plugins(PseudoStatesExtPlugin);
+            .add_plugins(PseudoStatesExtPlugin)
+            .add_plugins(BreakpointsPlugin)
+            .add_plugins(KeyframesPlugin)
+            .add_plugins(DespawnAnimatedPlugin)
+            .add_plugins(StaggerPlugin);
     }
 }
 
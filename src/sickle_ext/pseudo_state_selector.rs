@@ -0,0 +1,314 @@
+use std::fmt;
+
+use bevy::prelude::*;
+use serde::de::value::{EnumAccessDeserializer, MapAccessDeserializer, StrDeserializer, StringDeserializer};
+use serde::de::{DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, VariantAccess, Visitor};
+use serde::ser::SerializeSeq;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::sickle::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resumes deserializing an enum after its variant identifier has been read.
+struct ResumedEnum<A>
+{
+    name: String,
+    variant: A,
+}
+
+impl<'de, A> EnumAccess<'de> for ResumedEnum<A>
+where
+    A: VariantAccess<'de>,
+{
+    type Error = A::Error;
+    type Variant = A;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, A), A::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let name: StringDeserializer<A::Error> = self.name.into_deserializer();
+        seed.deserialize(name).map(|v| (v, self.variant))
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+struct SelectorVisitor;
+
+impl<'de> Visitor<'de> for SelectorVisitor
+{
+    type Value = PseudoStateSelector;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        f.write_str("a pseudo state, a list of pseudo states, or an All/Any/Not selector")
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        SelectorListVisitor.visit_seq(seq).map(PseudoStateSelector::All)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        let variant: StrDeserializer<E> = v.into_deserializer();
+        self.visit_enum(variant)
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        self.visit_enum(MapAccessDeserializer::new(map))
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        let (name, variant): (String, A::Variant) = data.variant()?;
+        match name.as_str() {
+            "All" => variant
+                .newtype_variant::<SelectorList>()
+                .map(|l| PseudoStateSelector::All(l.0)),
+            "Any" => variant
+                .newtype_variant::<SelectorList>()
+                .map(|l| PseudoStateSelector::Any(l.0)),
+            "Not" => variant
+                .newtype_variant::<SelectorList>()
+                .map(|l| PseudoStateSelector::Not(l.0)),
+            _ => PseudoState::deserialize(EnumAccessDeserializer::new(ResumedEnum { name, variant }))
+                .map(PseudoStateSelector::State),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// The contents of `All`, `Any`, and `Not`. Can be a list of selectors or a single selector.
+struct SelectorList(Vec<PseudoStateSelector>);
+
+impl<'de> Deserialize<'de> for SelectorList
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(SelectorListVisitor).map(SelectorList)
+    }
+}
+
+struct SelectorListVisitor;
+
+impl<'de> Visitor<'de> for SelectorListVisitor
+{
+    type Value = Vec<PseudoStateSelector>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        f.write_str("a pseudo state selector or a list of pseudo state selectors")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut selectors = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(selector) = seq.next_element::<PseudoStateSelector>()? {
+            selectors.push(selector);
+        }
+        Ok(selectors)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        SelectorVisitor.visit_str(v).map(|s| vec![s])
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        SelectorVisitor.visit_map(map).map(|s| vec![s])
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        SelectorVisitor.visit_enum(data).map(|s| vec![s])
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A boolean expression over [`PseudoStates`] that controls when an attribute is active.
+///
+/// Used by the `state` field of [`Static`](crate::prelude::Static), [`Responsive`](crate::prelude::Responsive),
+/// [`Animated`](crate::prelude::Animated), and [`Keyframes`](crate::prelude::Keyframes).
+///
+/// In COB, a list of states is shorthand for `All`, and `All`/`Any`/`Not` accept either a list or a single
+/// selector. Selectors can be nested.
+///
+/// ```rust
+/// #scenes
+/// "button"
+///     ControlRoot
+///     Multi<Static<BackgroundColor>>[
+///         {value:#444444}
+///         {state:[Selected] value:#4444AA}
+///         {state:All[Selected Not(Disabled)] value:#6666FF}
+///         {state:Any[Checked Custom("highlighted")] value:#AA4444}
+///     ]
+/// ```
+///
+/// When several selectors for the same attribute match, the one with the highest [specificity](Self::specificity)
+/// wins. Ties are won by the attribute that was inserted last.
+#[derive(Reflect, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[reflect(Serialize, Deserialize, no_field_bounds)]
+pub enum PseudoStateSelector
+{
+    /// Matches if the entity has this state.
+    State(PseudoState),
+    /// Matches if all the inner selectors match. Matches if empty.
+    All(Vec<PseudoStateSelector>),
+    /// Matches if any of the inner selectors match. Never matches if empty.
+    Any(Vec<PseudoStateSelector>),
+    /// Matches if none of the inner selectors match.
+    Not(Vec<PseudoStateSelector>),
+}
+
+impl PseudoStateSelector
+{
+    /// Makes a selector that requires all of `states`.
+    pub fn all(states: impl IntoIterator<Item = PseudoState>) -> Self
+    {
+        Self::All(states.into_iter().map(Self::State).collect())
+    }
+
+    /// Makes a selector that requires any of `states`.
+    pub fn any(states: impl IntoIterator<Item = PseudoState>) -> Self
+    {
+        Self::Any(states.into_iter().map(Self::State).collect())
+    }
+
+    /// Makes a selector that requires none of `states`.
+    pub fn not(states: impl IntoIterator<Item = PseudoState>) -> Self
+    {
+        Self::Not(states.into_iter().map(Self::State).collect())
+    }
+
+    /// Returns `true` if `states` satisfies the selector.
+    pub fn matches(&self, states: &[PseudoState]) -> bool
+    {
+        match self {
+            Self::State(state) => states.contains(state),
+            Self::All(selectors) => selectors.iter().all(|s| s.matches(states)),
+            Self::Any(selectors) => selectors.iter().any(|s| s.matches(states)),
+            Self::Not(selectors) => !selectors.iter().any(|s| s.matches(states)),
+        }
+    }
+
+    /// Gets the priority of the selector when several matching selectors compete.
+    ///
+    /// Each state counts as 1. `All` adds up its selectors, while `Any` and `Not` use their most specific
+    /// selector. For example, `[Selected]` has specificity 1 and `All[Selected Not(Disabled)]` has specificity 2.
+    pub fn specificity(&self) -> usize
+    {
+        match self {
+            Self::State(_) => 1,
+            Self::All(selectors) => selectors.iter().map(|s| s.specificity()).sum(),
+            Self::Any(selectors) | Self::Not(selectors) => {
+                selectors.iter().map(|s| s.specificity()).max().unwrap_or(0)
+            }
+        }
+    }
+
+    /// Returns `true` if `state` must be present for the selector to match.
+    pub fn requires(&self, state: &PseudoState) -> bool
+    {
+        match self {
+            Self::State(s) => s == state,
+            Self::All(selectors) => selectors.iter().any(|s| s.requires(state)),
+            Self::Any(selectors) => !selectors.is_empty() && selectors.iter().all(|s| s.requires(state)),
+            Self::Not(_) => false,
+        }
+    }
+
+    /// Sorts and deduplicates inner selectors so logically-equal selectors compare equal.
+    ///
+    /// `All` and `Any` with a single selector are replaced by that selector, so e.g. `[Selected]` equals
+    /// `Selected`.
+    pub fn normalize(&mut self)
+    {
+        match self {
+            Self::State(_) => (),
+            Self::All(selectors) | Self::Any(selectors) | Self::Not(selectors) => {
+                selectors.iter_mut().for_each(|s| s.normalize());
+                selectors.sort_unstable();
+                selectors.dedup();
+            }
+        }
+
+        if let Self::All(selectors) | Self::Any(selectors) = self {
+            if selectors.len() == 1 {
+                *self = selectors.pop().unwrap();
+            }
+        }
+    }
+}
+
+impl From<PseudoState> for PseudoStateSelector
+{
+    fn from(state: PseudoState) -> Self
+    {
+        Self::State(state)
+    }
+}
+
+impl Serialize for PseudoStateSelector
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::State(state) => state.serialize(serializer),
+            // Lists are shorthand for `All`.
+            Self::All(selectors) => {
+                let mut seq = serializer.serialize_seq(Some(selectors.len()))?;
+                for selector in selectors.iter() {
+                    seq.serialize_element(selector)?;
+                }
+                seq.end()
+            }
+            Self::Any(selectors) => {
+                serializer.serialize_newtype_variant("PseudoStateSelector", 2, "Any", selectors)
+            }
+            Self::Not(selectors) => match selectors.as_slice() {
+                [selector] => serializer.serialize_newtype_variant("PseudoStateSelector", 3, "Not", selector),
+                _ => serializer.serialize_newtype_variant("PseudoStateSelector", 3, "Not", selectors),
+            },
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PseudoStateSelector
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(SelectorVisitor)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
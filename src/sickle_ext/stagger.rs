@@ -0,0 +1,302 @@
+use bevy::ecs::entity::EntityHashSet;
+use bevy::prelude::*;
+use smallvec::SmallVec;
+use smol_str::SmolStr;
+
+use super::*;
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the `enter_idle_with` delay of a named animated attribute.
+fn attribute_delay(attrs: &NodeAttributes, name: &str) -> Option<f32>
+{
+    attrs
+        .animation_settings(name)
+        .and_then(|s| s.enter_idle_with.as_ref())
+        .map(|c| c.delay)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Sets the `enter_idle_with` delay of a named animated attribute.
+fn set_attribute_delay(attrs: &mut NodeAttributes, name: &str, delay: f32)
+{
+    attrs.edit_animation_settings(name, |s| {
+        if let Some(config) = s.enter_idle_with.as_mut() {
+            config.delay = delay;
+        }
+    });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Offsets the delay of a named attribute on a child of a [`Stagger`] entity.
+fn stagger_delay(
+    name: &SmolStr,
+    offset: f32,
+    attrs: &mut Option<Mut<NodeAttributes>>,
+    keyframes: &mut Option<Mut<KeyframeAnimations>>,
+    delays: &mut StaggerDelays,
+)
+{
+    let attr_delay = attrs.as_deref().and_then(|a| attribute_delay(a, name));
+    let Some(current) = attr_delay.or_else(|| keyframes.as_deref().and_then(|k| k.delay(name))) else { return };
+
+    // If the delay doesn't match the one applied previously, then the attribute was reloaded or edited and the
+    // current delay is the new base delay.
+    let base = match delays.get(name) {
+        Some((base, applied)) if applied == current => base,
+        _ => current,
+    };
+    let delay = base + offset;
+    delays.set(name, base, delay);
+    if delay == current {
+        return;
+    }
+
+    // Only access mutably when the delay changes, to avoid triggering attribute refreshes every tick.
+    if attr_delay.is_some() {
+        if let Some(attrs) = attrs.as_deref_mut() {
+            set_attribute_delay(attrs, name, delay);
+        }
+    } else if let Some(keyframes) = keyframes.as_deref_mut() {
+        keyframes.set_delay(name, delay);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn apply_stagger(
+    mut c: Commands,
+    mut edited_parents: Local<EntityHashSet>,
+    staggers: Query<(Entity, Ref<Stagger>, Ref<Children>)>,
+    mut p: ParamSet<(
+        Query<&Parent, Or<(Changed<NodeAttributes>, Changed<KeyframeAnimations>)>>,
+        Query<(
+            Option<&mut NodeAttributes>,
+            Option<&mut KeyframeAnimations>,
+            Option<&mut StaggerDelays>,
+        )>,
+    )>,
+)
+{
+    // Delays only need to be recomputed when the stagger or its children change, or when a child's attributes are
+    // added, reloaded, or edited.
+    edited_parents.clear();
+    edited_parents.extend(p.p0().iter().map(|p| p.get()));
+
+    let mut children = p.p1();
+
+    for (entity, stagger, stagger_children) in staggers.iter() {
+        if !stagger.is_changed() && !stagger_children.is_changed() && !edited_parents.contains(&entity) {
+            continue;
+        }
+
+        let count = stagger_children.len();
+        for (idx, child) in stagger_children.iter().enumerate() {
+            let Ok((mut attrs, mut keyframes, delays)) = children.get_mut(*child) else { continue };
+            if attrs.is_none() && keyframes.is_none() {
+                continue;
+            }
+
+            let mut new_delays = None;
+            let delays = match delays {
+                Some(delays) => delays.into_inner(),
+                None => new_delays.insert(StaggerDelays::default()),
+            };
+
+            let step = stagger.order.step(idx, count, false);
+            stagger_delay(
+                &stagger.attribute,
+                stagger.interval * step as f32,
+                &mut attrs,
+                &mut keyframes,
+                delays,
+            );
+
+            if let Some(exit_attribute) = &stagger.exit_attribute {
+                let step = stagger.order.step(idx, count, true);
+                stagger_delay(
+                    exit_attribute,
+                    stagger.interval * step as f32,
+                    &mut attrs,
+                    &mut keyframes,
+                    delays,
+                );
+            }
+
+            if let Some(new_delays) = new_delays.filter(|d| !d.entries.is_empty()) {
+                c.entity(*child).insert(new_delays);
+            }
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Records the delays applied to attributes on a child of a [`Stagger`] entity.
+#[derive(Component, Debug, Default)]
+struct StaggerDelays
+{
+    /// (attribute name, base delay, applied delay)
+    entries: SmallVec<[(SmolStr, f32, f32); 2]>,
+}
+
+impl StaggerDelays
+{
+    fn get(&self, name: &str) -> Option<(f32, f32)>
+    {
+        self.entries
+            .iter()
+            .find(|(n, _, _)| n == name)
+            .map(|(_, base, applied)| (*base, *applied))
+    }
+
+    fn set(&mut self, name: &SmolStr, base: f32, applied: f32)
+    {
+        match self.entries.iter_mut().find(|(n, _, _)| n == name) {
+            Some(entry) => *entry = (name.clone(), base, applied),
+            None => self.entries.push((name.clone(), base, applied)),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// The order in which the children of a [`Stagger`] entity are animated.
+#[derive(Reflect, Default, Debug, Copy, Clone, PartialEq)]
+pub enum StaggerOrder
+{
+    /// Children animate from first to last.
+    #[default]
+    Index,
+    /// Children animate from last to first.
+    ReverseIndex,
+    /// Children animate outward from the child at the given index.
+    FromPivot(usize),
+}
+
+impl StaggerOrder
+{
+    /// Gets the number of stagger intervals to delay a child by.
+    ///
+    /// If `reverse` is `true`, then the order is reversed (e.g. for exit animations).
+    pub fn step(&self, index: usize, count: usize, reverse: bool) -> usize
+    {
+        let last = count.saturating_sub(1);
+        let (step, max) = match *self {
+            Self::Index => (index, last),
+            Self::ReverseIndex => (last.saturating_sub(index), last),
+            Self::FromPivot(pivot) => {
+                let pivot = pivot.min(last);
+                (index.abs_diff(pivot), pivot.max(last - pivot))
+            }
+        };
+        match reverse {
+            true => max.saturating_sub(step),
+            false => step,
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Instruction loadable for animating the children of an entity in sequence.
+///
+/// Offsets the delay of a named attribute on each child by `interval` seconds per step, where steps are
+/// determined by [`StaggerOrder`]. Works with the `enter_idle_with` delay of [`Animated`] attributes and the
+/// `delay` of [`Keyframes`] animations. The delays are recomputed when children are inserted or removed, and
+/// when a child's attribute is reloaded.
+///
+/// Set `exit_attribute` to stagger an exit animation in the reverse order (e.g. an `Animated` attribute with
+/// `state:[Dying]` that plays when the tree is despawned with
+/// [`despawn_animated`](DespawnAnimatedExt::despawn_animated)).
+///
+/// Example:
+/// ```rust
+/// #scenes
+/// "list"
+///     FlexNode{flex_direction:Column}
+///     Stagger{attribute:"reveal" interval:0.05 exit_attribute:"exit"}
+///
+///     "item"
+///         Animated<PropagateOpacity>{
+///             name:"reveal" idle:1 enter_ref_override:0 enter_idle_with:{duration:0.3}
+///         }
+///         Animated<PropagateOpacity>{name:"exit" state:[Dying] idle:0 enter_idle_with:{duration:0.2}}
+/// ```
+#[derive(Component, Reflect, Default, Debug, Clone, PartialEq)]
+pub struct Stagger
+{
+    /// Name of the [`Animated`] or [`Keyframes`] attribute on each child to stagger.
+    pub attribute: SmolStr,
+    /// Seconds added to the delay of each step.
+    pub interval: f32,
+    /// The order in which children are animated. Defaults to [`StaggerOrder::Index`].
+    #[reflect(default)]
+    pub order: StaggerOrder,
+    /// Name of an attribute on each child to stagger in the reverse order.
+    #[reflect(default)]
+    pub exit_attribute: Option<SmolStr>,
+}
+
+impl Instruction for Stagger
+{
+    fn apply(self, entity: Entity, world: &mut World)
+    {
+        // Restore child delays in case the attribute names changed.
+        Self::revert(entity, world);
+
+        let Ok(mut emut) = world.get_entity_mut(entity) else { return };
+        emut.insert(self);
+    }
+
+    fn revert(entity: Entity, world: &mut World)
+    {
+        let Ok(mut emut) = world.get_entity_mut(entity) else { return };
+        if emut.take::<Stagger>().is_none() {
+            return;
+        }
+        let Some(children) = emut.get::<Children>() else { return };
+        let children: SmallVec<[Entity; 16]> = children.iter().copied().collect();
+
+        for child in children {
+            let Ok(mut emut) = world.get_entity_mut(child) else { continue };
+            let Some(delays) = emut.take::<StaggerDelays>() else { continue };
+
+            for (name, base, applied) in delays.entries {
+                if let Some(mut attrs) = emut.get_mut::<NodeAttributes>() {
+                    if attribute_delay(&attrs, &name) == Some(applied) {
+                        set_attribute_delay(&mut attrs, &name, base);
+                        continue;
+                    }
+                }
+                if let Some(mut keyframes) = emut.get_mut::<KeyframeAnimations>() {
+                    if keyframes.delay(&name) == Some(applied) {
+                        keyframes.set_delay(&name, base);
+                    }
+                }
+            }
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) struct StaggerPlugin;
+
+impl Plugin for StaggerPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.register_type::<StaggerOrder>()
+            .register_instruction_type::<Stagger>()
+            .add_systems(
+                PostUpdate,
+                apply_stagger.after(FileProcessingSet).before(ControlSet),
+            );
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -1,7 +1,9 @@
 mod plugin;
+mod ui_builder_for_each_ext;
 mod ui_builder_react_ext;
 mod ui_ext;
 
 pub(crate) use plugin::*;
+pub use ui_builder_for_each_ext::*;
 pub use ui_builder_react_ext::*;
 pub use ui_ext::*;
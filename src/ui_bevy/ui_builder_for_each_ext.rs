@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::{Deref, DerefMut};
+
+use bevy::ecs::entity::EntityHashSet;
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Rearranges row entities within `parent`'s children so they match `order`.
+///
+/// Non-row children keep their positions. Rows are written into the slots currently occupied by rows.
+fn reorder_for_each_rows(parent: Entity, order: Vec<Entity>, world: &mut World)
+{
+    let Some(children) = world.get::<Children>(parent) else { return };
+
+    // Ignore rows that are not children of the parent (e.g. if they were despawned or moved manually).
+    let mut rows: EntityHashSet = children.iter().copied().collect();
+    let order: Vec<Entity> = order.into_iter().filter(|e| rows.contains(e)).collect();
+    rows.clear();
+    rows.extend(order.iter().copied());
+
+    let mut next_row = order.iter();
+    let new_children: Vec<Entity> = children
+        .iter()
+        .map(|child| match rows.contains(child) {
+            true => *next_row.next().unwrap(),
+            false => *child,
+        })
+        .collect();
+
+    if new_children.iter().eq(children.iter()) {
+        return;
+    }
+
+    world.entity_mut(parent).replace_children(&new_children);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Reactive component inserted on rows spawned by [`UiBuilderForEachExt::for_each`].
+///
+/// Contains a copy of the collection item the row represents. It is mutated whenever the item with the row's key
+/// changes, so you can use `entity_mutation::<ForEachItem<I>>(row_entity)` to refresh the row.
+#[derive(Debug, Clone, PartialEq, Deref, DerefMut)]
+pub struct ForEachItem<I>(pub I);
+
+impl<I: Send + Sync + 'static> ReactComponent for ForEachItem<I> {}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Extension trait for spawning one scene per item of a reactive collection.
+pub trait UiBuilderForEachExt
+{
+    /// Spawns the `scene` as a child of the current entity for every item in the `React<C>` component on
+    /// `source`, and keeps the children in sync with the collection.
+    ///
+    /// Rows are identified by the `key` of their items. When the collection is mutated:
+    /// - Rows are spawned for new keys, and the `edit` callback is invoked on each new row's root node.
+    /// - Rows whose keys were removed are despawned.
+    /// - Existing rows are rearranged to match the collection order. Other children of the current entity keep
+    ///   their positions.
+    ///
+    /// Each row has a [`ForEachItem<I>`] reactive component that is updated when its item changes.
+    /**
+    ```rust
+    #[derive(ReactComponent, Deref)]
+    struct Inventory(Vec<Item>);
+
+    l.edit("items", |l| {
+        l.for_each::<Inventory, _, _>(player, ("main.cob", "item_row"), |item| item.id, |l, item| {
+            let row = l.id();
+            l.get("name").update_text(item.name.clone());
+            l.get("count").update_on(
+                entity_mutation::<ForEachItem<Item>>(row),
+                move |id: UpdateId, mut e: TextEditor, items: Reactive<ForEachItem<Item>>| {
+                    write_text!(e, *id, "x{}", items.get(row)?.count);
+                    OK
+                },
+            );
+        });
+    });
+    ```
+    */
+    ///
+    /// If the same key appears more than once, only the first item with that key will have a row.
+    ///
+    /// Does nothing if the current entity doesn't exist.
+    fn for_each<C, I, K>(
+        &mut self,
+        source: Entity,
+        scene: impl Into<SceneRef>,
+        key: impl Fn(&I) -> K + Send + Sync + 'static,
+        edit: impl for<'a> FnMut(&mut LoadedScene<'a, UiBuilder<'a, Entity>>, &I) + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        C: ReactComponent + Deref<Target = Vec<I>>,
+        I: Clone + PartialEq + Send + Sync + 'static,
+        K: Eq + Hash + Send + Sync + 'static;
+}
+
+impl UiBuilderForEachExt for UiBuilder<'_, Entity>
+{
+    fn for_each<C, I, K>(
+        &mut self,
+        source: Entity,
+        scene: impl Into<SceneRef>,
+        key: impl Fn(&I) -> K + Send + Sync + 'static,
+        mut edit: impl for<'a> FnMut(&mut LoadedScene<'a, UiBuilder<'a, Entity>>, &I) + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        C: ReactComponent + Deref<Target = Vec<I>>,
+        I: Clone + PartialEq + Send + Sync + 'static,
+        K: Eq + Hash + Send + Sync + 'static,
+    {
+        let scene = scene.into();
+        let mut rows: HashMap<K, Entity> = HashMap::default();
+
+        self.update_on(
+            (entity_insertion::<C>(source), entity_mutation::<C>(source)),
+            move |id: UpdateId,
+                  mut c: Commands,
+                  mut s: ResMut<SceneLoader>,
+                  sources: Reactive<C>,
+                  mut items: ReactiveMut<ForEachItem<I>>| {
+                let parent = *id;
+                let collection = sources.get(source)?;
+
+                // Match items to existing rows, spawning new rows as needed.
+                let mut prev_rows = std::mem::take(&mut rows);
+                let mut order = Vec::with_capacity(collection.len());
+                for item in collection.iter() {
+                    let item_key = (key)(item);
+                    if rows.contains_key(&item_key) {
+                        continue;
+                    }
+
+                    let row = match prev_rows.remove(&item_key) {
+                        Some(row) if c.get_entity(row).is_some() => {
+                            items.set_if_neq(&mut c, row, ForEachItem(item.clone()));
+                            row
+                        }
+                        _ => {
+                            let mut row = Entity::PLACEHOLDER;
+                            c.ui_builder(parent)
+                                .load_scene_and_edit(scene.clone(), &mut s, |l| {
+                                    row = l.id();
+                                    l.insert_reactive(ForEachItem(item.clone()));
+                                    (edit)(l, item);
+                                });
+                            row
+                        }
+                    };
+
+                    order.push(row);
+                    rows.insert(item_key, row);
+                }
+
+                // Despawn rows that are no longer in the collection.
+                for (_, dead) in prev_rows.drain() {
+                    let Some(ec) = c.get_entity(dead) else { continue };
+                    ec.despawn_recursive();
+                }
+
+                // Rearrange rows to match the collection order.
+                c.queue(move |world: &mut World| reorder_for_each_rows(parent, order, world));
+
+                OK
+            },
+        )
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+impl<'a> UiBuilderForEachExt for LoadedScene<'a, UiBuilder<'a, Entity>>
+{
+    fn for_each<C, I, K>(
+        &mut self,
+        source: Entity,
+        scene: impl Into<SceneRef>,
+        key: impl Fn(&I) -> K + Send + Sync + 'static,
+        edit: impl for<'b> FnMut(&mut LoadedScene<'b, UiBuilder<'b, Entity>>, &I) + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        C: ReactComponent + Deref<Target = Vec<I>>,
+        I: Clone + PartialEq + Send + Sync + 'static,
+        K: Eq + Hash + Send + Sync + 'static,
+    {
+        self.deref_mut()
+            .for_each::<C, I, K>(source, scene, key, edit);
+        self
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
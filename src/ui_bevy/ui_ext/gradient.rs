@@ -0,0 +1,357 @@
+use bevy::asset::embedded_asset;
+use bevy::color::ColorToComponents;
+use bevy::ecs::component::ComponentId;
+use bevy::ecs::world::DeferredWorld;
+use bevy::prelude::*;
+use bevy::render::render_resource::{AsBindGroup, ShaderRef, ShaderType};
+use smallvec::SmallVec;
+
+use crate::prelude::*;
+use crate::sickle::{DynamicStylePostUpdate, Lerp};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// The maximum number of stops a gradient can have. Extra stops are ignored.
+pub const MAX_GRADIENT_STOPS: usize = 8;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Lerps two lists element-wise. The shorter list is padded with its last element.
+fn lerp_padded<T: Lerp + Copy>(from: &[T], to: &[T], t: f32) -> Vec<T>
+{
+    (0..from.len().max(to.len()))
+        .filter_map(|i| {
+            let a = from.get(i).or(from.last()).copied();
+            let b = to.get(i).or(to.last()).copied();
+            match (a, b) {
+                (Some(a), Some(b)) => Some(a.lerp(b, t)),
+                (a, b) => a.or(b),
+            }
+        })
+        .collect()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn warn_invalid_background_gradient(world: DeferredWorld, entity: Entity, _: ComponentId)
+{
+    let Some(gradient) = world.get::<BackgroundGradient>(entity) else { return };
+    gradient.0.warn_if_invalid(entity);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn warn_invalid_border_gradient(world: DeferredWorld, entity: Entity, _: ComponentId)
+{
+    let Some(gradient) = world.get::<BorderGradient>(entity) else { return };
+    gradient.0.warn_if_invalid(entity);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn update_gradient_materials(
+    mut c: Commands,
+    mut materials: ResMut<Assets<UiGradientMaterial>>,
+    mut removed_backgrounds: RemovedComponents<BackgroundGradient>,
+    mut removed_borders: RemovedComponents<BorderGradient>,
+    changed: Query<Entity, Or<(Changed<BackgroundGradient>, Changed<BorderGradient>)>>,
+    gradients: Query<(
+        Option<&BackgroundGradient>,
+        Option<&BorderGradient>,
+        Option<&MaterialNode<UiGradientMaterial>>,
+    )>,
+)
+{
+    let mut entities: SmallVec<[Entity; 16]> = changed
+        .iter()
+        .chain(removed_backgrounds.read())
+        .chain(removed_borders.read())
+        .collect();
+    entities.sort_unstable();
+    entities.dedup();
+
+    for entity in entities {
+        // Entity was despawned.
+        let Ok((background, border, node)) = gradients.get(entity) else { continue };
+
+        if background.is_none() && border.is_none() {
+            if node.is_some() {
+                c.entity(entity)
+                    .remove::<MaterialNode<UiGradientMaterial>>();
+            }
+            continue;
+        }
+
+        // Each entity owns its material so gradients can be animated independently.
+        let material = UiGradientMaterial::new(background.map(|g| &g.0), border.map(|g| &g.0));
+        match node.and_then(|n| materials.get_mut(&n.0)) {
+            Some(existing) => *existing = material,
+            None => {
+                c.entity(entity)
+                    .try_insert(MaterialNode(materials.add(material)));
+            }
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// The shape of a [`UiGradient`].
+#[derive(Reflect, Default, Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum GradientKind
+{
+    /// Colors change along a line through the node's center, rotated by [`UiGradient::angle`].
+    #[default]
+    Linear,
+    /// Colors change outward from the node's center, in an ellipse that touches the node's corners.
+    Radial,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A color gradient for [`BackgroundGradient`] and [`BorderGradient`].
+///
+/// Colors are interpolated in linear RGB space.
+#[derive(Reflect, Default, Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct UiGradient
+{
+    /// Defaults to [`GradientKind::Linear`].
+    #[reflect(default)]
+    pub kind: GradientKind,
+    /// Direction of linear gradients in degrees, clockwise from 'to top' (like CSS `linear-gradient`).
+    ///
+    /// For example, `0` goes from the bottom to the top and `90` goes from left to right. Defaults to `0`.
+    #[reflect(default)]
+    pub angle: f32,
+    /// The colors of the gradient. At most [`MAX_GRADIENT_STOPS`] are used.
+    pub stops: Vec<Color>,
+    /// The position of each stop along the gradient, where `0` is the start and `1` is the end.
+    ///
+    /// Positions should be increasing. If empty or if the length doesn't match `stops`, then the stops are
+    /// spaced evenly (with a warning if the length doesn't match).
+    #[reflect(default)]
+    pub positions: Vec<f32>,
+}
+
+impl UiGradient
+{
+    /// Gets the position of each stop, with even spacing if [`Self::positions`] doesn't match [`Self::stops`].
+    pub fn resolved_positions(&self) -> Vec<f32>
+    {
+        if self.positions.len() == self.stops.len() {
+            return self.positions.clone();
+        }
+        let last = self.stops.len().saturating_sub(1).max(1) as f32;
+        (0..self.stops.len()).map(|i| i as f32 / last).collect()
+    }
+
+    /// Warns about stops and positions that won't be used as written.
+    ///
+    /// Called when a gradient component is inserted rather than when its material is rebuilt. Animation frames
+    /// always have matching positions (see [`Lerp`]), so animating a gradient doesn't repeat the positions warning.
+    fn warn_if_invalid(&self, entity: Entity)
+    {
+        if self.stops.len() > MAX_GRADIENT_STOPS {
+            tracing::warn!(
+                "ignoring extra stops in gradient with {} stops on {entity:?}; only {MAX_GRADIENT_STOPS} are \
+                supported",
+                self.stops.len()
+            );
+        }
+        if !self.positions.is_empty() && self.positions.len() != self.stops.len() {
+            tracing::warn!(
+                "gradient on {entity:?} has {} positions but {} stops; spacing stops evenly instead",
+                self.positions.len(),
+                self.stops.len()
+            );
+        }
+    }
+}
+
+impl Lerp for UiGradient
+{
+    /// Stops are lerped pairwise. If the stop counts differ, the shorter list is padded with its last stop.
+    ///
+    /// If either gradient has positions, then the [resolved positions](UiGradient::resolved_positions) are lerped
+    /// the same way, so the result has one position per stop.
+    fn lerp(&self, to: Self, t: f32) -> Self
+    {
+        let positions = match self.positions.is_empty() && to.positions.is_empty() {
+            true => Vec::default(),
+            false => lerp_padded(&self.resolved_positions(), &to.resolved_positions(), t),
+        };
+
+        Self {
+            kind: if t < 0.5 { self.kind } else { to.kind },
+            angle: self.angle.lerp(to.angle, t),
+            stops: lerp_padded(&self.stops, &to.stops, t),
+            positions,
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Component and animatable loadable for drawing a gradient behind a node.
+///
+/// Rendered with [`UiGradientMaterial`]. The gradient fills the node's padding box and border area (unless a
+/// [`BorderGradient`] is set).
+///
+/// A [`BackgroundColor`] on the same node is still drawn alongside the gradient, so leave it unset. The node's
+/// [`BorderRadius`] is ignored; the gradient always fills the node's full rectangle.
+///
+/// Example:
+/// ```rust
+/// #scenes
+/// "panel"
+///     BackgroundGradient{angle:90 stops:[#1E3C72 #2A5298]}
+///
+/// "button"
+///     Animated<BackgroundGradient>{
+///         idle:{angle:90 stops:[#1E3C72 #2A5298]}
+///         hover:{angle:90 stops:[#2A5298 #6DD5FA]}
+///         hover_with:{duration:0.2}
+///     }
+/// ```
+#[derive(Component, AnimatedNewtype, Reflect, Default, Debug, Clone, PartialEq)]
+#[component(on_insert = warn_invalid_background_gradient)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct BackgroundGradient(pub UiGradient);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Component and animatable loadable for drawing a gradient in a node's border.
+///
+/// Requires a non-zero border set in the node's layout (e.g. `FlexNode{border:{top:2px ...}}`). Rendered with
+/// [`UiGradientMaterial`], so it doesn't need a [`BorderColor`]. As with [`BackgroundGradient`], the node's
+/// [`BorderRadius`] is ignored.
+///
+/// Example:
+/// ```rust
+/// #scenes
+/// "button"
+///     FlexNode{border:{top:2px bottom:2px left:2px right:2px}}
+///     BorderGradient{kind:Radial stops:[#FFD700 #FF8C00] positions:[0.3 1]}
+/// ```
+#[derive(Component, AnimatedNewtype, Reflect, Default, Debug, Clone, PartialEq)]
+#[component(on_insert = warn_invalid_border_gradient)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct BorderGradient(pub UiGradient);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// GPU representation of a [`UiGradient`]. Must match `Gradient` in `gradient.wgsl`.
+#[derive(ShaderType, Default, Debug, Clone)]
+struct GradientUniform
+{
+    colors: [Vec4; MAX_GRADIENT_STOPS],
+    /// Stop positions packed four per vector.
+    positions: [Vec4; MAX_GRADIENT_STOPS / 4],
+    /// Zero if the gradient is unset.
+    count: u32,
+    kind: u32,
+    angle: f32,
+}
+
+impl GradientUniform
+{
+    fn new(gradient: &UiGradient) -> Self
+    {
+        let mut uniform = Self {
+            kind: match gradient.kind {
+                GradientKind::Linear => 0,
+                GradientKind::Radial => 1,
+            },
+            angle: gradient.angle,
+            ..default()
+        };
+        let positions = gradient.resolved_positions();
+        for (i, (color, position)) in gradient
+            .stops
+            .iter()
+            .zip(positions)
+            .take(MAX_GRADIENT_STOPS)
+            .enumerate()
+        {
+            uniform.colors[i] = LinearRgba::from(*color).to_vec4();
+            uniform.positions[i / 4][i % 4] = position;
+            uniform.count += 1;
+        }
+        uniform
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// UI material used to render [`BackgroundGradient`] and [`BorderGradient`].
+///
+/// Inserted and updated automatically as [`MaterialNode<UiGradientMaterial>`](MaterialNode) when either gradient
+/// is added to a node.
+#[derive(Asset, TypePath, AsBindGroup, Default, Debug, Clone)]
+pub struct UiGradientMaterial
+{
+    #[uniform(0)]
+    background: GradientUniform,
+    #[uniform(1)]
+    border: GradientUniform,
+}
+
+impl UiGradientMaterial
+{
+    fn new(background: Option<&UiGradient>, border: Option<&UiGradient>) -> Self
+    {
+        Self {
+            background: background.map(GradientUniform::new).unwrap_or_default(),
+            border: border.map(GradientUniform::new).unwrap_or_default(),
+        }
+    }
+}
+
+impl UiMaterial for UiGradientMaterial
+{
+    fn fragment_shader() -> ShaderRef
+    {
+        "embedded://bevy_cobweb_ui/gradient.wgsl".into()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) struct UiGradientExtPlugin;
+
+impl Plugin for UiGradientExtPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        embedded_asset!(app, "src/ui_bevy/ui_ext", "gradient.wgsl");
+
+        if !app.is_plugin_added::<UiMaterialPlugin<UiGradientMaterial>>() {
+            app.add_plugins(UiMaterialPlugin::<UiGradientMaterial>::default());
+        }
+
+        app.register_type::<GradientKind>()
+            .register_animatable::<BackgroundGradient>()
+            .register_animatable::<BorderGradient>()
+            .add_systems(PostUpdate, update_gradient_materials.after(DynamicStylePostUpdate));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
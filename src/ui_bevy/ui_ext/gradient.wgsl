@@ -0,0 +1,67 @@
+#import bevy_ui::ui_vertex_output::UiVertexOutput
+
+const KIND_LINEAR: u32 = 0u;
+const KIND_RADIAL: u32 = 1u;
+
+struct Gradient {
+    colors: array<vec4<f32>, 8>,
+    // Stop positions packed four per vector.
+    positions: array<vec4<f32>, 2>,
+    // Number of stops. Zero if the gradient is unset.
+    count: u32,
+    kind: u32,
+    // Degrees clockwise from 'to top'.
+    angle: f32,
+};
+
+@group(1) @binding(0) var<uniform> background: Gradient;
+@group(1) @binding(1) var<uniform> border: Gradient;
+
+fn stop_position(g: Gradient, i: u32) -> f32 {
+    return g.positions[i / 4u][i % 4u];
+}
+
+// Gets the position of a fragment along the gradient line.
+fn gradient_t(g: Gradient, uv: vec2<f32>, size: vec2<f32>) -> f32 {
+    if g.kind == KIND_RADIAL {
+        // Ellipse that touches the node's corners.
+        return length((uv - 0.5) * 2.0) / sqrt(2.0);
+    }
+
+    // CSS convention: the gradient line passes through the center, and its ends are where perpendicular lines
+    // touch the node's corners.
+    let angle = radians(g.angle);
+    let dir = vec2<f32>(sin(angle), -cos(angle));
+    let len = abs(size.x * dir.x) + abs(size.y * dir.y);
+    return dot((uv - 0.5) * size, dir) / max(len, 0.0001) + 0.5;
+}
+
+fn sample_gradient(g: Gradient, t: f32) -> vec4<f32> {
+    if g.count == 0u {
+        return vec4<f32>(0.0);
+    }
+    if t <= stop_position(g, 0u) {
+        return g.colors[0];
+    }
+    for (var i = 1u; i < g.count; i++) {
+        let start = stop_position(g, i - 1u);
+        let end = stop_position(g, i);
+        if t <= end {
+            let f = select(1.0, (t - start) / (end - start), end > start);
+            return mix(g.colors[i - 1u], g.colors[i], clamp(f, 0.0, 1.0));
+        }
+    }
+    return g.colors[g.count - 1u];
+}
+
+@fragment
+fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
+    // Border widths are in UV space. Order is left, right, top, bottom.
+    let b = in.border_widths;
+    let in_border = in.uv.x < b.x || in.uv.x > 1.0 - b.y || in.uv.y < b.z || in.uv.y > 1.0 - b.w;
+
+    if in_border && border.count > 0u {
+        return sample_gradient(border, gradient_t(border, in.uv, in.size));
+    }
+    return sample_gradient(background, gradient_t(background, in.uv, in.size));
+}
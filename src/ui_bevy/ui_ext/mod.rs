@@ -1,15 +1,21 @@
 mod component_wrappers;
+mod gradient;
 mod image_node;
 mod opacity;
 mod plugin;
+mod sprite_animation;
 mod style_field_wrappers;
 mod style_wrappers;
 mod text;
+mod transform;
 
 pub use component_wrappers::*;
+pub use gradient::*;
 pub use image_node::*;
 pub use opacity::*;
 pub use plugin::*;
+pub use sprite_animation::*;
 pub use style_field_wrappers::*;
 pub use style_wrappers::*;
 pub use text::*;
+pub use transform::*;
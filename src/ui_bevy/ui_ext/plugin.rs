@@ -19,7 +19,10 @@ impl Plugin for StyleExtPlugin
             // overwrite style fields correctly.
             .add_plugins(UiStyleFieldWrappersPlugin)
             .add_plugins(UiTextExtPlugin)
-            .add_plugins(ImageNodeExtPlugin);
+            .add_plugins(ImageNodeExtPlugin)
+            .add_plugins(SpriteAnimationPlugin)
+            .add_plugins(NodeTransformExtPlugin)
+            .add_plugins(UiGradientExtPlugin);
     }
 }
 
@@ -0,0 +1,334 @@
+use bevy::prelude::*;
+use smol_str::SmolStr;
+
+use crate::prelude::*;
+use crate::sickle::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Wraps a step count back and forth over `count` frames without repeating the end frames.
+fn ping_pong(step: u64, count: u64) -> u64
+{
+    if count <= 1 {
+        return 0;
+    }
+    let period = 2 * (count - 1);
+    let pos = step % period;
+    match pos < count {
+        true => pos,
+        false => period - pos,
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the frame to display for a clip after `step` frames have elapsed.
+///
+/// Returns `(atlas index, finished, completed loop iterations)`.
+///
+/// This is used by [`SpriteAnimation`]. It is only public for testing.
+#[doc(hidden)]
+pub fn clip_frame(clip: &SpriteClip, step: u64) -> (usize, bool, u32)
+{
+    let count = clip.start.abs_diff(clip.end) as u64 + 1;
+    // Ping-pong iterations are single passes, which share their end frames.
+    let pass = (count - 1).max(1);
+
+    let (offset, finished, iteration) = match clip.loop_type {
+        AnimationLoop::None => (step.min(count - 1), step >= count, 0),
+        AnimationLoop::Continous => (step % count, false, step / count),
+        AnimationLoop::Times(times, reset) => {
+            let times = times as u64;
+            match step >= count * times {
+                true => (if reset { 0 } else { count - 1 }, true, times),
+                false => (step % count, false, step / count),
+            }
+        }
+        AnimationLoop::PingPongContinous => (ping_pong(step, count), false, step / pass),
+        AnimationLoop::PingPong(times) => {
+            let times = times as u64;
+            match step > pass * times {
+                true => (ping_pong(pass * times, count), true, times),
+                false => (ping_pong(step, count), false, step / pass),
+            }
+        }
+    };
+
+    let offset = offset as usize;
+    let index = match clip.start <= clip.end {
+        true => clip.start + offset,
+        false => clip.start - offset,
+    };
+    (index, finished, iteration as u32)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Selects the clip that should play. See [`SpriteAnimation`] for the priority order.
+///
+/// It is only public for testing.
+#[doc(hidden)]
+pub fn select_clip(clips: &[SpriteClip], states: &[PseudoState], interaction: Option<&Interaction>) -> Option<usize>
+{
+    let hovered = matches!(interaction, Some(Interaction::Hovered | Interaction::Pressed));
+    let pressed = matches!(interaction, Some(Interaction::Pressed));
+
+    clips
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, clip)| {
+            let specificity = match &clip.state {
+                Some(selector) => match selector.matches(states) {
+                    true => selector.specificity(),
+                    false => return None,
+                },
+                None => 0,
+            };
+            let interaction_rank = match clip.interaction {
+                None => 0,
+                Some(SpriteClipInteraction::Hover) if hovered => 1,
+                Some(SpriteClipInteraction::Press) if pressed => 2,
+                _ => return None,
+            };
+            Some((specificity, interaction_rank, idx))
+        })
+        .max()
+        .map(|(_, _, idx)| idx)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn tick_sprite_animations(
+    mut c: Commands,
+    mut clocks: AnimationClocks,
+    mut animators: Query<(
+        Entity,
+        &mut SpriteAnimator,
+        &mut ImageNode,
+        Option<&PseudoStates>,
+        Option<&Interaction>,
+    )>,
+)
+{
+    for (entity, mut animator, mut image, states, interaction) in animators.iter_mut() {
+        let animator = &mut *animator;
+        let states = states.map(|s| s.get().as_slice()).unwrap_or_default();
+
+        let selected = select_clip(&animator.clips, states, interaction);
+        if selected != animator.current {
+            animator.current = selected;
+            animator.elapsed = 0.;
+            animator.iteration = 0;
+            animator.finished = false;
+            if let Some(clip) = selected.map(|idx| &animator.clips[idx]) {
+                AnimationLifecycle::Started.trigger(&mut c, entity, Some(clip.name.clone()));
+            }
+        } else if !animator.finished {
+            animator.elapsed += clocks.delta(entity).as_secs_f32();
+        }
+
+        // If no clip matches, the image stays on its current frame.
+        let Some(clip) = animator.current.map(|idx| &animator.clips[idx]) else { continue };
+        let fps = clip.fps.unwrap_or(animator.fps);
+        let step = match fps > 0. {
+            true => (animator.elapsed * fps).floor() as u64,
+            false => 0,
+        };
+        let (index, finished, iteration) = clip_frame(clip, step);
+
+        if iteration > animator.iteration {
+            animator.iteration = iteration;
+            AnimationLifecycle::LoopIterationFinished(iteration).trigger(&mut c, entity, Some(clip.name.clone()));
+        }
+        if finished && !animator.finished {
+            animator.finished = true;
+            AnimationLifecycle::Finished.trigger(&mut c, entity, Some(clip.name.clone()));
+        }
+
+        // Avoid triggering change detection if the frame didn't change.
+        if image.texture_atlas.as_ref().map(|a| a.index) == Some(index) {
+            continue;
+        }
+        let Some(atlas) = image.texture_atlas.as_mut() else {
+            tracing::warn!("failed animating sprite on {entity:?}; ImageNode has no texture atlas");
+            continue;
+        };
+        atlas.index = index;
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Runtime state of a [`SpriteAnimation`].
+#[derive(Component, Debug)]
+struct SpriteAnimator
+{
+    fps: f32,
+    clips: Vec<SpriteClip>,
+
+    current: Option<usize>,
+    elapsed: f32,
+    /// The number of completed loop iterations of the current clip.
+    iteration: u32,
+    finished: bool,
+
+    /// Whether [`Interactive`] was applied by the animation, so it can be reverted.
+    applied_interactive: bool,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Interaction that activates a [`SpriteClip`].
+#[derive(Reflect, Default, Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpriteClipInteraction
+{
+    /// The clip plays while the entity is hovered or pressed.
+    #[default]
+    Hover,
+    /// The clip plays while the entity is pressed.
+    Press,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A named range of frames in a texture atlas.
+#[derive(Reflect, Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpriteClip
+{
+    /// The clip name. Sent in [`AnimationStarted`], [`LoopIterationFinished`], and [`AnimationFinished`] events.
+    pub name: SmolStr,
+    /// The atlas index of the first frame.
+    pub start: usize,
+    /// The atlas index of the last frame (inclusive).
+    ///
+    /// If less than `start`, then the frames play backward.
+    pub end: usize,
+    /// Overrides [`SpriteAnimation::fps`] for this clip.
+    #[reflect(default)]
+    pub fps: Option<f32>,
+    /// Controls how the clip repeats. Ping-pong loops play every odd pass in reverse without repeating the end
+    /// frames.
+    ///
+    /// Defaults to playing once and stopping on the last frame.
+    #[reflect(default)]
+    pub loop_type: AnimationLoop,
+    /// Pseudo states the entity must be in for this clip to play.
+    #[reflect(default)]
+    pub state: Option<PseudoStateSelector>,
+    /// Interaction on the entity that makes this clip play.
+    #[reflect(default)]
+    pub interaction: Option<SpriteClipInteraction>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Instruction loadable for playing sprite-sheet animations on a [`LoadedImageNode`] with a texture atlas.
+///
+/// Each tick the clip to play is selected from the clips whose `state` and `interaction` match the entity. Clips
+/// with more specific [`PseudoStateSelector`]s are preferred, then clips with a matching `interaction` (press
+/// over hover), then clips that appear later in the list. The selected clip restarts every time it is selected
+/// anew. If no clip matches, the image stays on its current frame.
+///
+/// Frame timing uses the entity's [`AnimationClock`].
+///
+/// Emits [`AnimationStarted`] when a clip starts, [`LoopIterationFinished`] when a looping clip completes an
+/// iteration, and [`AnimationFinished`] when a clip ends, with the clip name.
+///
+/// Example:
+/// ```rust
+/// #scenes
+/// "coin"
+///     LoadedImageNode{image:"coin.png" atlas:{alias:"coin" index:0}}
+///     SpriteAnimation{
+///         fps:12
+///         clips:[
+///             {name:"idle" start:0 end:5 loop_type:Continous}
+///             {name:"hover" start:6 end:9 loop_type:PingPongContinous interaction:Hover}
+///             {name:"disabled" start:10 end:10 state:[Disabled]}
+///         ]
+///     }
+/// ```
+#[derive(Reflect, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpriteAnimation
+{
+    /// Frames per second of clips that don't set their own `fps`.
+    ///
+    /// Defaults to `12`.
+    #[reflect(default = "SpriteAnimation::default_fps")]
+    pub fps: f32,
+    /// The animation clips.
+    #[reflect(default)]
+    pub clips: Vec<SpriteClip>,
+}
+
+impl SpriteAnimation
+{
+    fn default_fps() -> f32
+    {
+        12.
+    }
+}
+
+impl Default for SpriteAnimation
+{
+    fn default() -> Self
+    {
+        Self { fps: Self::default_fps(), clips: Vec::default() }
+    }
+}
+
+impl Instruction for SpriteAnimation
+{
+    fn apply(self, entity: Entity, world: &mut World)
+    {
+        // Revert first in case the new clips don't need interactivity.
+        Self::revert(entity, world);
+
+        let Ok(mut emut) = world.get_entity_mut(entity) else { return };
+        // Only apply Interactive if the entity isn't already interactive.
+        let needs_interactive =
+            self.clips.iter().any(|c| c.interaction.is_some()) && !emut.contains::<Interaction>();
+        emut.insert(SpriteAnimator {
+            fps: self.fps,
+            clips: self.clips,
+            current: None,
+            elapsed: 0.,
+            iteration: 0,
+            finished: false,
+            applied_interactive: needs_interactive,
+        });
+
+        if needs_interactive {
+            Interactive.apply(entity, world);
+        }
+    }
+
+    fn revert(entity: Entity, world: &mut World)
+    {
+        let Ok(mut emut) = world.get_entity_mut(entity) else { return };
+        let Some(animator) = emut.take::<SpriteAnimator>() else { return };
+
+        // Don't strip interactivity the entity needs for other reasons.
+        if animator.applied_interactive {
+            Interactive::revert(entity, world);
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) struct SpriteAnimationPlugin;
+
+impl Plugin for SpriteAnimationPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.register_instruction_type::<SpriteAnimation>()
+            .add_systems(PostUpdate, tick_sprite_animations.after(DynamicStylePostUpdate));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
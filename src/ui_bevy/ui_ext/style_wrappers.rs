@@ -906,6 +906,15 @@ impl Instruction for AbsoluteNode
     }
 }
 
+impl StaticAttribute for AbsoluteNode
+{
+    type Value = Self;
+    fn construct(value: Self::Value) -> Self
+    {
+        value
+    }
+}
+
 //-------------------------------------------------------------------------------------------------------------------
 
 /// Instruction loadable for flexbox-controlled nodes.
@@ -1081,6 +1090,15 @@ impl Instruction for FlexNode
     }
 }
 
+impl StaticAttribute for FlexNode
+{
+    type Value = Self;
+    fn construct(value: Self::Value) -> Self
+    {
+        value
+    }
+}
+
 //-------------------------------------------------------------------------------------------------------------------
 
 /// Instruction loadable that toggles the [`Node::display`] field.
@@ -1161,8 +1179,8 @@ impl Plugin for StyleWrappersPlugin
 {
     fn build(&self, app: &mut App)
     {
-        app.register_instruction_type::<AbsoluteNode>()
-            .register_instruction_type::<FlexNode>()
+        app.register_static::<AbsoluteNode>()
+            .register_static::<FlexNode>()
             .register_static::<DisplayControl>()
             .add_systems(PostUpdate, DisplayControl::refresh.before(UiSystem::Prepare));
     }
@@ -0,0 +1,278 @@
+use bevy::prelude::TransformSystem::TransformPropagate;
+use bevy::prelude::*;
+use bevy::ui::UiSystem;
+
+use crate::prelude::*;
+use crate::sickle::Lerp;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resolves a `Val` to physical pixels. Only `Px` and `Percent` are supported; other values resolve to zero.
+fn resolve_val(val: Val, size: f32, inverse_scale_factor: f32) -> f32
+{
+    match val {
+        Val::Px(px) => px / inverse_scale_factor,
+        Val::Percent(percent) => size * percent / 100.,
+        _ => 0.,
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn apply_ui_transforms(
+    mut removed_scales: RemovedComponents<NodeScale>,
+    mut removed_rotations: RemovedComponents<NodeRotation>,
+    mut p: ParamSet<(
+        Query<&mut Transform, With<Node>>,
+        Query<
+            (
+                &ComputedNode,
+                &mut Transform,
+                Option<&NodeScale>,
+                Option<&NodeRotation>,
+                Option<&NodeTranslation>,
+                Option<&NodeTransformOrigin>,
+            ),
+            Or<(With<NodeScale>, With<NodeRotation>, With<NodeTranslation>)>,
+        >,
+    )>,
+)
+{
+    // Reset nodes that lost their scale or rotation. Layout only resets translations.
+    let mut transforms = p.p0();
+    for entity in removed_scales.read().chain(removed_rotations.read()) {
+        let Ok(mut transform) = transforms.get_mut(entity) else { continue };
+        transform.scale = Vec3::ONE;
+        transform.rotation = Quat::IDENTITY;
+    }
+
+    // Layout sets the translation of every node each tick, so offsets don't accumulate.
+    for (node, mut transform, scale, rotation, translation, origin) in p.p1().iter_mut() {
+        let size = node.size();
+        let inverse_scale_factor = node.inverse_scale_factor();
+
+        let scale = scale.map(|s| s.0).unwrap_or(1.);
+        let rotation = Quat::from_rotation_z(rotation.map(|r| r.0.to_radians()).unwrap_or(0.));
+        let offset = translation
+            .map(|t| {
+                Vec2::new(
+                    resolve_val(t.x, size.x, inverse_scale_factor),
+                    resolve_val(t.y, size.y, inverse_scale_factor),
+                )
+            })
+            .unwrap_or_default();
+        let origin = origin.cloned().unwrap_or_default();
+        let pivot = Vec2::new(
+            resolve_val(origin.x, size.x, inverse_scale_factor),
+            resolve_val(origin.y, size.y, inverse_scale_factor),
+        ) - size / 2.;
+
+        // Rotate and scale around the pivot instead of the node center.
+        let pivot_shift = pivot - (rotation * (pivot * scale).extend(0.)).truncate();
+
+        transform.scale = Vec3::new(scale, scale, 1.);
+        transform.rotation = rotation;
+        transform.translation += (offset + pivot_shift).extend(0.);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Component and animatable loadable for uniformly scaling a UI node and its children.
+///
+/// Scaling is visual only and doesn't affect layout. The node is scaled around its [`NodeTransformOrigin`].
+///
+/// Example (pop on hover):
+/// ```rust
+/// Animated<NodeScale>{
+///     idle:1
+///     hover:1.1
+///     hover_with:{duration:0.25 ease:Spring{stiffness:300 damping:15}}
+///     unhover_with:{duration:0.15 ease:OutQuad}
+/// }
+/// ```
+#[derive(Component, AnimatedNewtype, Reflect, Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct NodeScale(pub f32);
+
+impl Default for NodeScale
+{
+    fn default() -> Self
+    {
+        Self(1.)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Component and animatable loadable for rotating a UI node and its children, in degrees.
+///
+/// Positive values rotate clockwise. Rotation is visual only and doesn't affect layout. The node is rotated around
+/// its [`NodeTransformOrigin`].
+#[derive(Component, AnimatedNewtype, Reflect, Default, Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct NodeRotation(pub f32);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Component and animatable loadable for offsetting a UI node and its children from their layout position.
+///
+/// The offset is visual only and doesn't affect layout. Only [`Val::Px`] and [`Val::Percent`] are supported.
+/// Percentages are relative to the node's own size (like CSS `translate`).
+#[derive(Component, Reflect, Default, Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct NodeTranslation
+{
+    #[reflect(default)]
+    pub x: Val,
+    #[reflect(default)]
+    pub y: Val,
+}
+
+impl Lerp for NodeTranslation
+{
+    fn lerp(&self, to: Self, t: f32) -> Self
+    {
+        Self { x: self.x.lerp(to.x, t), y: self.y.lerp(to.y, t) }
+    }
+}
+
+impl Instruction for NodeTranslation
+{
+    fn apply(self, entity: Entity, world: &mut World)
+    {
+        let _ = world.get_entity_mut(entity).map(|mut e| {
+            e.insert(self);
+        });
+    }
+
+    fn revert(entity: Entity, world: &mut World)
+    {
+        let _ = world.get_entity_mut(entity).map(|mut e| {
+            e.remove::<Self>();
+        });
+    }
+}
+
+impl StaticAttribute for NodeTranslation
+{
+    type Value = Self;
+    fn construct(value: Self::Value) -> Self
+    {
+        value
+    }
+}
+
+impl ResponsiveAttribute for NodeTranslation {}
+impl AnimatedAttribute for NodeTranslation
+{
+    fn get_value(entity: Entity, world: &World) -> Option<Self::Value>
+    {
+        world.get::<Self>(entity).cloned()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Component and loadable for setting the point that [`NodeScale`] and [`NodeRotation`] transform around.
+///
+/// Measured from the node's top-left corner. Only [`Val::Px`] and [`Val::Percent`] are supported. Defaults to the
+/// node's center (`50%`, `50%`).
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct NodeTransformOrigin
+{
+    #[reflect(default = "NodeTransformOrigin::center")]
+    pub x: Val,
+    #[reflect(default = "NodeTransformOrigin::center")]
+    pub y: Val,
+}
+
+impl NodeTransformOrigin
+{
+    fn center() -> Val
+    {
+        Val::Percent(50.)
+    }
+}
+
+impl Default for NodeTransformOrigin
+{
+    fn default() -> Self
+    {
+        Self { x: Self::center(), y: Self::center() }
+    }
+}
+
+impl Instruction for NodeTransformOrigin
+{
+    fn apply(self, entity: Entity, world: &mut World)
+    {
+        let _ = world.get_entity_mut(entity).map(|mut e| {
+            e.insert(self);
+        });
+    }
+
+    fn revert(entity: Entity, world: &mut World)
+    {
+        let _ = world.get_entity_mut(entity).map(|mut e| {
+            e.remove::<Self>();
+        });
+    }
+}
+
+impl StaticAttribute for NodeTransformOrigin
+{
+    type Value = Self;
+    fn construct(value: Self::Value) -> Self
+    {
+        value
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// System set in `PostUpdate` where [`NodeScale`], [`NodeRotation`], and [`NodeTranslation`] are applied to node
+/// transforms.
+#[derive(SystemSet, Debug, Hash, Eq, PartialEq, Copy, Clone)]
+pub struct NodeTransformSet;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) struct NodeTransformExtPlugin;
+
+impl Plugin for NodeTransformExtPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.register_animatable::<NodeScale>()
+            .register_animatable::<NodeRotation>()
+            .register_animatable::<NodeTranslation>()
+            .register_static::<NodeTransformOrigin>()
+            .configure_sets(
+                PostUpdate,
+                NodeTransformSet
+                    .after(UiSystem::Layout)
+                    .before(TransformPropagate),
+            )
+            .add_systems(PostUpdate, apply_ui_transforms.in_set(NodeTransformSet));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,126 @@
+//! Animation clocks inherited from ancestors.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy_cobweb_ui::prelude::*;
+use bevy_cobweb_ui::sickle::{Ease, *};
+
+use crate::cob::helpers::prepare_headless_app;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Spawns `parent -> child`, where the child has a long-running `FlexGrow` animation named `"a"`. Time advances by
+/// 50ms per update.
+fn prepare_tree(clock: Option<AnimationClock>) -> (App, Entity, Entity)
+{
+    let mut app = prepare_headless_app();
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(50)));
+
+    let mut parent = app.world_mut().spawn(Node::default());
+    if let Some(clock) = clock {
+        parent.insert(clock);
+    }
+    let parent = parent.id();
+    let child = app.world_mut().spawn(Node::default()).set_parent(parent).id();
+    Keyframes::<FlexGrow> {
+        name: Some("a".into()),
+        frames: vec![
+            Keyframe { time: 0., value: 0., ease: Ease::Linear },
+            Keyframe { time: 100., value: 1., ease: Ease::Linear },
+        ],
+        ..default()
+    }
+    .apply(child, app.world_mut());
+    app.update();
+    app.update();
+
+    (app, parent, child)
+}
+
+/// Runs `updates` updates and returns how much the child's animation advanced.
+fn advance(app: &mut App, child: Entity, updates: u32) -> f32
+{
+    let elapsed = |app: &App| {
+        app.world()
+            .get::<KeyframeAnimations>(child)
+            .unwrap()
+            .elapsed("a")
+            .unwrap()
+    };
+    let start = elapsed(app);
+    for _ in 0..updates {
+        app.update();
+    }
+    elapsed(app) - start
+}
+
+fn assert_close(value: f32, expected: f32)
+{
+    assert!((value - expected).abs() < 1e-4, "expected {expected}, got {value}");
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn clock_defaults_to_real_time()
+{
+    let (mut app, _, child) = prepare_tree(None);
+    assert_close(advance(&mut app, child, 4), 0.2);
+
+    // Pausing virtual time doesn't affect real time.
+    app.world_mut().resource_mut::<Time<Virtual>>().pause();
+    assert_close(advance(&mut app, child, 4), 0.2);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn clock_inherits_pause_and_scale()
+{
+    let clock = AnimationClock { time: AnimationTime::Virtual, scale: 2. };
+    let (mut app, _, child) = prepare_tree(Some(clock));
+    assert_close(advance(&mut app, child, 4), 0.4);
+
+    app.world_mut().resource_mut::<Time<Virtual>>().pause();
+    assert_close(advance(&mut app, child, 4), 0.);
+
+    app.world_mut().resource_mut::<Time<Virtual>>().unpause();
+    app.update();
+    assert_close(advance(&mut app, child, 4), 0.4);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn clock_cache_invalidation()
+{
+    let clock = AnimationClock { time: AnimationTime::Virtual, scale: 2. };
+    let (mut app, parent, child) = prepare_tree(Some(clock));
+    assert!(!app.world().resource::<AnimationClockCache>().is_empty());
+
+    // Changing the clock.
+    app.world_mut().get_mut::<AnimationClock>(parent).unwrap().scale = 0.5;
+    assert_close(advance(&mut app, child, 4), 0.1);
+
+    // Reparenting to a node without a clock.
+    let other = app.world_mut().spawn(Node::default()).id();
+    app.world_mut().entity_mut(child).set_parent(other);
+    assert_close(advance(&mut app, child, 4), 0.2);
+
+    // Moving back, then removing the clock.
+    app.world_mut().entity_mut(child).set_parent(parent);
+    assert_close(advance(&mut app, child, 4), 0.1);
+    app.world_mut().entity_mut(parent).remove::<AnimationClock>();
+    assert_close(advance(&mut app, child, 4), 0.2);
+
+    // Despawned entities don't stay in the cache.
+    app.world_mut().entity_mut(parent).despawn_recursive();
+    app.world_mut().entity_mut(other).despawn_recursive();
+    app.update();
+    app.update();
+    assert!(app.world().resource::<AnimationClockCache>().is_empty());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,110 @@
+//! Observer events for animation lifecycles.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy_cobweb_ui::prelude::*;
+use bevy_cobweb_ui::sickle::{Ease, *};
+use smol_str::SmolStr;
+
+use crate::cob::helpers::prepare_headless_app;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Lifecycle events received by observers, in order.
+#[derive(Resource, Default)]
+struct Lifecycles(Vec<(Option<SmolStr>, AnimationLifecycle)>);
+
+/// Makes an app that advances time by 50ms per update and records lifecycle events on `entity`.
+fn prepare_lifecycle_app() -> (App, Entity)
+{
+    let mut app = prepare_headless_app();
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(50)))
+        .init_resource::<Lifecycles>();
+
+    let entity = app.world_mut().spawn(Node::default()).id();
+    app.world_mut()
+        .entity_mut(entity)
+        .observe(|trigger: Trigger<AnimationStarted>, mut l: ResMut<Lifecycles>| {
+            assert_eq!(trigger.entity, trigger.entity());
+            l.0.push((trigger.name.clone(), AnimationLifecycle::Started));
+        })
+        .observe(|trigger: Trigger<LoopIterationFinished>, mut l: ResMut<Lifecycles>| {
+            assert_eq!(trigger.entity, trigger.entity());
+            l.0.push((trigger.name.clone(), AnimationLifecycle::LoopIterationFinished(trigger.iteration)));
+        })
+        .observe(|trigger: Trigger<AnimationFinished>, mut l: ResMut<Lifecycles>| {
+            assert_eq!(trigger.entity, trigger.entity());
+            l.0.push((trigger.name.clone(), AnimationLifecycle::Finished));
+        });
+
+    (app, entity)
+}
+
+fn run_for(app: &mut App, seconds: f32)
+{
+    for _ in 0..(seconds / 0.05).ceil() as u32 {
+        app.update();
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn animated_lifecycle_events()
+{
+    let (mut app, entity) = prepare_lifecycle_app();
+    Animated::<FlexGrow> {
+        name: Some("grow".into()),
+        idle: 1.,
+        enter_idle_with: Some(AnimationConfig::new(0.2, Ease::Linear, 0.)),
+        idle_secondary: Some(2.),
+        idle_loop: Some(LoopedAnimationConfig::new(0.2, Ease::Linear, 0., 0., AnimationLoop::Times(2, false))),
+        ..default()
+    }
+    .apply(entity, app.world_mut());
+    run_for(&mut app, 1.5);
+
+    let name = Some(SmolStr::from("grow"));
+    let lifecycles = &app.world().resource::<Lifecycles>().0;
+    // The enter animation and the idle loop are separate animations.
+    assert_eq!(lifecycles, &vec![
+        (name.clone(), AnimationLifecycle::Started),
+        (name.clone(), AnimationLifecycle::Finished),
+        (name.clone(), AnimationLifecycle::Started),
+        (name.clone(), AnimationLifecycle::LoopIterationFinished(1)),
+        (name.clone(), AnimationLifecycle::LoopIterationFinished(2)),
+        (name, AnimationLifecycle::Finished),
+    ]);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn keyframes_lifecycle_events()
+{
+    let (mut app, entity) = prepare_lifecycle_app();
+    Keyframes::<FlexGrow> {
+        name: Some("grow".into()),
+        frames: vec![
+            Keyframe { time: 0., value: 0., ease: Ease::Linear },
+            Keyframe { time: 0.2, value: 1., ease: Ease::Linear },
+        ],
+        loop_type: AnimationLoop::Times(2, false),
+        ..default()
+    }
+    .apply(entity, app.world_mut());
+    run_for(&mut app, 1.);
+
+    let name = Some(SmolStr::from("grow"));
+    let lifecycles = &app.world().resource::<Lifecycles>().0;
+    assert_eq!(lifecycles, &vec![
+        (name.clone(), AnimationLifecycle::Started),
+        (name.clone(), AnimationLifecycle::LoopIterationFinished(1)),
+        (name.clone(), AnimationLifecycle::LoopIterationFinished(2)),
+        (name, AnimationLifecycle::Finished),
+    ]);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,113 @@
+//! Binding loadables with component sources.
+
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+use bevy_cobweb_ui::prelude::*;
+
+use crate::cob::helpers::prepare_headless_app;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(ReactComponent, Reflect, Default)]
+struct Palette
+{
+    color: Color,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn prepare_app() -> App
+{
+    let mut app = prepare_headless_app();
+    app.register_bind_component::<Palette>();
+    app.update();
+    app
+}
+
+fn insert_palette(app: &mut App, entity: Entity, color: Color)
+{
+    app.world_mut()
+        .run_system_once(move |mut c: Commands| {
+            c.react().insert(entity, Palette { color });
+        })
+        .unwrap();
+    app.update();
+}
+
+fn set_palette(app: &mut App, entity: Entity, color: Color)
+{
+    app.world_mut()
+        .run_system_once(move |mut c: Commands, mut palettes: ReactiveMut<Palette>| {
+            palettes.get_mut(&mut c, entity).unwrap().color = color;
+        })
+        .unwrap();
+    app.update();
+}
+
+fn background(app: &App, entity: Entity) -> Option<Color>
+{
+    app.world().get::<BackgroundColor>(entity).map(|c| c.0)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn bind_component_source()
+{
+    let mut app = prepare_app();
+    let red = Color::srgb(1., 0., 0.);
+    let green = Color::srgb(0., 1., 0.);
+    let blue = Color::srgb(0., 0., 1.);
+
+    let parent = app.world_mut().spawn(Node::default()).id();
+    let child = app.world_mut().spawn(Node::default()).set_parent(parent).id();
+    Bind::<BackgroundColor>::new("Palette.color").apply(child, app.world_mut());
+    app.update();
+
+    // No source yet, so the node keeps its default color.
+    assert_eq!(background(&app, child), Some(BackgroundColor::default().0));
+
+    // The source is resolved from an ancestor when it is inserted later.
+    insert_palette(&mut app, parent, red);
+    assert_eq!(background(&app, child), Some(red));
+
+    // Mutations are tracked.
+    set_palette(&mut app, parent, blue);
+    assert_eq!(background(&app, child), Some(blue));
+
+    // A closer source replaces the ancestor source.
+    insert_palette(&mut app, child, green);
+    assert_eq!(background(&app, child), Some(green));
+    set_palette(&mut app, parent, red);
+    assert_eq!(background(&app, child), Some(green));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn bind_cleanup_on_despawn()
+{
+    let mut app = prepare_app();
+    let red = Color::srgb(1., 0., 0.);
+
+    let parent = app.world_mut().spawn(Node::default()).id();
+    insert_palette(&mut app, parent, red);
+    let num_entities = app.world().entities().len();
+
+    let child = app.world_mut().spawn(Node::default()).set_parent(parent).id();
+    Bind::<BackgroundColor>::new("Palette.color").apply(child, app.world_mut());
+    app.update();
+    assert_eq!(background(&app, child), Some(red));
+
+    // Despawning the bound entity cleans up the binding's systems.
+    app.world_mut().entity_mut(child).despawn_recursive();
+    app.update();
+    assert_eq!(app.world().entities().len(), num_entities);
+
+    // The source can still be mutated.
+    set_palette(&mut app, parent, Color::BLACK);
+    assert_eq!(app.world().entities().len(), num_entities);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,99 @@
+//! Viewport breakpoint pseudo states.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_cobweb_ui::prelude::*;
+use bevy_cobweb_ui::sickle::*;
+
+use crate::cob::helpers::prepare_headless_app;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn set_viewport_width(app: &mut App, width: f32)
+{
+    let mut windows = app
+        .world_mut()
+        .query_filtered::<&mut Window, With<PrimaryWindow>>();
+    windows
+        .single_mut(app.world_mut())
+        .resolution
+        .set(width, 600.);
+    app.update();
+}
+
+fn custom_states(app: &App, entity: Entity) -> Vec<String>
+{
+    let Some(states) = app.world().get::<PseudoStates>(entity) else { return vec![] };
+    states
+        .get()
+        .iter()
+        .filter_map(|s| match s {
+            PseudoState::Custom(name) => Some(name.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn breakpoints_default()
+{
+    let mut app = prepare_headless_app();
+    let entity = app.world_mut().spawn(Node::default()).id();
+    BreakpointStates::default().apply(entity, app.world_mut());
+
+    set_viewport_width(&mut app, 500.);
+    assert_eq!(custom_states(&app, entity), vec!["compact"]);
+
+    // The threshold is inclusive.
+    set_viewport_width(&mut app, 800.);
+    assert_eq!(custom_states(&app, entity), vec!["wide"]);
+
+    // The previous state is removed when shrinking again.
+    set_viewport_width(&mut app, 799.);
+    assert_eq!(custom_states(&app, entity), vec!["compact"]);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn breakpoints_per_entity()
+{
+    let mut app = prepare_headless_app();
+    let entity = app.world_mut().spawn(Node::default()).id();
+    let default_entity = app.world_mut().spawn(Node::default()).id();
+    BreakpointStates {
+        // Out of order to check that the largest matching `min_width` is selected.
+        breakpoints: vec![
+            Breakpoint::new("large", 1000.),
+            Breakpoint::new("small", 200.),
+            Breakpoint::new("medium", 600.),
+        ],
+    }
+    .apply(entity, app.world_mut());
+    BreakpointStates::default().apply(default_entity, app.world_mut());
+
+    // No breakpoint matches below the smallest `min_width`.
+    set_viewport_width(&mut app, 100.);
+    assert!(custom_states(&app, entity).is_empty());
+    assert_eq!(custom_states(&app, default_entity), vec!["compact"]);
+
+    set_viewport_width(&mut app, 700.);
+    assert_eq!(custom_states(&app, entity), vec!["medium"]);
+    assert_eq!(custom_states(&app, default_entity), vec!["compact"]);
+
+    set_viewport_width(&mut app, 1200.);
+    assert_eq!(custom_states(&app, entity), vec!["large"]);
+    assert_eq!(custom_states(&app, default_entity), vec!["wide"]);
+
+    set_viewport_width(&mut app, 300.);
+    assert_eq!(custom_states(&app, entity), vec!["small"]);
+
+    // Reverting removes the active state.
+    BreakpointStates::revert(entity, app.world_mut());
+    app.update();
+    assert!(custom_states(&app, entity).is_empty());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -1,6 +1,9 @@
+use bevy::ecs::world::Command;
+use bevy::prelude::*;
 use bevy_cobweb_ui::prelude::cob::*;
+use bevy_cobweb_ui::prelude::*;
 
-use super::helpers::{test_cob, test_cob_fail};
+use super::helpers::{prepare_headless_app, test_cob, test_cob_fail, update_until};
 
 //-------------------------------------------------------------------------------------------------------------------
 
@@ -51,6 +54,48 @@ C<D>::X{ a: 1, b: 2 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
+#[test]
+fn commands_condition()
+{
+    let res = test_cob(
+        b"#commands{platform:\"wasm\"}
+A
+",
+    );
+    let CobSection::Commands(commands) = &res.sections[0] else { unreachable!() };
+    assert!(commands.condition.is_some());
+    assert_eq!(commands.entries.len(), 1);
+
+    let res = test_cob(
+        b"#commands{ cfg:[\"a\" \"b\"] not:{build:\"release\"} }
+A
+B
+",
+    );
+    let CobSection::Commands(commands) = &res.sections[0] else { unreachable!() };
+    let Some(condition) = &commands.condition else { unreachable!() };
+    assert_eq!(condition.entries.len(), 2);
+    assert_eq!(commands.entries.len(), 2);
+
+    let res = test_cob(
+        b"#commands
+A
+",
+    );
+    let CobSection::Commands(commands) = &res.sections[0] else { unreachable!() };
+    assert!(commands.condition.is_none());
+
+    // Condition must be attached to the section tag
+    test_cob_fail(
+        b"#commands {platform:\"wasm\"}
+A
+",
+        b"{platform:\"wasm\"}\nA\n",
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 #[test]
 fn commands_errors()
 {
@@ -83,3 +128,56 @@ A
 }
 
 //-------------------------------------------------------------------------------------------------------------------
+
+/// Records which conditional `#commands` sections were applied.
+#[derive(Resource, Default)]
+struct RecordedConditions(Vec<String>);
+
+#[derive(Reflect, Default, Debug, Clone, PartialEq)]
+struct RecordCondition(String);
+
+impl Command for RecordCondition
+{
+    fn apply(self, world: &mut World)
+    {
+        world.resource_mut::<RecordedConditions>().0.push(self.0);
+    }
+}
+
+#[test]
+fn commands_condition_evaluation()
+{
+    let mut app = prepare_headless_app();
+    app.init_resource::<RecordedConditions>()
+        .register_command_type::<RecordCondition>()
+        .enable_cob_cfg("test_cfg")
+        .insert_resource(Locale::new("fr-FR").unwrap())
+        .load("command_conditions.cob");
+    update_until(&mut app, |w| {
+        w.resource::<RecordedConditions>()
+            .0
+            .iter()
+            .any(|r| r == "done")
+    });
+
+    let recorded = &app.world().resource::<RecordedConditions>().0;
+    let applied = |name: &str| recorded.iter().any(|r| r == name);
+    assert!(applied("always"));
+    assert!(applied("cfg"));
+    assert!(!applied("missing_cfg"));
+    assert!(applied("cfg_any"));
+    assert!(applied("not"));
+    assert!(!applied("not_cfg"));
+    assert_eq!(
+        applied("platform"),
+        cfg!(any(target_os = "linux", target_os = "macos", target_os = "windows"))
+    );
+    assert!(!applied("missing_platform"));
+    assert_eq!(applied("debug"), cfg!(debug_assertions));
+    assert_eq!(applied("release"), !cfg!(debug_assertions));
+    assert!(applied("locale"));
+    assert!(!applied("missing_locale"));
+    assert!(!applied("all"));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,192 @@
+//! Pseudo state selectors in COB and selector priority.
+
+use bevy::prelude::*;
+use bevy_cobweb_ui::prelude::cob::*;
+use bevy_cobweb_ui::prelude::*;
+use bevy_cobweb_ui::sickle::{ManagePseudoStateExt, PseudoState};
+use serde::Deserialize;
+
+use super::helpers::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn parse_selector(raw: &str) -> PseudoStateSelector
+{
+    let value = match CobValue::try_parse(CobFill::default(), test_span(raw)) {
+        Ok((Some(value), _, _)) => value,
+        Err(err) => panic!("{raw}, ERR={err:?}"),
+        _ => panic!("{raw}, TRY FAILED"),
+    };
+    PseudoStateSelector::deserialize(&value).unwrap()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn write_selector(selector: &PseudoStateSelector) -> String
+{
+    let value = CobValue::extract(selector).unwrap();
+    let mut buff = Vec::<u8>::default();
+    let mut serializer = DefaultRawSerializer::new(&mut buff);
+    value.write_to(&mut serializer).unwrap();
+    String::from_utf8(buff).unwrap()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tests that raw COB deserializes to `expected`, and that the selector survives a round trip through COB.
+fn test_selector(raw: &str, reserialized: &str, expected: PseudoStateSelector)
+{
+    let parsed = parse_selector(raw);
+    assert_eq!(parsed, expected);
+
+    let written = write_selector(&parsed);
+    assert_eq!(written, reserialized);
+    assert_eq!(parse_selector(&written), expected);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn selector_state_list()
+{
+    test_selector(
+        "[Selected]",
+        "[Selected]",
+        PseudoStateSelector::All(vec![PseudoStateSelector::State(PseudoState::Selected)]),
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn selector_all()
+{
+    // Lists are shorthand for `All`, so the prefix is dropped when written back.
+    test_selector(
+        "All[Selected Not(Disabled)]",
+        "[Selected Not(Disabled)]",
+        PseudoStateSelector::All(vec![
+            PseudoStateSelector::State(PseudoState::Selected),
+            PseudoStateSelector::not([PseudoState::Disabled]),
+        ]),
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn selector_any()
+{
+    test_selector(
+        "Any[Checked Custom(\"highlighted\")]",
+        "Any[Checked Custom(\"highlighted\")]",
+        PseudoStateSelector::any([PseudoState::Checked, PseudoState::Custom("highlighted".into())]),
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn selector_not()
+{
+    test_selector("Not(Disabled)", "Not(Disabled)", PseudoStateSelector::not([PseudoState::Disabled]));
+    test_selector(
+        "Not[Disabled Checked]",
+        "Not[Disabled Checked]",
+        PseudoStateSelector::not([PseudoState::Disabled, PseudoState::Checked]),
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn selector_specificity()
+{
+    assert_eq!(parse_selector("[Selected]").specificity(), 1);
+    assert_eq!(parse_selector("All[Selected Not(Disabled)]").specificity(), 2);
+    assert_eq!(parse_selector("Any[Checked Custom(\"highlighted\")]").specificity(), 1);
+    assert_eq!(parse_selector("Not(Disabled)").specificity(), 1);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn selector_priority()
+{
+    let mut app = prepare_headless_app();
+    app.update();
+
+    let green = Color::srgb(0., 1., 0.);
+    let red = Color::srgb(1., 0., 0.);
+    let blue = Color::srgb(0., 0., 1.);
+
+    let entity = app.world_mut().spawn(Node::default()).id();
+    let world = app.world_mut();
+    ControlRoot.apply(entity, world);
+    for (selector, value) in [
+        ("All[Selected Not(Disabled)]", green),
+        ("[Selected]", red),
+        ("Any[Checked Custom(\"highlighted\")]", blue),
+    ] {
+        let attribute = Static::<BackgroundColor> { name: None, state: Some(parse_selector(selector)), value };
+        attribute.apply(entity, world);
+    }
+
+    let set_states = |app: &mut App, add: &[PseudoState], remove: &[PseudoState]| {
+        let mut emut = app.world_mut().entity_mut(entity);
+        for state in add {
+            emut.add_pseudo_state(state.clone());
+        }
+        for state in remove {
+            emut.remove_pseudo_state(state.clone());
+        }
+        app.update();
+        app.update();
+        app.world().get::<BackgroundColor>(entity).map(|c| c.0)
+    };
+
+    // The most specific selector wins even though it was inserted first.
+    let color = set_states(&mut app, &[PseudoState::Selected, PseudoState::Checked], &[]);
+    assert_eq!(color, Some(green));
+
+    // Ties are won by the selector that was inserted last.
+    let color = set_states(&mut app, &[PseudoState::Disabled], &[]);
+    assert_eq!(color, Some(blue));
+
+    let color = set_states(&mut app, &[], &[PseudoState::Checked]);
+    assert_eq!(color, Some(red));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn selector_normalize()
+{
+    let normalized = |raw: &str| {
+        let mut selector = parse_selector(raw);
+        selector.normalize();
+        selector
+    };
+
+    // Single-selector `All` and `Any` are unified with the inner selector.
+    let selected = PseudoStateSelector::State(PseudoState::Selected);
+    assert_eq!(normalized("Selected"), selected);
+    assert_eq!(normalized("[Selected]"), selected);
+    assert_eq!(normalized("All[Selected Selected]"), selected);
+    assert_eq!(normalized("Any[[Selected]]"), selected);
+    assert_eq!(normalized("Not(Selected)"), PseudoStateSelector::not([PseudoState::Selected]));
+
+    // Inner selectors are sorted.
+    assert_eq!(normalized("[Selected Not([Disabled])]"), normalized("All[Not(Disabled) Selected]"));
+
+    // Attributes inserted with equivalent selectors replace each other.
+    let mut attributes = NodeAttributes::default();
+    let attribute = || NodeAttribute::new_static::<BackgroundColor>(None, Color::BLACK);
+    assert!(attributes.insert(Some(parse_selector("[Selected]")), attribute()).is_none());
+    assert!(attributes.insert(Some(selected.clone()), attribute()).is_some());
+    assert!(attributes
+        .get_with(Some(&parse_selector("Any[Selected]")), std::any::TypeId::of::<BackgroundColor>())
+        .is_some());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,92 @@
+use bevy::ecs::system::RunSystemOnce;
+use bevy::ecs::world::Command;
+use bevy::prelude::*;
+use bevy_cobweb_ui::prelude::cob::*;
+use bevy_cobweb_ui::prelude::*;
+
+use super::helpers::{prepare_headless_app, test_cob, update_until};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn theme_tokens()
+{
+    let mut res = test_cob(
+        b"#scenes
+\"a\"
+    A(@primary_bg)
+    B{ a: @a b: [1 @b] }
+    C{ a: 1 }
+",
+    );
+    let CobSection::Scenes(scenes) = &mut res.sections[0] else { unreachable!() };
+    let CobSceneLayerEntry::Loadable(a) = &mut scenes.scenes[0].entries[0] else { unreachable!() };
+    let CobLoadableVariant::Tuple(tuple) = &a.variant else { unreachable!() };
+    let CobValue::ThemeToken(token) = &tuple.entries[0] else { unreachable!() };
+    assert_eq!(token.name.as_str(), "primary_bg");
+    assert_eq!(a.resolve_theme_tokens(None), Ok(true));
+
+    let CobSceneLayerEntry::Loadable(b) = &mut scenes.scenes[0].entries[1] else { unreachable!() };
+    assert_eq!(b.resolve_theme_tokens(None), Ok(true));
+
+    let CobSceneLayerEntry::Loadable(c) = &mut scenes.scenes[0].entries[2] else { unreachable!() };
+    assert_eq!(c.resolve_theme_tokens(None), Ok(false));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn set_theme_refreshes_loadables()
+{
+    let mut app = prepare_headless_app();
+    app.register_theme("light", "theme_light.cob")
+        .register_theme("dark", "theme_dark.cob")
+        .load("theme_light.cob")
+        .load("theme_dark.cob")
+        .load("themed.cob");
+    update_until(&mut app, |w| *w.resource::<State<LoadState>>() == LoadState::Done);
+
+    let entity = app
+        .world_mut()
+        .run_system_once(|mut c: Commands, mut s: ResMut<SceneLoader>| {
+            let mut entity = Entity::PLACEHOLDER;
+            c.ui_root()
+                .load_scene_and_edit(("themed.cob", "root"), &mut s, |l| {
+                    entity = l.id();
+                });
+            entity
+        })
+        .unwrap();
+    update_until(&mut app, |w| w.get::<BackgroundColor>(entity).is_some());
+
+    let colors = |app: &App| {
+        let world = app.world();
+        (world.get::<BackgroundColor>(entity).map(|c| c.0), world.get::<BorderColor>(entity).map(|c| c.0))
+    };
+
+    // Tokens resolve with the first registered theme.
+    let (bg, border) = colors(&app);
+    assert_eq!(bg, Some(Color::srgb(1., 0., 0.)));
+    assert_eq!(border, Some(Color::srgb(0., 1., 0.)));
+
+    // Switching themes re-applies themed loadables to the existing entity. The dark theme has no `$border`, so
+    // `BorderColor` is reverted and not re-applied. The node's required `BorderColor` is restored when the scene
+    // node is reinitialized.
+    SetTheme("dark".into()).apply(app.world_mut());
+    app.update();
+    assert_eq!(app.world().resource::<CobThemes>().active(), Some("dark"));
+
+    let (bg, border) = colors(&app);
+    assert_eq!(bg, Some(Color::srgb(0., 0., 1.)));
+    assert_eq!(border, Some(BorderColor::default().0));
+
+    // Switching back restores the token.
+    SetTheme("light".into()).apply(app.world_mut());
+    app.update();
+
+    let (bg, border) = colors(&app);
+    assert_eq!(bg, Some(Color::srgb(1., 0., 0.)));
+    assert_eq!(border, Some(Color::srgb(0., 1., 0.)));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -1,5 +1,14 @@
+use std::time::{Duration, Instant};
+
+use bevy::app::Plugins;
+use bevy::log::LogPlugin;
 use bevy::prelude::*;
+use bevy::render::settings::WgpuSettings;
+use bevy::render::RenderPlugin;
+use bevy::window::ExitCondition;
+use bevy::winit::WinitPlugin;
 use bevy_cobweb_ui::prelude::cob::*;
+use bevy_cobweb_ui::prelude::*;
 
 use crate::cob::helpers::*;
 
@@ -15,6 +24,69 @@ pub fn prepare_test_app() -> App
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Makes an app with [`CobwebUiPlugin`] that runs without an OS window or GPU.
+///
+/// The primary window entity is still spawned because the editor expects one. Assets are loaded from
+/// `tests/assets`.
+pub fn prepare_headless_app() -> App
+{
+    prepare_headless_app_with(())
+}
+
+/// Makes a headless app like [`prepare_headless_app`] with extra plugins.
+///
+/// Plugins can't be added after the app is finished, so extra plugins must be passed in here.
+pub fn prepare_headless_app_with<M>(plugins: impl Plugins<M>) -> App
+{
+    prepare_headless_app_at("tests/assets", plugins)
+}
+
+/// Makes a headless app like [`prepare_headless_app_with`] that loads assets from `asset_dir`.
+pub fn prepare_headless_app_at<M>(asset_dir: impl Into<String>, plugins: impl Plugins<M>) -> App
+{
+    let mut app = App::new();
+    app.add_plugins(
+        DefaultPlugins
+            .build()
+            .disable::<LogPlugin>()
+            .disable::<WinitPlugin>()
+            .set(WindowPlugin {
+                primary_window: Some(Window::default()),
+                exit_condition: ExitCondition::DontExit,
+                close_when_requested: false,
+            })
+            .set(RenderPlugin {
+                render_creation: WgpuSettings { backends: None, ..default() }.into(),
+                ..default()
+            })
+            .set(AssetPlugin { file_path: asset_dir.into(), ..default() }),
+    )
+    .add_plugins(CobwebUiPlugin)
+    .add_plugins(plugins);
+
+    // Normally done by the app runner.
+    app.finish();
+    app.cleanup();
+    app
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Updates the app until `done` returns `true`.
+///
+/// Panics after 30 seconds, e.g. if an asset never finishes loading.
+pub fn update_until(app: &mut App, done: impl Fn(&World) -> bool)
+{
+    let start = Instant::now();
+    while !done(app.world()) {
+        assert!(start.elapsed() < Duration::from_secs(30), "timed out updating app");
+        app.update();
+        std::thread::sleep(Duration::from_millis(1));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 pub fn test_span(val: &str) -> Span
 {
     Span::new_extra(val, CobLocationMetadata { file: "test.cob" })
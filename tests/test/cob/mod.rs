@@ -5,7 +5,9 @@ mod cob_constants;
 mod cob_fill;
 mod cob_import;
 mod cob_manifest;
+mod cob_pseudo_state_selector;
 mod cob_scenes;
+mod cob_theme_tokens;
 mod serde;
 
 //mod reflection_bug;  // Uses serde_json which is no longer a dependency.
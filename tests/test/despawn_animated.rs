@@ -0,0 +1,118 @@
+//! Despawning UI trees after their exit animations.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy_cobweb_ui::prelude::*;
+use bevy_cobweb_ui::sickle::{Ease, *};
+
+use crate::cob::helpers::prepare_headless_app;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Animates `FlexGrow` over `duration` seconds.
+fn keyframes(duration: f32, loop_type: AnimationLoop, state: Option<PseudoStateSelector>) -> Keyframes<FlexGrow>
+{
+    Keyframes {
+        state,
+        frames: vec![
+            Keyframe { time: 0., value: 0., ease: Ease::Linear },
+            Keyframe { time: duration, value: 1., ease: Ease::Linear },
+        ],
+        loop_type,
+        ..default()
+    }
+}
+
+/// Spawns a root node with a child that has a `Dying` exit animation. Time advances by 50ms per update.
+///
+/// The root has a continuous animation that should not delay the despawn.
+fn prepare_tree(exit: Keyframes<FlexGrow>) -> (App, Entity, Entity)
+{
+    let mut app = prepare_headless_app();
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(50)));
+
+    let root = app.world_mut().spawn(Node::default()).id();
+    let child = app.world_mut().spawn(Node::default()).set_parent(root).id();
+    keyframes(0.2, AnimationLoop::Continous, None).apply(root, app.world_mut());
+    exit.apply(child, app.world_mut());
+    app.update();
+
+    (app, root, child)
+}
+
+fn run_for(app: &mut App, seconds: f32)
+{
+    for _ in 0..(seconds / 0.05).round() as u32 {
+        app.update();
+    }
+}
+
+fn exists(app: &App, entity: Entity) -> bool
+{
+    app.world().get_entity(entity).is_ok()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn despawn_after_exit_animation()
+{
+    let dying = Some(PseudoState::Dying.into());
+    let (mut app, root, child) = prepare_tree(keyframes(0.5, AnimationLoop::None, dying));
+
+    app.world_mut().commands().entity(root).despawn_animated();
+    app.update();
+    let states = app.world().get::<PseudoStates>(child).unwrap();
+    assert!(states.has(&PseudoState::Dying));
+
+    // The tree survives while the exit animation plays.
+    run_for(&mut app, 0.3);
+    assert!(exists(&app, root));
+    assert!(exists(&app, child));
+
+    // The tree is despawned once the exit animation finishes, well before the timeout.
+    run_for(&mut app, 0.5);
+    assert!(!exists(&app, root));
+    assert!(!exists(&app, child));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn despawn_after_timeout()
+{
+    // The exit animation never finishes.
+    let dying = Some(PseudoState::Dying.into());
+    let (mut app, root, child) = prepare_tree(keyframes(0.2, AnimationLoop::Continous, dying));
+
+    app.world_mut()
+        .commands()
+        .entity(root)
+        .despawn_animated_with_timeout(0.5);
+    app.update();
+
+    run_for(&mut app, 0.3);
+    assert!(exists(&app, root));
+
+    run_for(&mut app, 0.5);
+    assert!(!exists(&app, root));
+    assert!(!exists(&app, child));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn despawn_without_exit_animation()
+{
+    // Animations that don't require `Dying` are not waited on.
+    let (mut app, root, child) = prepare_tree(keyframes(0.2, AnimationLoop::Continous, None));
+
+    app.world_mut().commands().entity(root).despawn_animated();
+    run_for(&mut app, 0.2);
+    assert!(!exists(&app, root));
+    assert!(!exists(&app, child));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,125 @@
+//! Custom easing curves.
+
+use std::collections::HashSet;
+
+use bevy_cobweb_ui::sickle::{Ease, StepJump, ValueEasing};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn sample(ease: Ease) -> Vec<f32>
+{
+    (0..=100).map(|i| (i as f32 / 100.).ease(ease)).collect()
+}
+
+fn assert_endpoints(ease: Ease, start: f32)
+{
+    let (first, last) = (0f32.ease(ease), 1f32.ease(ease));
+    assert!((first - start).abs() < 1e-4, "{ease:?} starts at {first}");
+    assert!((last - 1.).abs() < 1e-4, "{ease:?} ends at {last}");
+}
+
+fn assert_monotonic(ease: Ease)
+{
+    let values = sample(ease);
+    for pair in values.windows(2) {
+        assert!(pair[1] >= pair[0] - 1e-5, "{ease:?} is not monotonic: {pair:?}");
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn ease_cubic_bezier()
+{
+    let ease = Ease::CubicBezier { x1: 0.25, y1: 0.1, x2: 0.25, y2: 1. };
+    assert_endpoints(ease, 0.);
+    assert_monotonic(ease);
+
+    // Equivalent to linear easing.
+    let linear = Ease::CubicBezier { x1: 0., y1: 0., x2: 1., y2: 1. };
+    for (i, value) in sample(linear).into_iter().enumerate() {
+        assert!((value - i as f32 / 100.).abs() < 1e-4, "{value}");
+    }
+
+    // Control points outside [0, 1] overshoot but still end at the endpoints.
+    let overshoot = Ease::CubicBezier { x1: 0.3, y1: -0.5, x2: 0.7, y2: 1.5 };
+    assert_endpoints(overshoot, 0.);
+    let values = sample(overshoot);
+    assert!(values.iter().any(|v| *v < 0.));
+    assert!(values.iter().any(|v| *v > 1.));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn ease_steps()
+{
+    let count = 4;
+    for (jump, start) in [
+        (StepJump::End, 0.),
+        (StepJump::None, 0.),
+        (StepJump::Start, 1. / 4.),
+        (StepJump::Both, 1. / 5.),
+    ] {
+        let ease = Ease::Steps { count, jump };
+        assert_endpoints(ease, start);
+        assert_monotonic(ease);
+    }
+
+    // Steps are held between jumps.
+    let ease = Ease::Steps { count, jump: StepJump::End };
+    assert_eq!(0.3f32.ease(ease), 0.25);
+    assert_eq!(0.49f32.ease(ease), 0.25);
+    assert_eq!(0.5f32.ease(ease), 0.5);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn ease_spring()
+{
+    // Critically damped and over-damped springs approach the target without overshooting.
+    for ease in [
+        Ease::Spring { stiffness: 100., damping: 20., mass: 1. },
+        Ease::Spring { stiffness: 100., damping: 40., mass: 1. },
+    ] {
+        assert_endpoints(ease, 0.);
+        assert_monotonic(ease);
+        assert!(sample(ease).iter().all(|v| *v <= 1. + 1e-5));
+    }
+
+    // Under-damped springs overshoot.
+    let ease = Ease::Spring { stiffness: 100., damping: 5., mass: 1. };
+    assert_endpoints(ease, 0.);
+    assert!(sample(ease).iter().any(|v| *v > 1.));
+
+    // Invalid springs fall back to linear easing.
+    let ease = Ease::Spring { stiffness: 0., damping: 5., mass: 1. };
+    assert_eq!(0.3f32.ease(ease), 0.3);
+    assert_eq!(ease.settle_time(), None);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn ease_eq_hash()
+{
+    let ease = Ease::Spring { stiffness: 100., damping: 5., mass: 1. };
+    let set: HashSet<Ease> = [
+        ease,
+        ease,
+        Ease::Spring { stiffness: 100., damping: 6., mass: 1. },
+        Ease::Steps { count: 2, jump: StepJump::End },
+        Ease::Steps { count: 2, jump: StepJump::Start },
+        Ease::Linear,
+        Ease::Linear,
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(set.len(), 5);
+
+    let nan = Ease::CubicBezier { x1: f32::NAN, y1: 0., x2: 1., y2: 1. };
+    assert_eq!(nan, nan);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,245 @@
+//! Resolving conflicts between unsaved editor changes and external file changes.
+
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+
+use bevy::ecs::world::Command;
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+use bevy_cobweb_ui::editor::*;
+use bevy_cobweb_ui::prelude::*;
+
+use crate::cob::helpers::{prepare_headless_app_at, update_until};
+use crate::editor_structure::{read_file, remote_request};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+const FILE: &str = "conflict.cob";
+
+const ORIGINAL: &str = "#scenes
+\"root\"
+    FlexNode{width:100px}
+
+    \"a\"
+        FlexNode{width:10px}
+";
+
+/// External change to a different node than [`edit_mine`].
+const THEIRS: &str = "#scenes
+\"root\"
+    FlexNode{width:100px}
+
+    \"a\"
+        FlexNode{width:30px}
+";
+
+/// External change to the same node as [`edit_mine`].
+const THEIRS_SAME_NODE: &str = "#scenes
+\"root\"
+    FlexNode{width:50px}
+
+    \"a\"
+        FlexNode{width:10px}
+";
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Counts [`EditorFileConflict`] events.
+#[derive(Resource, Default)]
+struct Conflicts(usize);
+
+/// Makes an app that loads [`ORIGINAL`] from a temporary asset directory. Returns the path to the file.
+fn prepare_conflict_app(name: &str) -> (App, PathBuf)
+{
+    let dir = std::env::temp_dir().join(format!("cob_editor_conflicts_{name}_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(FILE);
+    std::fs::write(&path, ORIGINAL).unwrap();
+
+    let mut app = prepare_headless_app_at(
+        dir.to_string_lossy(),
+        CobEditorRemotePlugin { addr: SocketAddr::from((Ipv4Addr::LOCALHOST, 0)), ..default() },
+    );
+    app.init_resource::<Conflicts>();
+    app.react(|rc| {
+        rc.on_persistent(broadcast::<EditorFileConflict>(), |mut conflicts: ResMut<Conflicts>| {
+            conflicts.0 += 1;
+        })
+    });
+    app.load(FILE);
+    update_until(&mut app, |w| *w.resource::<State<LoadState>>() == LoadState::Done);
+    assert_eq!(read_file(&mut app, FILE), (ORIGINAL.into(), false));
+
+    (app, path)
+}
+
+fn file() -> CobFile
+{
+    CobFile::try_new(FILE).unwrap()
+}
+
+fn conflicts(app: &App) -> usize
+{
+    app.world().resource::<Conflicts>().0
+}
+
+/// Makes an unsaved change to the root node.
+fn edit_mine(app: &mut App)
+{
+    let response = remote_request(app, EditorClientMessage::SetLoadable {
+        file: FILE.into(),
+        path: "root".into(),
+        loadable: "BackgroundColor(#FF0000)".into(),
+    });
+    assert_eq!(response, EditorServerMessage::Accepted);
+    let (content, unsaved) = read_file(app, FILE);
+    assert!(content.contains("BackgroundColor(#FF0000)"), "{content}");
+    assert!(unsaved);
+}
+
+/// Changes the file on disk and reloads it.
+fn edit_theirs(app: &mut App, path: &Path, content: &str)
+{
+    std::fs::write(path, content).unwrap();
+    app.world().resource::<AssetServer>().reload(FILE);
+}
+
+/// Changes the file on disk and waits for the conflict.
+fn make_conflict(app: &mut App, path: &Path, content: &str)
+{
+    let prev = conflicts(app);
+    edit_theirs(app, path, content);
+    update_until(app, |w| w.resource::<Conflicts>().0 > prev);
+}
+
+fn run(app: &mut App, command: impl Command)
+{
+    app.world_mut().commands().queue(command);
+    app.update();
+}
+
+fn resolve(app: &mut App, resolution: ConflictResolution)
+{
+    run(app, ResolveEditorConflict { file: file(), resolution });
+}
+
+fn save(app: &mut App)
+{
+    run(app, SaveEditor);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn conflict_take_external_without_unsaved_changes()
+{
+    let (mut app, path) = prepare_conflict_app("external");
+
+    // External changes replace the data if there are no unsaved changes.
+    edit_theirs(&mut app, &path, THEIRS);
+    let start = std::time::Instant::now();
+    while read_file(&mut app, FILE).0 != THEIRS {
+        assert!(start.elapsed() < std::time::Duration::from_secs(30), "timed out waiting for reload");
+    }
+    assert!(!read_file(&mut app, FILE).1);
+    assert_eq!(conflicts(&app), 0);
+
+    // The history was discarded with the old data.
+    run(&mut app, UndoEditor { file: file() });
+    assert_eq!(read_file(&mut app, FILE), (THEIRS.into(), false));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn conflict_keep_mine()
+{
+    let (mut app, path) = prepare_conflict_app("keep_mine");
+    edit_mine(&mut app);
+    make_conflict(&mut app, &path, THEIRS);
+
+    // The editor keeps its data while conflicted, and doesn't save over the external changes.
+    let (content, unsaved) = read_file(&mut app, FILE);
+    assert!(content.contains("BackgroundColor(#FF0000)"), "{content}");
+    assert!(unsaved);
+    save(&mut app);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), THEIRS);
+
+    resolve(&mut app, ConflictResolution::KeepMine);
+    let (content, unsaved) = read_file(&mut app, FILE);
+    assert!(content.contains("BackgroundColor(#FF0000)"), "{content}");
+    assert!(content.contains("10px"), "{content}");
+    assert!(unsaved);
+
+    // Undoing no longer reaches a saved state, since the saved file is now theirs.
+    run(&mut app, UndoEditor { file: file() });
+    let (content, unsaved) = read_file(&mut app, FILE);
+    assert_eq!(content, ORIGINAL);
+    assert!(unsaved);
+
+    // Saving overwrites the external changes.
+    run(&mut app, RedoEditor { file: file() });
+    save(&mut app);
+    let saved = std::fs::read_to_string(&path).unwrap();
+    assert!(saved.contains("BackgroundColor(#FF0000)"), "{saved}");
+    assert!(saved.contains("10px"), "{saved}");
+    assert!(!read_file(&mut app, FILE).1);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn conflict_take_theirs()
+{
+    let (mut app, path) = prepare_conflict_app("take_theirs");
+    edit_mine(&mut app);
+    make_conflict(&mut app, &path, THEIRS);
+
+    resolve(&mut app, ConflictResolution::TakeTheirs);
+    assert_eq!(read_file(&mut app, FILE), (THEIRS.into(), false));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn conflict_merge()
+{
+    let (mut app, path) = prepare_conflict_app("merge");
+    edit_mine(&mut app);
+    make_conflict(&mut app, &path, THEIRS);
+
+    // Changes to different nodes are merged.
+    resolve(&mut app, ConflictResolution::Merge);
+    let (content, unsaved) = read_file(&mut app, FILE);
+    assert!(content.contains("BackgroundColor(#FF0000)"), "{content}");
+    assert!(content.contains("30px"), "{content}");
+    assert!(unsaved);
+
+    save(&mut app);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), content);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn conflict_merge_same_node()
+{
+    let (mut app, path) = prepare_conflict_app("merge_same_node");
+    edit_mine(&mut app);
+    make_conflict(&mut app, &path, THEIRS_SAME_NODE);
+    let prev = conflicts(&app);
+
+    // Changes to the same node can't be merged, so the conflict remains.
+    resolve(&mut app, ConflictResolution::Merge);
+    assert_eq!(conflicts(&app), prev + 1);
+    let (content, unsaved) = read_file(&mut app, FILE);
+    assert!(content.contains("BackgroundColor(#FF0000)"), "{content}");
+    assert!(content.contains("100px"), "{content}");
+    assert!(unsaved);
+
+    resolve(&mut app, ConflictResolution::TakeTheirs);
+    assert_eq!(read_file(&mut app, FILE), (THEIRS_SAME_NODE.into(), false));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,177 @@
+//! Undo/redo history in the editor.
+
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+use bevy_cobweb_ui::editor::*;
+use bevy_cobweb_ui::prelude::*;
+
+use crate::cob::helpers::update_until;
+use crate::editor_widgets::{drag, prepare_editor_app, val_field};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+const FILE: &str = "editor_widgets.cob";
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks whether the test file has unsaved changes.
+#[derive(Resource, Default)]
+struct Unsaved(bool);
+
+/// Prepares the editor app and tracks its unsaved status. Files are saved to a temporary directory.
+fn prepare_history_app() -> (App, Entity)
+{
+    let (mut app, entity) = prepare_editor_app();
+    let save_dir = std::env::temp_dir().join(format!("cob_editor_history_{}", std::process::id()));
+    app.world_mut()
+        .resource_mut::<CobEditorSaveTargets>()
+        .set_resolver(move |file| Some(save_dir.join(file.as_str())));
+    app.init_resource::<Unsaved>();
+    app.react(|rc| {
+        rc.on_persistent(broadcast::<EditorFileUnsaved>(), |mut unsaved: ResMut<Unsaved>| unsaved.0 = true)
+    });
+    app.react(|rc| {
+        rc.on_persistent(broadcast::<EditorFileSaved>(), |mut unsaved: ResMut<Unsaved>| unsaved.0 = false)
+    });
+    (app, entity)
+}
+
+fn width(app: &App, entity: Entity) -> Val
+{
+    app.world().get::<Node>(entity).unwrap().width
+}
+
+fn height(app: &App, entity: Entity) -> Val
+{
+    app.world().get::<Node>(entity).unwrap().height
+}
+
+fn unsaved(app: &App) -> bool
+{
+    app.world().resource::<Unsaved>().0
+}
+
+/// Drags the `Val` field with `name` and waits for the node to change.
+fn drag_val(app: &mut App, entity: Entity, name: &str, delta: f32)
+{
+    let prev = app.world().get::<Node>(entity).unwrap().clone();
+    let field = val_field(app.world(), name);
+    drag(app, field, Vec2::new(delta, 0.));
+    update_until(app, |w| *w.get::<Node>(entity).unwrap() != prev);
+}
+
+fn drag_width(app: &mut App, entity: Entity, delta: f32)
+{
+    drag_val(app, entity, "width", delta);
+}
+
+fn undo(app: &mut App)
+{
+    app.world_mut()
+        .commands()
+        .queue(UndoEditor { file: CobFile::try_new(FILE).unwrap() });
+    app.update();
+    app.update();
+}
+
+fn redo(app: &mut App)
+{
+    app.world_mut()
+        .commands()
+        .queue(RedoEditor { file: CobFile::try_new(FILE).unwrap() });
+    app.update();
+    app.update();
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn history_merges_rapid_patches()
+{
+    let (mut app, entity) = prepare_history_app();
+
+    // Patches to the same value inside the merge window are undone together.
+    drag_width(&mut app, entity, 10.);
+    drag_width(&mut app, entity, 10.);
+    assert_eq!(width(&app, entity), Val::Px(110.));
+    assert!(unsaved(&app));
+
+    undo(&mut app);
+    assert_eq!(width(&app, entity), Val::Px(100.));
+    assert!(!unsaved(&app));
+
+    redo(&mut app);
+    assert_eq!(width(&app, entity), Val::Px(110.));
+    assert!(unsaved(&app));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn history_does_not_merge_across_save()
+{
+    let (mut app, entity) = prepare_history_app();
+
+    drag_width(&mut app, entity, 10.);
+    app.world_mut().commands().queue(SaveEditor);
+    app.update();
+    assert!(!unsaved(&app));
+
+    // The save splits rapid patches into separate entries.
+    drag_width(&mut app, entity, 10.);
+    assert_eq!(width(&app, entity), Val::Px(110.));
+    assert!(unsaved(&app));
+
+    // Undoing to the saved state clears the unsaved status.
+    undo(&mut app);
+    assert_eq!(width(&app, entity), Val::Px(105.));
+    assert!(!unsaved(&app));
+
+    // Undoing past the saved state makes the file unsaved again.
+    undo(&mut app);
+    assert_eq!(width(&app, entity), Val::Px(100.));
+    assert!(unsaved(&app));
+
+    redo(&mut app);
+    assert_eq!(width(&app, entity), Val::Px(105.));
+    assert!(!unsaved(&app));
+
+    redo(&mut app);
+    assert_eq!(width(&app, entity), Val::Px(110.));
+    assert!(unsaved(&app));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn history_drops_oldest_entries()
+{
+    let (mut app, entity) = prepare_history_app();
+
+    // Alternate fields so the patches aren't merged.
+    // - The history keeps 256 entries.
+    for _ in 0..129 {
+        drag_val(&mut app, entity, "width", 2.);
+        drag_val(&mut app, entity, "height", 2.);
+    }
+    assert_eq!(width(&app, entity), Val::Px(229.));
+    assert_eq!(height(&app, entity), Val::Px(229.));
+
+    // The first two patches were dropped.
+    for _ in 0..300 {
+        undo(&mut app);
+    }
+    assert_eq!(width(&app, entity), Val::Px(101.));
+    assert_eq!(height(&app, entity), Val::Px(101.));
+
+    // The saved state was dropped, so the file stays unsaved.
+    assert!(unsaved(&app));
+
+    for _ in 0..256 {
+        redo(&mut app);
+    }
+    assert_eq!(width(&app, entity), Val::Px(229.));
+    assert_eq!(height(&app, entity), Val::Px(229.));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,130 @@
+//! Driving the editor over the remote protocol.
+
+use std::net::{Ipv4Addr, SocketAddr};
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use bevy_cobweb_ui::editor::*;
+use bevy_cobweb_ui::prelude::*;
+
+use crate::cob::helpers::prepare_headless_app_with;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+const FILE: &str = "editor_remote.cob";
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn prepare_remote_app() -> App
+{
+    let mut app = prepare_headless_app_with(CobEditorRemotePlugin {
+        addr: SocketAddr::from((Ipv4Addr::LOCALHOST, 0)),
+        ..default()
+    });
+    app.load(FILE);
+    app
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Pipelines an edit and a read of the file. Returns the file content and whether it is unsaved.
+fn edit_and_read(client: &mut EditorRemoteClient, edit: EditorClientMessage) -> std::io::Result<(String, bool)>
+{
+    client.send(&edit)?;
+    client.send(&EditorClientMessage::GetFile { file: FILE.into() })?;
+
+    let mut responses = Vec::default();
+    while responses.len() < 2 {
+        let message = client.recv()?;
+        if !message.is_notification() {
+            responses.push(message);
+        }
+    }
+    assert_eq!(responses[0], EditorServerMessage::Accepted);
+    let EditorServerMessage::File { file, content, unsaved } = &responses[1] else {
+        panic!("expected file, got {:?}", responses[1]);
+    };
+    assert_eq!(file, FILE);
+
+    Ok((content.clone(), *unsaved))
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn run_client_script(addr: SocketAddr) -> std::io::Result<()>
+{
+    let mut client = EditorRemoteClient::connect(addr)?;
+
+    // Wait for the file to be loaded into the editor.
+    loop {
+        let EditorServerMessage::Files { files } = client.request(&EditorClientMessage::ListFiles)? else {
+            panic!("expected file list");
+        };
+        if files.iter().any(|f| f == FILE) {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    // The read must see the edit.
+    let (content, unsaved) = edit_and_read(&mut client, EditorClientMessage::SetLoadable {
+        file: FILE.into(),
+        path: "root".into(),
+        loadable: "BackgroundColor(#FF0000)".into(),
+    })?;
+    assert!(content.contains("BackgroundColor(#FF0000)"), "{content}");
+    assert!(content.contains("FlexNode"), "{content}");
+    assert!(unsaved);
+
+    // Structural edits can be undone and redone.
+    let (content, unsaved) = edit_and_read(&mut client, EditorClientMessage::Undo { file: FILE.into() })?;
+    assert!(!content.contains("BackgroundColor"), "{content}");
+    assert!(content.contains("FlexNode"), "{content}");
+    assert!(!unsaved);
+
+    let (content, unsaved) = edit_and_read(&mut client, EditorClientMessage::Redo { file: FILE.into() })?;
+    assert!(content.contains("BackgroundColor(#FF0000)"), "{content}");
+    assert!(content.contains("FlexNode"), "{content}");
+    assert!(unsaved);
+
+    Ok(())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn remote_round_trip()
+{
+    let mut app = prepare_remote_app();
+    let addr = app.world().resource::<CobEditorRemoteAddr>().0;
+
+    let client = std::thread::spawn(move || run_client_script(addr));
+    let start = Instant::now();
+    while !client.is_finished() {
+        assert!(start.elapsed() < Duration::from_secs(30), "timed out waiting for editor remote client");
+        app.update();
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    client.join().unwrap().unwrap();
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn remote_requires_opt_in_for_non_loopback()
+{
+    // Non-loopback addresses are rejected by default.
+    let app = prepare_headless_app_with(CobEditorRemotePlugin {
+        addr: SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0)),
+        ..default()
+    });
+    assert!(app.world().get_resource::<CobEditorRemoteAddr>().is_none());
+
+    let app = prepare_headless_app_with(CobEditorRemotePlugin {
+        addr: SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0)),
+        allow_remote: true,
+    });
+    assert!(app.world().get_resource::<CobEditorRemoteAddr>().is_some());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,98 @@
+//! Resolving where the editor saves files.
+
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use bevy_cobweb_ui::editor::*;
+use bevy_cobweb_ui::prelude::*;
+
+use crate::cob::helpers::{prepare_headless_app, prepare_headless_app_with, update_until};
+use crate::editor_structure::{read_file, remote_request};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn file(name: &str) -> CobFile
+{
+    CobFile::try_new(name).unwrap()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn save_targets_resolve()
+{
+    let mut app = prepare_headless_app();
+    let mut targets = app.world_mut().resource_mut::<CobEditorSaveTargets>();
+
+    // Files in the default asset source are saved to the asset directory.
+    let main = targets.resolve(&file("ui/main.cob")).unwrap();
+    assert!(main.ends_with("tests/assets/ui/main.cob"), "{main:?}");
+
+    // Files in other sources have no save target by default.
+    assert_eq!(targets.resolve(&file("embedded://my_ui/menu.cob")), None);
+    assert!(!targets.is_editable(&file("embedded://my_ui/menu.cob")));
+
+    // The longest matching prefix is used.
+    targets
+        .add_prefix("embedded://my_ui/", "/ui")
+        .add_prefix("embedded://my_ui/sub/", "/sub");
+    assert_eq!(targets.resolve(&file("embedded://my_ui/menu.cob")), Some(PathBuf::from("/ui/menu.cob")));
+    assert_eq!(targets.resolve(&file("embedded://my_ui/sub/a.cob")), Some(PathBuf::from("/sub/a.cob")));
+    assert!(targets.is_editable(&file("embedded://my_ui/menu.cob")));
+
+    // Adding a prefix again replaces its directory.
+    targets.add_prefix("embedded://my_ui/", "/ui2");
+    assert_eq!(targets.resolve(&file("embedded://my_ui/menu.cob")), Some(PathBuf::from("/ui2/menu.cob")));
+
+    // The resolver takes precedence, and files it doesn't resolve fall back to prefixes and the asset directory.
+    targets.set_resolver(|file| file.as_str().ends_with("a.cob").then(|| PathBuf::from("/custom.cob")));
+    assert_eq!(targets.resolve(&file("embedded://my_ui/sub/a.cob")), Some(PathBuf::from("/custom.cob")));
+    assert_eq!(targets.resolve(&file("embedded://my_ui/menu.cob")), Some(PathBuf::from("/ui2/menu.cob")));
+    assert_eq!(targets.resolve(&file("ui/main.cob")), Some(main));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn save_targets_scratch_file()
+{
+    const FILE: &str = "editor_structure.cob";
+
+    let dir = std::env::temp_dir().join(format!("cob_editor_save_targets_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let target = dir.join("saved.cob");
+    let scratch = dir.join(".__cob_editor_scratch");
+
+    // Leave a stale scratch file from a failed save. It should be overwritten.
+    std::fs::write(&scratch, "stale").unwrap();
+
+    let mut app = prepare_headless_app_with(CobEditorRemotePlugin {
+        addr: SocketAddr::from((Ipv4Addr::LOCALHOST, 0)),
+        ..default()
+    });
+    let resolved = target.clone();
+    app.set_editor_save_resolver(move |_| Some(resolved.clone()));
+    app.load(FILE);
+    update_until(&mut app, |w| *w.resource::<State<LoadState>>() == LoadState::Done);
+
+    let response = remote_request(&mut app, EditorClientMessage::SetLoadable {
+        file: FILE.into(),
+        path: "root".into(),
+        loadable: "BackgroundColor(#00FF00)".into(),
+    });
+    assert_eq!(response, EditorServerMessage::Accepted);
+    assert_eq!(remote_request(&mut app, EditorClientMessage::Save), EditorServerMessage::Accepted);
+    app.update();
+
+    // The file is written to the target through a scratch file next to it.
+    let (content, unsaved) = read_file(&mut app, FILE);
+    assert!(!unsaved);
+    assert_eq!(std::fs::read_to_string(&target).unwrap(), content);
+    assert!(content.contains("BackgroundColor(#00FF00)"), "{content}");
+    assert!(!scratch.exists());
+    assert!(!PathBuf::from("tests/assets/.__cob_editor_scratch").exists());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,50 @@
+//! Ranking of editor search results.
+
+use bevy_cobweb_ui::editor::fuzzy_score;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Sorts the candidates that match `query` by descending score.
+fn rank<'a>(query: &str, candidates: &[&'a str]) -> Vec<&'a str>
+{
+    let mut scored: Vec<(i32, &str)> = candidates
+        .iter()
+        .filter_map(|c| Some((fuzzy_score(query, c)?, *c)))
+        .collect();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, c)| c).collect()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn fuzzy_score_matches()
+{
+    assert!(fuzzy_score("bgc", "BackgroundColor").is_some());
+    assert!(fuzzy_score("ab", "ba").is_none());
+    assert!(fuzzy_score("xyz", "BackgroundColor").is_none());
+    assert!(fuzzy_score("", "BackgroundColor").is_none());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn fuzzy_score_ranking()
+{
+    // Consecutive matches rank higher.
+    assert_eq!(
+        rank("bg", &["debugging", "BackgroundColor", "Border", "BgColor"]),
+        vec!["BgColor", "BackgroundColor", "debugging"]
+    );
+
+    // Matches at the start of words rank higher.
+    assert_eq!(
+        rank("main", &["my_animation.cob", "domain.cob", "main_menu.cob"]),
+        vec!["main_menu.cob", "domain.cob", "my_animation.cob"]
+    );
+
+    // Shorter candidates rank higher.
+    assert_eq!(rank("node", &["nodes", "node"]), vec!["node", "nodes"]);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,125 @@
+//! Persisting the editor's view state between sessions.
+
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use bevy_cobweb_ui::editor::*;
+use bevy_cobweb_ui::prelude::*;
+use serde::Deserialize;
+
+use crate::cob::helpers::{prepare_headless_app_at, update_until};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+const FILE: &str = "stack.cob";
+
+const CONTENT: &str = "#scenes
+\"root\"
+    \"a\"
+        \"x\"
+    \"b\"
+";
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Mirrors the saved view state file.
+#[derive(Deserialize, Debug, PartialEq)]
+struct SavedStack
+{
+    file: Option<String>,
+    scroll: Vec<(String, f32)>,
+    folded: Vec<(String, String)>,
+    selected: Option<(String, String)>,
+}
+
+fn prepare_stack_dir() -> PathBuf
+{
+    let dir = std::env::temp_dir().join(format!("cob_editor_stack_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join(FILE), CONTENT).unwrap();
+    dir
+}
+
+/// Makes an app that persists view state in `dir`.
+fn prepare_stack_app(dir: &Path) -> App
+{
+    let mut app = prepare_headless_app_at(dir.to_string_lossy(), EditorStackSettingsPlugin);
+    app.load(FILE);
+    update_until(&mut app, |w| *w.resource::<State<LoadState>>() == LoadState::Done);
+    app
+}
+
+/// Enables view state persistence before the editor loads it on startup.
+struct EditorStackSettingsPlugin;
+
+impl Plugin for EditorStackSettingsPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.insert_resource(EditorStackSettings { persist: true });
+    }
+}
+
+fn select(app: &mut App, path: &str)
+{
+    app.world_mut()
+        .commands()
+        .queue(SelectSceneNode { scene_ref: SceneRef::from((FILE, path)) });
+    app.update();
+}
+
+/// Waits for the view state file to contain `saved`.
+fn wait_for_saved(app: &mut App, stack_file: &Path, saved: &SavedStack)
+{
+    update_until(app, |_| {
+        std::fs::read_to_string(stack_file)
+            .ok()
+            .and_then(|content| ron::de::from_str::<SavedStack>(&content).ok())
+            .is_some_and(|s| s.file == saved.file && s.folded == saved.folded && s.selected == saved.selected)
+    });
+}
+
+fn scene(path: &str) -> (String, String)
+{
+    (FILE.into(), path.into())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn editor_stack_round_trip()
+{
+    let dir = prepare_stack_dir();
+    let stack_file = dir.join(".cob_editor_stack.ron");
+
+    // Selecting a node saves the view state.
+    let mut app = prepare_stack_app(&dir);
+    select(&mut app, "root::b");
+    wait_for_saved(&mut app, &stack_file, &SavedStack {
+        file: Some(FILE.into()),
+        scroll: vec![],
+        folded: vec![],
+        selected: Some(scene("root::b")),
+    });
+    drop(app);
+
+    // Fold some nodes by hand, then restore the view state in a new session.
+    let saved = format!(
+        "(file: Some({FILE:?}), scroll: [], folded: [({FILE:?}, \"root::a\"), ({FILE:?}, \"root::b\")], \
+        selected: Some(({FILE:?}, \"root::b\")))"
+    );
+    std::fs::write(&stack_file, saved).unwrap();
+    let mut app = prepare_stack_app(&dir);
+
+    // Selecting a node unfolds its ancestors and keeps the other restored folds.
+    select(&mut app, "root::a::x");
+    wait_for_saved(&mut app, &stack_file, &SavedStack {
+        file: Some(FILE.into()),
+        scroll: vec![],
+        folded: vec![scene("root::b")],
+        selected: Some(scene("root::a::x")),
+    });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,163 @@
+//! Structural edits in the editor, applied and undone.
+
+use std::net::{Ipv4Addr, SocketAddr};
+use std::time::{Duration, Instant};
+
+use bevy::ecs::world::Command;
+use bevy::prelude::*;
+use bevy_cobweb_ui::editor::*;
+use bevy_cobweb_ui::prelude::*;
+
+use crate::cob::helpers::{prepare_headless_app_with, update_until};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+const FILE: &str = "editor_structure.cob";
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn prepare_structure_app() -> App
+{
+    let mut app = prepare_headless_app_with(CobEditorRemotePlugin {
+        addr: SocketAddr::from((Ipv4Addr::LOCALHOST, 0)),
+        ..default()
+    });
+    app.load(FILE);
+    update_until(&mut app, |w| *w.resource::<State<LoadState>>() == LoadState::Done);
+    app
+}
+
+fn file() -> CobFile
+{
+    CobFile::try_new(FILE).unwrap()
+}
+
+fn scene_ref(path: &str) -> SceneRef
+{
+    SceneRef::from((FILE, path))
+}
+
+/// Sends a request to the editor over the remote protocol and returns the response.
+pub fn remote_request(app: &mut App, message: EditorClientMessage) -> EditorServerMessage
+{
+    let addr = app.world().resource::<CobEditorRemoteAddr>().0;
+    let client = std::thread::spawn(move || {
+        let mut client = EditorRemoteClient::connect(addr)?;
+        client.request(&message)
+    });
+    let start = Instant::now();
+    while !client.is_finished() {
+        assert!(start.elapsed() < Duration::from_secs(30), "timed out waiting for editor remote response");
+        app.update();
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    client.join().unwrap().unwrap()
+}
+
+/// Reads the editor's current text of a file. Also returns whether the file is unsaved.
+pub fn read_file(app: &mut App, file: &str) -> (String, bool)
+{
+    match remote_request(app, EditorClientMessage::GetFile { file: file.into() }) {
+        EditorServerMessage::File { content, unsaved, .. } => (content, unsaved),
+        message => panic!("expected file, got {message:?}"),
+    }
+}
+
+fn run(app: &mut App, command: impl Command)
+{
+    app.world_mut().commands().queue(command);
+    app.update();
+}
+
+/// Applies `command`, then checks that undoing restores the file and redoing restores the edit.
+///
+/// Returns the edited file.
+fn apply_and_undo(app: &mut App, command: impl Command) -> String
+{
+    let original = read_file(app, FILE).0;
+    run(app, command);
+    let edited = read_file(app, FILE).0;
+    assert_ne!(edited, original);
+
+    run(app, UndoEditor { file: file() });
+    assert_eq!(read_file(app, FILE).0, original);
+
+    run(app, RedoEditor { file: file() });
+    assert_eq!(read_file(app, FILE).0, edited);
+
+    run(app, UndoEditor { file: file() });
+    edited
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn structure_node_entries()
+{
+    let mut app = prepare_structure_app();
+
+    let edited = apply_and_undo(
+        &mut app,
+        InsertLoadable { scene_ref: scene_ref("root::b"), loadable_name: "BackgroundColor".into() },
+    );
+    assert!(edited.contains("\"b\"\n        BackgroundColor"), "{edited}");
+
+    let edited = apply_and_undo(
+        &mut app,
+        RemoveLoadable { scene_ref: scene_ref("root"), loadable_name: "BackgroundColor".into() },
+    );
+    assert!(!edited.contains("BackgroundColor"), "{edited}");
+
+    let edited = apply_and_undo(
+        &mut app,
+        MoveLoadable { scene_ref: scene_ref("root"), loadable_name: "BackgroundColor".into(), offset: -1 },
+    );
+    assert!(edited.find("BackgroundColor") < edited.find("FlexNode{width:100px}"), "{edited}");
+
+    // Child nodes are kept when a node's entries are replaced.
+    assert!(edited.contains("\"a\"\n        FlexNode{width:10px}"), "{edited}");
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn structure_commands()
+{
+    let mut app = prepare_structure_app();
+
+    let edited = apply_and_undo(
+        &mut app,
+        MoveLoadable { scene_ref: scene_ref("#commands"), loadable_name: "LoadFonts".into(), offset: -1 },
+    );
+    assert!(edited.find("LoadFonts") < edited.find("LoadImages"), "{edited}");
+
+    let edited = apply_and_undo(
+        &mut app,
+        RemoveLoadable { scene_ref: scene_ref("#commands"), loadable_name: "LoadImages".into() },
+    );
+    assert!(!edited.contains("LoadImages"), "{edited}");
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn structure_nodes()
+{
+    let mut app = prepare_structure_app();
+
+    let edited = apply_and_undo(&mut app, InsertSceneNode { parent: scene_ref("root"), name: "c".into() });
+    assert!(edited.find("\"c\"") > edited.find("\"b\""), "{edited}");
+
+    let edited = apply_and_undo(&mut app, RemoveSceneNode { scene_ref: scene_ref("root::a") });
+    assert!(!edited.contains("\"a\""), "{edited}");
+    assert!(!edited.contains("10px"), "{edited}");
+
+    let edited = apply_and_undo(&mut app, RenameSceneNode { scene_ref: scene_ref("root::a"), name: "d".into() });
+    assert!(!edited.contains("\"a\""), "{edited}");
+    assert!(edited.contains("\"d\"\n        FlexNode{width:10px}"), "{edited}");
+
+    let edited = apply_and_undo(&mut app, MoveSceneNode { scene_ref: scene_ref("root::b"), offset: -1 });
+    assert!(edited.find("\"b\"") < edited.find("\"a\""), "{edited}");
+}
+
+//-------------------------------------------------------------------------------------------------------------------
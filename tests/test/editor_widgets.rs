@@ -0,0 +1,118 @@
+//! Submitting edits with the editor's default widgets.
+
+use bevy::ecs::system::RunSystemOnce;
+use bevy::picking::pointer::{Location, PointerButton, PointerId};
+use bevy::prelude::*;
+use bevy::render::camera::NormalizedRenderTarget;
+use bevy_cobweb_ui::editor::*;
+use bevy_cobweb_ui::prelude::*;
+
+use crate::cob::helpers::{prepare_headless_app, update_until};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+const FILE: &str = "editor_widgets.cob";
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Loads the test scene and opens its file in the editor. Returns the scene's root entity.
+pub fn prepare_editor_app() -> (App, Entity)
+{
+    let mut app = prepare_headless_app();
+    app.load(FILE);
+    update_until(&mut app, |w| *w.resource::<State<LoadState>>() == LoadState::Done);
+
+    let entity = app
+        .world_mut()
+        .run_system_once(|mut c: Commands, mut s: ResMut<SceneLoader>| {
+            let mut entity = Entity::PLACEHOLDER;
+            c.ui_root().load_scene_and_edit((FILE, "root"), &mut s, |l| {
+                entity = l.id();
+            });
+            c.queue(SelectSceneNode { scene_ref: SceneRef::from((FILE, "root")) });
+            entity
+        })
+        .unwrap();
+    update_until(&mut app, |w| find_text(w, "width:").is_some() && find_text(w, "r").is_some());
+
+    (app, entity)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub fn find_text(world: &World, text: &str) -> Option<Entity>
+{
+    world
+        .iter_entities()
+        .find(|e| e.get::<Text>().is_some_and(|t| t.0 == text))
+        .map(|e| e.id())
+}
+
+/// Gets the `idx`-th child of `entity`.
+pub fn child(world: &World, entity: Entity, idx: usize) -> Entity
+{
+    world.get::<Children>(entity).unwrap()[idx]
+}
+
+pub fn parent(world: &World, entity: Entity) -> Entity
+{
+    world.get::<Parent>(entity).unwrap().get()
+}
+
+/// Gets the number field of the `Val` widget for a field (e.g. `width`).
+pub fn val_field(world: &World, name: &str) -> Entity
+{
+    // field -> content -> val row -> number field
+    let field = parent(world, find_text(world, &format!("{name}:")).unwrap());
+    child(world, child(world, child(world, field, 1), 0), 0)
+}
+
+pub fn drag(app: &mut App, target: Entity, delta: Vec2)
+{
+    let location = Location { target: NormalizedRenderTarget::Image(Handle::default()), position: Vec2::ZERO };
+    let event = Drag { button: PointerButton::Primary, distance: delta, delta };
+    app.world_mut()
+        .trigger_targets(Pointer::new(target, PointerId::Mouse, location, event), target);
+    app.update();
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn submit_val_drag()
+{
+    let (mut app, entity) = prepare_editor_app();
+    assert_eq!(app.world().get::<Node>(entity).unwrap().width, Val::Px(100.));
+
+    let number_field = val_field(app.world(), "width");
+    drag(&mut app, number_field, Vec2::new(10., 0.));
+    update_until(&mut app, |w| w.get::<Node>(entity).unwrap().width != Val::Px(100.));
+    let node = app.world().get::<Node>(entity).unwrap();
+    assert_eq!(node.width, Val::Px(105.));
+    assert_eq!(node.height, Val::Px(100.));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn submit_color_keeps_color_space()
+{
+    let (mut app, entity) = prepare_editor_app();
+    let original = Color::hsla(0., 1., 0.5, 1.);
+    assert_eq!(app.world().get::<BackgroundColor>(entity).unwrap().0, original);
+
+    // Drag the blue channel.
+    // - color channel -> value
+    let world = app.world();
+    let channel = parent(world, find_text(world, "b").unwrap());
+    let value = child(world, channel, 1);
+
+    drag(&mut app, value, Vec2::new(100., 0.));
+    update_until(&mut app, |w| w.get::<BackgroundColor>(entity).unwrap().0 != original);
+    let color = app.world().get::<BackgroundColor>(entity).unwrap().0;
+    let Color::Hsla(hsla) = color else { panic!("expected Hsla, got {color:?}") };
+    let expected = Srgba::new(1., 0., 0.5, 1.);
+    assert!(Srgba::from(hsla).to_vec4().distance(expected.to_vec4()) < 1e-4, "{hsla:?}");
+}
+
+//-------------------------------------------------------------------------------------------------------------------
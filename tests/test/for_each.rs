@@ -0,0 +1,110 @@
+//! Diffing of `for_each` rows.
+
+use std::sync::{Arc, Mutex};
+
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+use bevy_cobweb_ui::prelude::*;
+
+use crate::cob::helpers::{prepare_headless_app, update_until};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+type Item = (u32, &'static str);
+
+#[derive(ReactComponent, Deref)]
+struct Items(Vec<Item>);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Returns the rows under `parent` with their items.
+fn rows(app: &App, parent: Entity) -> Vec<(Entity, Item)>
+{
+    let world = app.world();
+    let Some(children) = world.get::<Children>(parent) else { return vec![] };
+    children
+        .iter()
+        .filter_map(|row| {
+            let item = world.get::<React<ForEachItem<Item>>>(*row)?;
+            Some((*row, item.get().0))
+        })
+        .collect()
+}
+
+fn set_items(app: &mut App, source: Entity, new: Vec<Item>)
+{
+    app.world_mut()
+        .run_system_once(move |mut c: Commands, mut items: ReactiveMut<Items>| {
+            items.get_mut(&mut c, source).unwrap().0 = new.clone();
+        })
+        .unwrap();
+    app.update();
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn for_each_diffing()
+{
+    let mut app = prepare_headless_app();
+    app.load("for_each.cob");
+    update_until(&mut app, |w| *w.resource::<State<LoadState>>() == LoadState::Done);
+
+    // The edit callback records the keys of new rows.
+    let edited = Arc::new(Mutex::new(Vec::<u32>::default()));
+    let edited_clone = edited.clone();
+    let (source, parent) = app
+        .world_mut()
+        .run_system_once(move |mut c: Commands| {
+            let source = c.spawn_empty().id();
+            c.react()
+                .insert(source, Items(vec![(1, "a"), (2, "b"), (3, "c")]));
+            let parent = c.spawn(Node::default()).id();
+            let edited = edited_clone.clone();
+            c.ui_builder(parent)
+                .for_each::<Items, _, _>(source, ("for_each.cob", "row"), |i| i.0, move |_, item| {
+                    edited.lock().unwrap().push(item.0);
+                });
+            (source, parent)
+        })
+        .unwrap();
+    update_until(&mut app, |w| w.get::<Children>(parent).is_some_and(|c| c.len() == 3));
+    let take_edited = || std::mem::take(&mut *edited.lock().unwrap());
+
+    let initial = rows(&app, parent);
+    assert_eq!(initial.iter().map(|(_, i)| *i).collect::<Vec<_>>(), vec![(1, "a"), (2, "b"), (3, "c")]);
+    assert_eq!(take_edited(), vec![1, 2, 3]);
+    let row = |key: u32| initial.iter().find(|(_, i)| i.0 == key).unwrap().0;
+
+    // Inserting spawns and edits only the new row.
+    set_items(&mut app, source, vec![(1, "a"), (4, "d"), (2, "b"), (3, "c")]);
+    let current = rows(&app, parent);
+    assert_eq!(current.iter().map(|(_, i)| i.0).collect::<Vec<_>>(), vec![1, 4, 2, 3]);
+    assert_eq!(current[0].0, row(1));
+    assert_eq!(current[2].0, row(2));
+    assert_eq!(current[3].0, row(3));
+    assert_eq!(take_edited(), vec![4]);
+    let row_4 = current[1].0;
+
+    // Removing despawns the row.
+    set_items(&mut app, source, vec![(1, "a"), (4, "d"), (3, "c")]);
+    let current = rows(&app, parent);
+    assert_eq!(current.iter().map(|(_, i)| i.0).collect::<Vec<_>>(), vec![1, 4, 3]);
+    assert!(app.world().get_entity(row(2)).is_err());
+    assert!(take_edited().is_empty());
+
+    // Reordering moves the existing rows.
+    set_items(&mut app, source, vec![(3, "c"), (1, "a"), (4, "d")]);
+    let current = rows(&app, parent);
+    assert_eq!(current.iter().map(|(e, _)| *e).collect::<Vec<_>>(), vec![row(3), row(1), row_4]);
+    assert!(take_edited().is_empty());
+
+    // Changing an item updates the row's item without respawning or editing it.
+    set_items(&mut app, source, vec![(3, "c"), (1, "z"), (4, "d")]);
+    let current = rows(&app, parent);
+    assert_eq!(current[1], (row(1), (1, "z")));
+    assert!(take_edited().is_empty());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
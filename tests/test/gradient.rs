@@ -0,0 +1,102 @@
+//! Gradient stop positions and interpolation.
+
+use bevy::prelude::*;
+use bevy_cobweb_ui::prelude::*;
+use bevy_cobweb_ui::sickle::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn gradient(stops: &[f32], positions: &[f32]) -> UiGradient
+{
+    UiGradient {
+        stops: stops.iter().map(|v| Color::srgb(*v, *v, *v)).collect(),
+        positions: positions.to_vec(),
+        ..default()
+    }
+}
+
+fn assert_stops(gradient: &UiGradient, expected: &[f32])
+{
+    let stops: Vec<f32> = gradient.stops.iter().map(|c| c.to_srgba().red).collect();
+    assert_eq!(stops.len(), expected.len(), "stops {stops:?}, expected {expected:?}");
+    for (stop, expected) in stops.iter().zip(expected) {
+        assert!((stop - expected).abs() < 1e-4, "stops {stops:?}, expected {expected:?}");
+    }
+}
+
+fn assert_positions(positions: &[f32], expected: &[f32])
+{
+    assert_eq!(positions.len(), expected.len(), "positions {positions:?}, expected {expected:?}");
+    for (position, expected) in positions.iter().zip(expected) {
+        assert!((position - expected).abs() < 1e-4, "positions {positions:?}, expected {expected:?}");
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn gradient_resolved_positions()
+{
+    assert_positions(&gradient(&[], &[]).resolved_positions(), &[]);
+    // A single stop sits at the start.
+    assert_positions(&gradient(&[0.], &[]).resolved_positions(), &[0.]);
+    assert_positions(&gradient(&[0., 1.], &[]).resolved_positions(), &[0., 1.]);
+    assert_positions(&gradient(&[0., 0.5, 1., 1., 0.], &[]).resolved_positions(), &[0., 0.25, 0.5, 0.75, 1.]);
+
+    // Matching positions are used as-is.
+    assert_positions(&gradient(&[0., 1., 0.], &[0.2, 0.3, 0.9]).resolved_positions(), &[0.2, 0.3, 0.9]);
+
+    // Mismatched positions are replaced with even spacing.
+    assert_positions(&gradient(&[0., 1., 0.], &[0.2, 0.3]).resolved_positions(), &[0., 0.5, 1.]);
+    assert_positions(&gradient(&[0., 1.], &[0.2, 0.3, 0.9]).resolved_positions(), &[0., 1.]);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn gradient_lerp_unequal_stops()
+{
+    let from = gradient(&[0., 1.], &[]);
+    let to = gradient(&[1., 0., 0.5], &[]);
+
+    // The shorter list is padded with its last stop.
+    let mid = from.lerp(to.clone(), 0.5);
+    assert_stops(&mid, &[0.5, 0.5, 0.75]);
+    assert!(mid.positions.is_empty());
+
+    assert_stops(&from.lerp(to.clone(), 0.), &[0., 1., 1.]);
+    assert_stops(&from.lerp(to.clone(), 1.), &[1., 0., 0.5]);
+    assert_stops(&to.lerp(from.clone(), 0.5), &[0.5, 0.5, 0.75]);
+
+    // Empty gradients take the other gradient's stops.
+    assert_stops(&UiGradient::default().lerp(to.clone(), 0.5), &[1., 0., 0.5]);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn gradient_lerp_positions()
+{
+    // Positions are resolved before lerping, so the result has one position per stop.
+    let from = gradient(&[0., 1.], &[]);
+    let to = gradient(&[1., 0., 0.5], &[0.1, 0.2, 0.4]);
+    let mid = from.lerp(to.clone(), 0.5);
+    assert_eq!(mid.stops.len(), 3);
+    assert_positions(&mid.positions, &[0.05, 0.6, 0.7]);
+    assert_positions(&mid.resolved_positions(), &[0.05, 0.6, 0.7]);
+
+    // Mismatched positions are resolved to even spacing first.
+    let from = gradient(&[0., 1., 0.], &[0.5]);
+    let to = gradient(&[0., 1., 0.], &[0., 0., 0.]);
+    assert_positions(&from.lerp(to, 0.5).positions, &[0., 0.25, 0.5]);
+
+    // Angles lerp, and the kind switches halfway.
+    let from = UiGradient { kind: GradientKind::Linear, angle: 0., ..gradient(&[0.], &[]) };
+    let to = UiGradient { kind: GradientKind::Radial, angle: 90., ..gradient(&[0.], &[]) };
+    let quarter = from.lerp(to.clone(), 0.25);
+    assert_eq!(quarter.kind, GradientKind::Linear);
+    assert!((quarter.angle - 22.5).abs() < 1e-4);
+    assert_eq!(from.lerp(to, 0.5).kind, GradientKind::Radial);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,155 @@
+//! Keyframe timeline animations.
+
+use bevy::prelude::*;
+use bevy_cobweb_ui::prelude::*;
+use bevy_cobweb_ui::sickle::{Ease, *};
+
+use crate::cob::helpers::prepare_headless_app;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Animates `FlexGrow` from `0` to `10` over one second.
+fn ramp(name: &str, loop_type: AnimationLoop) -> Keyframes<FlexGrow>
+{
+    Keyframes {
+        name: Some(name.into()),
+        frames: vec![
+            Keyframe { time: 0., value: 0., ease: Ease::Linear },
+            Keyframe { time: 1., value: 10., ease: Ease::Linear },
+        ],
+        loop_type,
+        // Paused so time only moves when seeking.
+        paused: true,
+        ..default()
+    }
+}
+
+fn spawn_animated(app: &mut App, keyframes: Keyframes<FlexGrow>) -> Entity
+{
+    let entity = app.world_mut().spawn(Node::default()).id();
+    keyframes.apply(entity, app.world_mut());
+    app.update();
+    entity
+}
+
+fn seek(app: &mut App, entity: Entity, name: &str, time: f32)
+{
+    let mut animations = app
+        .world_mut()
+        .get_mut::<KeyframeAnimations>(entity)
+        .unwrap();
+    assert!(animations.control(Some(name), KeyframesControl::Seek(time)));
+    app.update();
+}
+
+fn assert_flex_grow(app: &App, entity: Entity, expected: f32)
+{
+    let value = app.world().get::<Node>(entity).unwrap().flex_grow;
+    assert!((value - expected).abs() < 1e-4, "expected {expected}, got {value}");
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn keyframes_interpolate()
+{
+    let mut app = prepare_headless_app();
+    let mut keyframes = ramp("a", AnimationLoop::None);
+    // Out of order to check that keyframes are sorted.
+    keyframes.frames.insert(0, Keyframe { time: 2., value: 0., ease: Ease::InQuad });
+    keyframes.delay = 1.;
+    let entity = spawn_animated(&mut app, keyframes);
+    assert_flex_grow(&app, entity, 0.);
+
+    // The first keyframe is held during the delay.
+    seek(&mut app, entity, "a", 0.5);
+    assert_flex_grow(&app, entity, 0.);
+
+    seek(&mut app, entity, "a", 1.5);
+    assert_flex_grow(&app, entity, 5.);
+
+    // Easing is taken from the keyframe being interpolated towards.
+    seek(&mut app, entity, "a", 2.5);
+    assert_flex_grow(&app, entity, 10. - 10. * 0.25);
+
+    // The last keyframe is held after the animation finishes.
+    seek(&mut app, entity, "a", 5.);
+    assert_flex_grow(&app, entity, 0.);
+    let animations = app.world().get::<KeyframeAnimations>(entity).unwrap();
+    assert_eq!(animations.is_finished("a"), Some(true));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn keyframes_loop()
+{
+    let mut app = prepare_headless_app();
+
+    let entity = spawn_animated(&mut app, ramp("a", AnimationLoop::Continous));
+    seek(&mut app, entity, "a", 2.25);
+    assert_flex_grow(&app, entity, 2.5);
+
+    // Odd ping-pong iterations play in reverse.
+    let entity = spawn_animated(&mut app, ramp("a", AnimationLoop::PingPongContinous));
+    seek(&mut app, entity, "a", 1.25);
+    assert_flex_grow(&app, entity, 7.5);
+    seek(&mut app, entity, "a", 2.25);
+    assert_flex_grow(&app, entity, 2.5);
+
+    // The loop gap holds the end of an iteration.
+    let mut keyframes = ramp("a", AnimationLoop::Continous);
+    keyframes.loop_gap = 0.5;
+    let entity = spawn_animated(&mut app, keyframes);
+    seek(&mut app, entity, "a", 1.25);
+    assert_flex_grow(&app, entity, 10.);
+    seek(&mut app, entity, "a", 1.75);
+    assert_flex_grow(&app, entity, 2.5);
+
+    // Finite loops stop at the end, or reset to the start.
+    let entity = spawn_animated(&mut app, ramp("a", AnimationLoop::Times(2, false)));
+    seek(&mut app, entity, "a", 1.5);
+    assert_flex_grow(&app, entity, 5.);
+    seek(&mut app, entity, "a", 3.5);
+    assert_flex_grow(&app, entity, 10.);
+
+    let entity = spawn_animated(&mut app, ramp("a", AnimationLoop::Times(2, true)));
+    seek(&mut app, entity, "a", 3.5);
+    assert_flex_grow(&app, entity, 0.);
+
+    // Finite ping-pong loops stop at the end of the last iteration's direction.
+    let entity = spawn_animated(&mut app, ramp("a", AnimationLoop::PingPong(3)));
+    seek(&mut app, entity, "a", 5.);
+    assert_flex_grow(&app, entity, 10.);
+    let animations = app.world().get::<KeyframeAnimations>(entity).unwrap();
+    assert_eq!(animations.is_finished("a"), Some(true));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn keyframes_revert_own_animation()
+{
+    let mut app = prepare_headless_app();
+    let entity = spawn_animated(&mut app, ramp("a", AnimationLoop::None));
+    ramp("b", AnimationLoop::None).apply(entity, app.world_mut());
+    app.update();
+
+    // Only the animation added by the latest instruction is removed.
+    Keyframes::<FlexGrow>::revert(entity, app.world_mut());
+    let animations = app.world().get::<KeyframeAnimations>(entity).unwrap();
+    assert!(animations.elapsed("a").is_some());
+    assert!(animations.elapsed("b").is_none());
+
+    // The remaining animation still controls the attribute.
+    seek(&mut app, entity, "a", 0.5);
+    assert_flex_grow(&app, entity, 5.);
+
+    // Reverting the only animation reverts the attribute.
+    let entity = spawn_animated(&mut app, ramp("a", AnimationLoop::None));
+    Keyframes::<FlexGrow>::revert(entity, app.world_mut());
+    assert!(app.world().get::<KeyframeAnimations>(entity).is_none());
+    assert!(app.world().get::<Node>(entity).is_none());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -1,4 +1,32 @@
+mod animation_clock;
+mod animation_events;
+mod bindings;
+mod breakpoints;
 mod cob;
+mod despawn_animated;
+mod ease;
+#[cfg(feature = "editor")]
+mod editor_conflicts;
+#[cfg(feature = "editor")]
+mod editor_history;
+#[cfg(feature = "editor")]
+mod editor_remote;
+#[cfg(feature = "editor")]
+mod editor_save_targets;
+#[cfg(feature = "editor")]
+mod editor_search;
+#[cfg(feature = "editor")]
+mod editor_stack;
+#[cfg(feature = "editor")]
+mod editor_structure;
+#[cfg(feature = "editor")]
+mod editor_widgets;
+mod for_each;
+mod gradient;
+mod keyframes;
+mod node_transform;
+mod sprite_animation;
+mod stagger;
 //mod common;
 mod type_name;
 
@@ -0,0 +1,76 @@
+//! Visual transforms of UI nodes.
+
+use bevy::prelude::*;
+use bevy_cobweb_ui::prelude::*;
+
+use crate::cob::helpers::prepare_headless_app;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Spawns a camera and a 100x100 node at the top-left of the window.
+fn prepare_node_app() -> (App, Entity)
+{
+    let mut app = prepare_headless_app();
+    app.world_mut().spawn(Camera2d);
+    let entity = app
+        .world_mut()
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(0.),
+            top: Val::Px(0.),
+            width: Val::Px(100.),
+            height: Val::Px(100.),
+            ..default()
+        })
+        .id();
+    app.update();
+    (app, entity)
+}
+
+fn transform(app: &App, entity: Entity) -> Transform
+{
+    *app.world().get::<Transform>(entity).unwrap()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn node_transform_around_origin()
+{
+    let (mut app, entity) = prepare_node_app();
+
+    // Layout places the node's center.
+    let center = transform(&app, entity).translation;
+    assert_eq!(center.truncate(), Vec2::new(50., 50.));
+
+    // Scale and rotate around the top-left corner.
+    app.world_mut().entity_mut(entity).insert((
+        NodeScale(2.),
+        NodeRotation(90.),
+        NodeTransformOrigin { x: Val::Px(0.), y: Val::Px(0.) },
+    ));
+
+    // The pivot is (-50, -50) from the center. Scaled and rotated it moves to (100, -100), so the node shifts by
+    // (-150, 50) to keep the pivot in place.
+    let expected = center + Vec3::new(-150., 50., 0.);
+    for _ in 0..5 {
+        app.update();
+        let transform = transform(&app, entity);
+        assert!(transform.translation.distance(expected) < 1e-3, "{:?}", transform.translation);
+        assert_eq!(transform.scale, Vec3::new(2., 2., 1.));
+        let (axis, angle) = transform.rotation.to_axis_angle();
+        assert!(axis.distance(Vec3::Z) < 1e-4 && (angle - 90f32.to_radians()).abs() < 1e-4);
+    }
+
+    // Removing the components resets the transform.
+    app.world_mut()
+        .entity_mut(entity)
+        .remove::<(NodeScale, NodeRotation, NodeTransformOrigin)>();
+    app.update();
+    let transform = transform(&app, entity);
+    assert_eq!(transform.translation, center);
+    assert_eq!(transform.scale, Vec3::ONE);
+    assert_eq!(transform.rotation, Quat::IDENTITY);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,168 @@
+//! Sprite animation frame stepping and clip selection.
+
+use bevy::prelude::*;
+use bevy_cobweb_ui::prelude::*;
+use bevy_cobweb_ui::sickle::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn clip(name: &str, start: usize, end: usize, loop_type: AnimationLoop) -> SpriteClip
+{
+    SpriteClip { name: name.into(), start, end, loop_type, ..default() }
+}
+
+fn frames(clip: &SpriteClip, steps: std::ops::Range<u64>) -> Vec<(usize, bool, u32)>
+{
+    steps.map(|step| clip_frame(clip, step)).collect()
+}
+
+fn with_state(mut clip: SpriteClip, state: PseudoStateSelector) -> SpriteClip
+{
+    clip.state = Some(state);
+    clip
+}
+
+fn with_interaction(mut clip: SpriteClip, interaction: SpriteClipInteraction) -> SpriteClip
+{
+    clip.interaction = Some(interaction);
+    clip
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn clip_frame_reverse_range()
+{
+    // Frames 5, 4, 3, 2.
+    let once = clip("a", 5, 2, AnimationLoop::None);
+    assert_eq!(
+        frames(&once, 0..6),
+        vec![(5, false, 0), (4, false, 0), (3, false, 0), (2, false, 0), (2, true, 0), (2, true, 0)]
+    );
+
+    let continuous = clip("a", 5, 2, AnimationLoop::Continous);
+    assert_eq!(clip_frame(&continuous, 4), (5, false, 1));
+    assert_eq!(clip_frame(&continuous, 9), (4, false, 2));
+
+    let ping_pong = clip("a", 5, 2, AnimationLoop::PingPongContinous);
+    assert_eq!(
+        frames(&ping_pong, 0..8).iter().map(|(i, ..)| *i).collect::<Vec<_>>(),
+        vec![5, 4, 3, 2, 3, 4, 5, 4]
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn clip_frame_loop_times()
+{
+    // Stops on the last frame.
+    let hold = clip("a", 0, 2, AnimationLoop::Times(2, false));
+    assert_eq!(
+        frames(&hold, 2..8),
+        vec![(2, false, 0), (0, false, 1), (1, false, 1), (2, false, 1), (2, true, 2), (2, true, 2)]
+    );
+
+    // Resets to the first frame.
+    let reset = clip("a", 0, 2, AnimationLoop::Times(2, true));
+    assert_eq!(clip_frame(&reset, 5), (2, false, 1));
+    assert_eq!(clip_frame(&reset, 6), (0, true, 2));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn clip_frame_ping_pong_times()
+{
+    // One pass ends on the last frame.
+    let once = clip("a", 0, 3, AnimationLoop::PingPong(1));
+    assert_eq!(
+        frames(&once, 0..6),
+        vec![(0, false, 0), (1, false, 0), (2, false, 0), (3, false, 1), (3, true, 1), (3, true, 1)]
+    );
+
+    // Two passes end back on the first frame, which is shown for one step before finishing.
+    let twice = clip("a", 0, 3, AnimationLoop::PingPong(2));
+    assert_eq!(
+        frames(&twice, 0..9),
+        vec![
+            (0, false, 0),
+            (1, false, 0),
+            (2, false, 0),
+            (3, false, 1),
+            (2, false, 1),
+            (1, false, 1),
+            (0, false, 2),
+            (0, true, 2),
+            (0, true, 2),
+        ]
+    );
+
+    // Reverse ranges count passes the same way.
+    let reverse = clip("a", 3, 0, AnimationLoop::PingPong(2));
+    assert_eq!(clip_frame(&reverse, 3), (0, false, 1));
+    assert_eq!(clip_frame(&reverse, 7), (3, true, 2));
+
+    // Single-frame clips still terminate.
+    let single = clip("a", 4, 4, AnimationLoop::PingPong(2));
+    assert_eq!(frames(&single, 0..4), vec![(4, false, 0), (4, false, 1), (4, false, 2), (4, true, 2)]);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn select_clip_priority()
+{
+    let clips = vec![
+        clip("idle", 0, 0, AnimationLoop::None),
+        with_interaction(clip("press", 2, 2, AnimationLoop::None), SpriteClipInteraction::Press),
+        with_interaction(clip("hover", 1, 1, AnimationLoop::None), SpriteClipInteraction::Hover),
+        with_state(
+            clip("disabled", 3, 3, AnimationLoop::None),
+            PseudoStateSelector::State(PseudoState::Disabled),
+        ),
+        with_state(
+            clip("disabled_selected", 4, 4, AnimationLoop::None),
+            PseudoStateSelector::All(vec![
+                PseudoStateSelector::State(PseudoState::Disabled),
+                PseudoStateSelector::State(PseudoState::Selected),
+            ]),
+        ),
+    ];
+
+    // Interaction: press over hover, regardless of list order.
+    assert_eq!(select_clip(&clips, &[], None), Some(0));
+    assert_eq!(select_clip(&clips, &[], Some(&Interaction::None)), Some(0));
+    assert_eq!(select_clip(&clips, &[], Some(&Interaction::Hovered)), Some(2));
+    assert_eq!(select_clip(&clips, &[], Some(&Interaction::Pressed)), Some(1));
+
+    // Specificity beats interaction, and more specific selectors win.
+    assert_eq!(select_clip(&clips, &[PseudoState::Disabled], Some(&Interaction::Pressed)), Some(3));
+    assert_eq!(
+        select_clip(&clips, &[PseudoState::Selected, PseudoState::Disabled], None),
+        Some(4)
+    );
+    assert_eq!(select_clip(&clips, &[PseudoState::Selected], None), Some(0));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn select_clip_list_order()
+{
+    // Later clips win ties.
+    let clips = vec![clip("a", 0, 0, AnimationLoop::None), clip("b", 1, 1, AnimationLoop::None)];
+    assert_eq!(select_clip(&clips, &[], None), Some(1));
+
+    let hovers = vec![
+        with_interaction(clip("a", 0, 0, AnimationLoop::None), SpriteClipInteraction::Hover),
+        with_interaction(clip("b", 1, 1, AnimationLoop::None), SpriteClipInteraction::Hover),
+    ];
+    assert_eq!(select_clip(&hovers, &[], Some(&Interaction::Hovered)), Some(1));
+
+    // No clip matches.
+    assert_eq!(select_clip(&hovers, &[], None), None);
+    assert_eq!(select_clip(&[], &[], Some(&Interaction::Pressed)), None);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
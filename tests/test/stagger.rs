@@ -0,0 +1,106 @@
+//! Staggering the animations of child nodes.
+
+use bevy::prelude::*;
+use bevy_cobweb_ui::prelude::*;
+use bevy_cobweb_ui::sickle::{Ease, *};
+
+use crate::cob::helpers::prepare_headless_app;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn stagger(order: StaggerOrder) -> Stagger
+{
+    Stagger { attribute: "reveal".into(), interval: 0.1, order, exit_attribute: Some("exit".into()) }
+}
+
+/// An enter animation named `reveal` with a base delay of 0.05s.
+fn reveal() -> Animated<FlexGrow>
+{
+    Animated {
+        name: Some("reveal".into()),
+        idle: 1.,
+        enter_idle_with: Some(AnimationConfig::new(0.2, Ease::Linear, 0.05)),
+        ..default()
+    }
+}
+
+/// A keyframes exit animation named `exit`.
+fn exit(delay: f32) -> Keyframes<FlexShrink>
+{
+    Keyframes {
+        name: Some("exit".into()),
+        state: Some(PseudoState::Dying.into()),
+        frames: vec![Keyframe { time: 0.2, value: 0., ease: Ease::Linear }],
+        delay,
+        ..default()
+    }
+}
+
+fn spawn_child(app: &mut App, parent: Entity) -> Entity
+{
+    let child = app.world_mut().spawn(Node::default()).set_parent(parent).id();
+    reveal().apply(child, app.world_mut());
+    exit(0.).apply(child, app.world_mut());
+    child
+}
+
+/// Gets the `(reveal, exit)` delays of each child.
+fn delays(app: &App, children: &[Entity]) -> Vec<(f32, f32)>
+{
+    let round = |d: f32| (d * 1000.).round() / 1000.;
+    children
+        .iter()
+        .map(|child| {
+            let attrs = app.world().get::<NodeAttributes>(*child).unwrap();
+            let reveal = attrs
+                .animation_settings("reveal")
+                .and_then(|s| s.enter_idle_with.as_ref())
+                .unwrap()
+                .delay;
+            let keyframes = app.world().get::<KeyframeAnimations>(*child).unwrap();
+            (round(reveal), round(keyframes.delay("exit").unwrap()))
+        })
+        .collect()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn stagger_child_delays()
+{
+    let mut app = prepare_headless_app();
+    let parent = app.world_mut().spawn(Node::default()).id();
+    let mut children: Vec<Entity> = (0..3).map(|_| spawn_child(&mut app, parent)).collect();
+    stagger(StaggerOrder::Index).apply(parent, app.world_mut());
+    app.update();
+
+    // Delays are offset from each child's base delay. Exit delays are staggered in reverse.
+    assert_eq!(delays(&app, &children), vec![(0.05, 0.2), (0.15, 0.1), (0.25, 0.)]);
+
+    // Delays are recomputed from the base delays when the stagger changes.
+    stagger(StaggerOrder::ReverseIndex).apply(parent, app.world_mut());
+    app.update();
+    assert_eq!(delays(&app, &children), vec![(0.25, 0.), (0.15, 0.1), (0.05, 0.2)]);
+
+    stagger(StaggerOrder::FromPivot(1)).apply(parent, app.world_mut());
+    app.update();
+    assert_eq!(delays(&app, &children), vec![(0.15, 0.), (0.05, 0.1), (0.15, 0.)]);
+
+    // Delays are recomputed when children are added.
+    stagger(StaggerOrder::Index).apply(parent, app.world_mut());
+    children.push(spawn_child(&mut app, parent));
+    app.update();
+    assert_eq!(delays(&app, &children), vec![(0.05, 0.3), (0.15, 0.2), (0.25, 0.1), (0.35, 0.)]);
+
+    // A reloaded attribute's delay becomes the new base delay.
+    exit(0.5).apply(children[0], app.world_mut());
+    app.update();
+    assert_eq!(delays(&app, &children)[0], (0.05, 0.8));
+
+    // Reverting restores the base delays.
+    Stagger::revert(parent, app.world_mut());
+    app.update();
+    assert_eq!(delays(&app, &children), vec![(0.05, 0.5), (0.05, 0.), (0.05, 0.), (0.05, 0.)]);
+}
+
+//-------------------------------------------------------------------------------------------------------------------